@@ -1,3 +1,5 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
 use std::{collections::HashMap, sync::Arc};
 
 use std::hash::Hash;
@@ -57,23 +59,121 @@ pub enum CacheError {
 #[derive(Default)]
 pub struct Cache<K: Eq + PartialEq + Hash, V: Clone> {
     pub memory_cache: RwLock<HashMap<K, V>>,
+    in_flight: Mutex<HashMap<K, Arc<Mutex<()>>>>,
     nb_write: Mutex<i32>,
+    nb_saved_requests: AtomicUsize,
+    /// Entries older than the validity are loaded again, for data changing while the application
+    /// runs.
+    validity: Option<Duration>,
+    loaded_at: Mutex<HashMap<K, Instant>>,
 }
 
 impl<K: CacheKey, V: Clone> Cache<K, V> {
     pub fn new() -> Self {
         Self {
             memory_cache: Default::default(),
+            in_flight: Default::default(),
             nb_write: Mutex::new(0),
+            nb_saved_requests: AtomicUsize::new(0),
+            validity: None,
+            loaded_at: Default::default(),
         }
     }
 
     pub fn from(preloaded: HashMap<K, V>) -> Self {
         Self {
             memory_cache: RwLock::new(preloaded),
+            in_flight: Default::default(),
             nb_write: Mutex::new(0),
+            nb_saved_requests: AtomicUsize::new(0),
+            validity: None,
+            loaded_at: Default::default(),
         }
     }
+
+    /// Cache whose entries expire once loaded for longer than the validity.
+    pub fn with_validity(validity: Duration) -> Self {
+        Self {
+            validity: Some(validity),
+            ..Self::new()
+        }
+    }
+
+    /// Number of loads avoided because another caller was already loading the same key.
+    pub fn saved_requests(&self) -> usize {
+        self.nb_saved_requests.load(Ordering::Relaxed)
+    }
+
+    async fn in_flight_lock(&self, k: &K) -> Arc<Mutex<()>> {
+        self.in_flight
+            .lock()
+            .await
+            .entry(k.clone())
+            .or_default()
+            .clone()
+    }
+
+    /// Forgets the key lock once its last user releases it. Callers arriving while waiters retry
+    /// a failed load keep sharing the same lock instead of loading concurrently.
+    async fn release_in_flight_lock(&self, k: &K, key_lock: Arc<Mutex<()>>) {
+        let mut in_flight = self.in_flight.lock().await;
+        drop(key_lock);
+        if in_flight
+            .get(k)
+            .is_some_and(|lock| Arc::strong_count(lock) == 1)
+        {
+            in_flight.remove(k);
+        }
+    }
+
+    async fn load_and_insert<Err: CacheableRetryableError>(
+        &self,
+        k: &K,
+        loader: &impl KeyLoader<K, V, Err>,
+    ) -> Result<V, CacheError> {
+        if let Some(v) = self.get_valid(k).await {
+            self.nb_saved_requests.fetch_add(1, Ordering::Relaxed);
+            return Ok(v);
+        }
+
+        let v = loader
+            .load_with_retry(k, 5)
+            .await
+            .map_err(Arc::new)
+            .map_err(|source| CacheError::DataLoading { source })?;
+
+        let mut cache = self.memory_cache.write().await;
+        cache.insert(k.clone(), v.clone());
+        if self.validity.is_some() {
+            self.loaded_at
+                .lock()
+                .await
+                .insert(k.clone(), Instant::now());
+        }
+
+        let mut nb_write = self.nb_write.lock().await;
+        *nb_write += 1;
+        if *nb_write >= 100 {
+            let ca = &*cache;
+            loader.persist(ca).await?;
+            *nb_write = 0;
+        }
+        Ok(v)
+    }
+
+    async fn get_valid(&self, k: &K) -> Option<V> {
+        let v = self.memory_cache.read().await.get(k).cloned()?;
+        if let Some(validity) = self.validity {
+            let expired = match self.loaded_at.lock().await.get(k) {
+                Some(loaded_at) => loaded_at.elapsed() >= validity,
+                None => true,
+            };
+            if expired {
+                return None;
+            }
+        }
+        Some(v)
+    }
 }
 
 impl<K: CacheKey, V: Clone> IdentifiedEntryCache<K, V> for Cache<K, V> {
@@ -82,46 +182,50 @@ impl<K: CacheKey, V: Clone> IdentifiedEntryCache<K, V> for Cache<K, V> {
         k: &K,
         loader: &impl KeyLoader<K, V, Err>,
     ) -> Result<V, CacheError> {
-        if !self.memory_cache.read().await.contains_key(k) {
-            let mut cache = self.memory_cache.write().await;
-            if !cache.contains_key(k) {
-                let v = loader
-                    .load_with_retry(k, 5)
-                    .await
-                    .map_err(Arc::new)
-                    .map_err(|source| CacheError::DataLoading { source })?;
-                cache.insert(k.clone(), v);
-
-                let mut nb_write = self.nb_write.lock().await;
-                *nb_write += 1;
-                if *nb_write >= 100 {
-                    let ca = &*cache;
-                    loader.persist(ca).await?;
-                    *nb_write = 0;
-                }
-            }
+        if let Some(v) = self.get_valid(k).await {
+            return Ok(v);
         }
-        Ok(self.memory_cache.read().await[k].clone())
+
+        // Only one caller per key reaches the loader, the others wait for it to fill the cache.
+        // When the load fails, the next waiter tries again.
+        let key_lock = self.in_flight_lock(k).await;
+        let loaded = {
+            let _guard = key_lock.lock().await;
+            self.load_and_insert(k, loader).await
+        };
+        self.release_in_flight_lock(k, key_lock).await;
+        loaded
     }
 }
 
 #[derive(Default)]
 pub struct SingleCache<V: Clone> {
     pub memory_cache: RwLock<Option<V>>,
+    in_flight: Mutex<()>,
+    nb_saved_requests: AtomicUsize,
 }
 
 impl<V: Clone> SingleCache<V> {
     pub fn new() -> Self {
         Self {
             memory_cache: RwLock::new(None),
+            in_flight: Mutex::new(()),
+            nb_saved_requests: AtomicUsize::new(0),
         }
     }
 
     pub fn from(preloaded: V) -> Self {
         Self {
             memory_cache: RwLock::new(Some(preloaded)),
+            in_flight: Mutex::new(()),
+            nb_saved_requests: AtomicUsize::new(0),
         }
     }
+
+    /// Number of loads avoided because another caller was already loading the value.
+    pub fn saved_requests(&self) -> usize {
+        self.nb_saved_requests.load(Ordering::Relaxed)
+    }
 }
 
 impl<V: Clone> GlobalEntryCache<V> for SingleCache<V> {
@@ -129,23 +233,23 @@ impl<V: Clone> GlobalEntryCache<V> for SingleCache<V> {
         &self,
         loader: &impl Loader<V, Err>,
     ) -> Result<V, CacheError> {
-        if self.memory_cache.read().await.is_none() {
-            let mut cache = self.memory_cache.write().await;
-            if cache.is_none() {
-                let v = loader
-                    .load()
-                    .await
-                    .map_err(Arc::new)
-                    .map_err(|source| CacheError::DataLoading { source })?;
-                *cache = Some(v);
-            }
+        if let Some(v) = self.memory_cache.read().await.as_ref() {
+            return Ok(v.clone());
         }
-        Ok(self
-            .memory_cache
-            .read()
+
+        let _guard = self.in_flight.lock().await;
+        if let Some(v) = self.memory_cache.read().await.as_ref() {
+            self.nb_saved_requests.fetch_add(1, Ordering::Relaxed);
+            return Ok(v.clone());
+        }
+
+        let v = loader
+            .load()
             .await
-            .clone()
-            .expect("Cache should be already filled here"))
+            .map_err(Arc::new)
+            .map_err(|source| CacheError::DataLoading { source })?;
+        *self.memory_cache.write().await = Some(v.clone());
+        Ok(v)
     }
 }
 
@@ -182,7 +286,11 @@ pub mod mocks {
 mod tests {
     use tests::mocks::MockCacheableRetryableError;
 
-    use crate::api::evecache::loader::mocks::{MockGlobalLoader, MockKeyLoader};
+    use std::sync::atomic::Ordering;
+
+    use futures_util::future::join_all;
+
+    use crate::api::evecache::loader::mocks::{MockGlobalLoader, MockKeyLoader, MockSlowKeyLoader};
 
     use super::*;
 
@@ -275,4 +383,82 @@ mod tests {
         let nb_write = cache.nb_write.lock().await;
         assert_eq!(*nb_write, 0);
     }
+
+    #[tokio::test]
+    async fn test_get_or_insert_concurrent_requests_deduplicated() {
+        let cache: Cache<u32, String> = Cache::new();
+        let loader = MockSlowKeyLoader::default();
+
+        let mut futures = vec![];
+        for _ in 0..10 {
+            futures.push(IdentifiedEntryCache::get_or_insert::<
+                MockCacheableRetryableError,
+            >(&cache, &1, &loader));
+        }
+        let results = join_all(futures).await;
+
+        for result in results {
+            assert_eq!(result.unwrap(), "value_1".to_string());
+        }
+        assert_eq!(loader.nb_load.load(Ordering::Relaxed), 1);
+        assert_eq!(cache.saved_requests(), 9);
+    }
+
+    #[tokio::test]
+    async fn test_get_or_insert_expired_entry_reloaded() {
+        let cache: Cache<u32, String> = Cache::with_validity(Duration::from_millis(100));
+        let loader = MockSlowKeyLoader::default();
+
+        cache
+            .get_or_insert::<MockCacheableRetryableError>(&1, &loader)
+            .await
+            .unwrap();
+        cache
+            .get_or_insert::<MockCacheableRetryableError>(&1, &loader)
+            .await
+            .unwrap();
+        assert_eq!(loader.nb_load.load(Ordering::Relaxed), 1);
+
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        let result = cache
+            .get_or_insert::<MockCacheableRetryableError>(&1, &loader)
+            .await;
+
+        assert_eq!(result.unwrap(), "value_1".to_string());
+        assert_eq!(loader.nb_load.load(Ordering::Relaxed), 2);
+    }
+
+    #[tokio::test]
+    async fn test_get_or_insert_failed_load_retried_once() {
+        let cache: Cache<u32, String> = Cache::new();
+        let loader = MockSlowKeyLoader {
+            nb_failures: 1,
+            ..Default::default()
+        };
+
+        // The late callers arrive while a waiter retries the failed load.
+        let mut futures = vec![];
+        for i in 0..10 {
+            let (cache, loader) = (&cache, &loader);
+            futures.push(async move {
+                if i >= 5 {
+                    tokio::time::sleep(Duration::from_millis(75)).await;
+                }
+                cache
+                    .get_or_insert::<MockCacheableRetryableError>(&1, loader)
+                    .await
+            });
+        }
+        let results = join_all(futures).await;
+
+        assert!(matches!(
+            results[0],
+            Err(CacheError::DataLoading { source: _ })
+        ));
+        for result in results.into_iter().skip(1) {
+            assert_eq!(result.unwrap(), "value_1".to_string());
+        }
+        assert_eq!(loader.nb_load.load(Ordering::Relaxed), 2);
+        assert!(cache.in_flight.lock().await.is_empty());
+    }
 }
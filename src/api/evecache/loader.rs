@@ -7,9 +7,9 @@ use crate::{
 use futures_util::future::TryJoinAll;
 use rfesi::{
     groups::{
-        AllianceInfo, CharacterOrder, Constellation, CorporationPublicInfo, IndustrialSystem,
-        IndustryJob, MarketOrder, PriceItem, Region, SearchResult, Skills, Station, Structure,
-        System, Type,
+        AllianceInfo, CharacterAttributes, CharacterOrder, Constellation, CorporationPublicInfo,
+        IndustrialSystem, IndustryJob, MarketOrder, PriceItem, Region, SearchResult, Skills,
+        Station, Structure, System, Type,
     },
     prelude::{Esi, EsiError},
 };
@@ -126,7 +126,7 @@ impl<'a> KeyLoader<i32, Station, APIError> for EsiLoader<'a> {
 
     async fn persist(&self, data: &HashMap<i32, Station>) -> Result<(), FSCacheWriteError> {
         if let CacheLevel::Full(fs_data) = self.cache_level {
-            fs_data.save_to_cache(CacheName::Stations, data)?;
+            fs_data.save_to_cache(CacheName::Stations, data).await?;
         }
         Ok(())
     }
@@ -147,7 +147,7 @@ impl<'a> KeyLoader<i64, Structure, APIError> for EsiLoader<'a> {
 
     async fn persist(&self, data: &HashMap<i64, Structure>) -> Result<(), FSCacheWriteError> {
         if let CacheLevel::Full(fs_data) = self.cache_level {
-            fs_data.save_to_cache(CacheName::Structures, data)?;
+            fs_data.save_to_cache(CacheName::Structures, data).await?;
         }
         Ok(())
     }
@@ -168,7 +168,7 @@ impl<'a> KeyLoader<i32, System, APIError> for EsiLoader<'a> {
 
     async fn persist(&self, data: &HashMap<i32, System>) -> Result<(), FSCacheWriteError> {
         if let CacheLevel::Full(fs_data) = self.cache_level {
-            fs_data.save_to_cache(CacheName::Systems, data)?;
+            fs_data.save_to_cache(CacheName::Systems, data).await?;
         }
         Ok(())
     }
@@ -189,7 +189,9 @@ impl<'a> KeyLoader<i32, Constellation, APIError> for EsiLoader<'a> {
 
     async fn persist(&self, data: &HashMap<i32, Constellation>) -> Result<(), FSCacheWriteError> {
         if let CacheLevel::Full(fs_data) = self.cache_level {
-            fs_data.save_to_cache(CacheName::Constellations, data)?;
+            fs_data
+                .save_to_cache(CacheName::Constellations, data)
+                .await?;
         }
         Ok(())
     }
@@ -210,7 +212,7 @@ impl<'a> KeyLoader<i32, Region, APIError> for EsiLoader<'a> {
 
     async fn persist(&self, data: &HashMap<i32, Region>) -> Result<(), FSCacheWriteError> {
         if let CacheLevel::Full(fs_data) = self.cache_level {
-            fs_data.save_to_cache(CacheName::Regions, data)?;
+            fs_data.save_to_cache(CacheName::Regions, data).await?;
         }
         Ok(())
     }
@@ -231,7 +233,7 @@ impl<'a> KeyLoader<i32, Type, APIError> for EsiLoader<'a> {
 
     async fn persist(&self, data: &HashMap<i32, Type>) -> Result<(), FSCacheWriteError> {
         if let CacheLevel::Full(fs_data) = self.cache_level {
-            fs_data.save_to_cache(CacheName::Types, data)?;
+            fs_data.save_to_cache(CacheName::Types, data).await?;
         }
         Ok(())
     }
@@ -255,7 +257,7 @@ impl<'a> KeyLoader<i32, CorporationPublicInfo, APIError> for EsiLoader<'a> {
         data: &HashMap<i32, CorporationPublicInfo>,
     ) -> Result<(), FSCacheWriteError> {
         if let CacheLevel::Full(fs_data) = self.cache_level {
-            fs_data.save_to_cache(CacheName::Corporations, data)?;
+            fs_data.save_to_cache(CacheName::Corporations, data).await?;
         }
         Ok(())
     }
@@ -276,7 +278,7 @@ impl<'a> KeyLoader<i32, AllianceInfo, APIError> for EsiLoader<'a> {
 
     async fn persist(&self, data: &HashMap<i32, AllianceInfo>) -> Result<(), FSCacheWriteError> {
         if let CacheLevel::Full(fs_data) = self.cache_level {
-            fs_data.save_to_cache(CacheName::Alliances, data)?;
+            fs_data.save_to_cache(CacheName::Alliances, data).await?;
         }
         Ok(())
     }
@@ -305,7 +307,7 @@ impl<'a> KeyLoader<SearchKey, SearchResult, APIError> for EsiLoader<'a> {
         data: &HashMap<SearchKey, SearchResult>,
     ) -> Result<(), FSCacheWriteError> {
         if let CacheLevel::Full(fs_data) = self.cache_level {
-            fs_data.save_to_cache(CacheName::Search, data)?;
+            fs_data.save_to_cache(CacheName::Search, data).await?;
         }
         Ok(())
     }
@@ -400,6 +402,45 @@ impl<'a> KeyLoader<i32, Skills, APIError> for EsiLoader<'a> {
     }
 }
 
+impl<'a> KeyLoader<i32, CharacterAttributes, APIError> for EsiLoader<'a> {
+    async fn load(&self, character_id: &i32) -> Result<CharacterAttributes, APIError> {
+        logging::trace!("Load attributes for character ID: {:?}", character_id);
+        self.esi
+            .group_skills()
+            .get_attributes(*character_id)
+            .await
+            .map_err(|source| APIError {
+                description: "get_character_attributes".to_string(),
+                source,
+            })
+    }
+
+    async fn persist(
+        &self,
+        _: &HashMap<i32, CharacterAttributes>,
+    ) -> Result<(), FSCacheWriteError> {
+        Ok(())
+    }
+}
+
+impl<'a> KeyLoader<i32, Vec<i32>, APIError> for EsiLoader<'a> {
+    async fn load(&self, character_id: &i32) -> Result<Vec<i32>, APIError> {
+        logging::trace!("Load implants for character ID: {:?}", character_id);
+        self.esi
+            .group_clones()
+            .get_implants(*character_id)
+            .await
+            .map_err(|source| APIError {
+                description: "get_character_implants".to_string(),
+                source,
+            })
+    }
+
+    async fn persist(&self, _: &HashMap<i32, Vec<i32>>) -> Result<(), FSCacheWriteError> {
+        Ok(())
+    }
+}
+
 impl<'a> KeyLoader<i32, Vec<IndustryJob>, APIError> for EsiLoader<'a> {
     async fn load(&self, character_id: &i32) -> Result<Vec<IndustryJob>, APIError> {
         logging::trace!("Load industry jobs for character ID: {:?}", character_id);
@@ -505,8 +546,11 @@ impl<'a> KeyLoader<MarketOrderKey, Vec<MarketOrder>, APIError> for EsiLoader<'a>
 
     async fn persist(
         &self,
-        _: &HashMap<MarketOrderKey, Vec<MarketOrder>>,
+        data: &HashMap<MarketOrderKey, Vec<MarketOrder>>,
     ) -> Result<(), FSCacheWriteError> {
+        if let CacheLevel::Full(fs_data) = self.cache_level {
+            fs_data.save_to_cache(CacheName::MarketOrders, data).await?;
+        }
         Ok(())
     }
 }
@@ -514,8 +558,13 @@ impl<'a> KeyLoader<MarketOrderKey, Vec<MarketOrder>, APIError> for EsiLoader<'a>
 #[cfg(test)]
 pub mod mocks {
     use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
 
-    use crate::{cache::FSCacheWriteError, retry::RetryableError};
+    use crate::{
+        api::evecache::cache::mocks::MockCacheableRetryableError, cache::FSCacheWriteError,
+        retry::RetryableError,
+    };
 
     use super::{KeyLoader, Loader};
 
@@ -545,4 +594,29 @@ pub mod mocks {
             Ok(())
         }
     }
+
+    #[derive(Default)]
+    pub struct MockSlowKeyLoader {
+        pub nb_load: AtomicUsize,
+        /// Number of first loads failing with a non retryable error.
+        pub nb_failures: usize,
+    }
+
+    impl KeyLoader<u32, String, MockCacheableRetryableError> for MockSlowKeyLoader {
+        async fn load(&self, key: &u32) -> Result<String, MockCacheableRetryableError> {
+            let nb_load = self.nb_load.fetch_add(1, Ordering::Relaxed);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            if nb_load < self.nb_failures {
+                return Err(MockCacheableRetryableError {
+                    msg: "expected error".to_string(),
+                    retry: false,
+                });
+            }
+            Ok(format!("value_{}", key))
+        }
+
+        async fn persist(&self, _: &HashMap<u32, String>) -> Result<(), FSCacheWriteError> {
+            Ok(())
+        }
+    }
 }
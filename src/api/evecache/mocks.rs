@@ -1,7 +1,7 @@
 use rfesi::groups::{CostIndex, Position, Skill};
 
 use crate::{
-    api::sde::{Activities, Invention, Item, Manufacturing, ProbableMultipleItems},
+    api::sde::{Activities, Invention, Item, Manufacturing, ProbableMultipleItems, SDETypeInfo},
     model::industry::IndustryType,
 };
 
@@ -16,6 +16,8 @@ pub struct MockRequesterBuilder {
     character_market_orders: HashMap<i32, Vec<CharacterOrder>>,
     character_industrial_jobs: HashMap<i32, Vec<IndustryJob>>,
     character_skills: HashMap<i32, Skills>,
+    character_attributes: HashMap<i32, CharacterAttributes>,
+    character_implants: HashMap<i32, Vec<i32>>,
 
     // Blueprints
     blueprints: HashMap<i32, SDEBlueprint>,
@@ -37,6 +39,7 @@ pub struct MockRequesterBuilder {
     structures: HashMap<i64, Structure>,
     stations: HashMap<i32, Station>,
     types: HashMap<i32, Type>,
+    type_infos: HashMap<i32, SDETypeInfo>,
 }
 
 impl MockRequesterBuilder {
@@ -49,6 +52,8 @@ impl MockRequesterBuilder {
             character_market_orders: HashMap::default(),
             character_industrial_jobs: HashMap::default(),
             character_skills: HashMap::default(),
+            character_attributes: HashMap::default(),
+            character_implants: HashMap::default(),
             blueprints: HashMap::default(),
             alliances: HashMap::default(),
             corporations: HashMap::default(),
@@ -62,6 +67,7 @@ impl MockRequesterBuilder {
             structures: HashMap::default(),
             stations: HashMap::default(),
             types: HashMap::default(),
+            type_infos: HashMap::default(),
         }
     }
 
@@ -108,6 +114,11 @@ impl MockRequesterBuilder {
         self
     }
 
+    pub fn insert_character_implants(mut self, id: i32, value: Vec<i32>) -> Self {
+        self.character_implants.insert(id, value);
+        self
+    }
+
     fn insert_blueprints(mut self, id: i32, value: SDEBlueprint) -> Self {
         self.blueprints.insert(id, value);
         self
@@ -185,6 +196,11 @@ impl MockRequesterBuilder {
         self
     }
 
+    pub fn insert_type_info(mut self, id: i32, value: SDETypeInfo) -> Self {
+        self.type_infos.insert(id, value);
+        self
+    }
+
     pub fn build(self) -> MockRequester {
         MockRequester {
             character_base_info: self.character_base_info,
@@ -194,6 +210,8 @@ impl MockRequesterBuilder {
             character_market_orders: self.character_market_orders,
             character_industrial_jobs: self.character_industrial_jobs,
             character_skills: self.character_skills,
+            character_attributes: self.character_attributes,
+            character_implants: self.character_implants,
             blueprints: self.blueprints,
             alliances: self.alliances,
             corporations: self.corporations,
@@ -207,6 +225,7 @@ impl MockRequesterBuilder {
             structures: self.structures,
             stations: self.stations,
             types: self.types,
+            type_infos: self.type_infos,
         }
     }
 }
@@ -381,6 +400,7 @@ impl Default for MockRequesterBuilder {
                     unallocated_sp: ignored_number as i32,
                 },
             )
+            .insert_character_implants(1, vec![])
             .insert_type(4, create_skill_type(4, "Test Skill n4"))
             .insert_type(5, create_skill_type(5, "Test Skill n5"))
             .insert_type(6, create_skill_type(6, "Test Skill n6"))
@@ -437,6 +457,7 @@ impl Default for MockRequesterBuilder {
                                 quantity: 10,
                                 type_id: 19,
                             }]),
+                            skills: None,
                             time: 1054,
                         }),
                         research_material: None,
@@ -468,6 +489,7 @@ impl Default for MockRequesterBuilder {
                                 quantity: 1,
                                 type_id: 20,
                             }]),
+                            skills: None,
                             time: 100,
                         }),
                         research_material: None,
@@ -658,6 +680,8 @@ pub struct MockRequester {
     character_market_orders: HashMap<i32, Vec<CharacterOrder>>,
     character_industrial_jobs: HashMap<i32, Vec<IndustryJob>>,
     character_skills: HashMap<i32, Skills>,
+    character_attributes: HashMap<i32, CharacterAttributes>,
+    character_implants: HashMap<i32, Vec<i32>>,
 
     // Blueprints
     blueprints: HashMap<i32, SDEBlueprint>,
@@ -679,6 +703,7 @@ pub struct MockRequester {
     structures: HashMap<i64, Structure>,
     stations: HashMap<i32, Station>,
     types: HashMap<i32, Type>,
+    type_infos: HashMap<i32, SDETypeInfo>,
 }
 
 impl MockRequester {
@@ -755,6 +780,59 @@ impl TypeLoader for MockRequester {
     }
 }
 
+#[async_trait]
+impl TypeInfoLoader for MockRequester {
+    async fn get_type_info(&self, id: i32) -> Result<Option<SDETypeInfo>, CacheError> {
+        Ok(self.type_infos.get(&id).cloned())
+    }
+
+    async fn get_skill_type_ids(&self) -> Result<Option<Vec<i32>>, CacheError> {
+        if self.type_infos.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(
+            self.type_infos
+                .iter()
+                .filter(|(_, info)| info.category_name.as_deref() == Some("Skill"))
+                .map(|(id, _)| *id)
+                .collect(),
+        ))
+    }
+
+    async fn search_types(
+        &self,
+        query: &str,
+        activity: &BlueprintActivityType,
+        strict: bool,
+    ) -> Result<Option<Vec<i32>>, CacheError> {
+        if self.type_infos.is_empty() {
+            return Ok(None);
+        }
+        let mut found: Vec<(usize, i32)> = vec![];
+        for (id, info) in &self.type_infos {
+            let manufacturing_blueprints = self
+                .get_producing_blueprints(*id, &BlueprintActivityType::Manufacturing)
+                .await?;
+            let mut searchable = !manufacturing_blueprints.is_empty();
+            if let BlueprintActivityType::Invention = activity {
+                searchable = false;
+                for (bp_id, _) in manufacturing_blueprints {
+                    if self.get_blueprint(bp_id, activity).await?.is_some() {
+                        searchable = true;
+                    }
+                }
+            }
+            if let Some(rank) = crate::search::rank(query, &info.name) {
+                if searchable && (!strict || rank == 0) {
+                    found.push((rank, *id));
+                }
+            }
+        }
+        found.sort();
+        Ok(Some(found.into_iter().map(|(_, id)| id).collect()))
+    }
+}
+
 #[async_trait]
 impl CorporationLoader for MockRequester {
     async fn get_corporation(&self, id: i32) -> Result<CorporationPublicInfo, CacheError> {
@@ -866,6 +944,26 @@ impl CharacterSkillsLoader for MockRequester {
     }
 }
 
+#[async_trait]
+impl CharacterAttributesLoader for MockRequester {
+    async fn get_character_attributes(&self, id: i32) -> Result<CharacterAttributes, CacheError> {
+        match self.character_attributes.get(&id) {
+            Some(attributes) => return Ok(attributes.clone()),
+            None => panic!("Could not load attributes: {}", id),
+        }
+    }
+}
+
+#[async_trait]
+impl CharacterImplantsLoader for MockRequester {
+    async fn get_character_implants(&self, id: i32) -> Result<Vec<i32>, CacheError> {
+        match self.character_implants.get(&id) {
+            Some(implants) => return Ok(implants.clone()),
+            None => panic!("Could not load implants: {}", id),
+        }
+    }
+}
+
 #[async_trait]
 impl CharacterIndustryJobsLoader for MockRequester {
     async fn get_character_industry_jobs(&self, id: i32) -> Result<Vec<IndustryJob>, CacheError> {
@@ -893,19 +991,28 @@ impl BlueprintsLoader for MockRequester {
         product_id: i32,
         activity: &BlueprintActivityType,
     ) -> Result<Option<(i32, SDEBlueprint)>, CacheError> {
-        let founds: Vec<(i32, SDEBlueprint)> = self
+        let founds = self.get_producing_blueprints(product_id, activity).await?;
+        if founds.is_empty() {
+            return Ok(None);
+        }
+        return Ok(Some(founds[0].clone()));
+    }
+    async fn get_producing_blueprints(
+        &self,
+        product_id: i32,
+        activity: &BlueprintActivityType,
+    ) -> Result<Vec<(i32, SDEBlueprint)>, CacheError> {
+        let mut founds: Vec<(i32, SDEBlueprint)> = self
             .blueprints
             .iter()
             .filter(|(_, bp)| bp.can_produce(product_id, activity))
             .map(|(id, bp)| (id.clone(), bp.clone()))
             .collect();
-        if founds.is_empty() {
-            return Ok(None);
-        }
-        return Ok(Some(founds[0].clone()));
+        founds.sort_by_key(|(id, _)| *id);
+        Ok(founds)
     }
-    async fn get_blueprints(&self) -> Result<HashMap<i32, SDEBlueprint>, CacheError> {
-        Ok(self.blueprints.clone())
+    async fn get_blueprints(&self) -> Result<Arc<HashMap<i32, SDEBlueprint>>, CacheError> {
+        Ok(Arc::new(self.blueprints.clone()))
     }
 }
 
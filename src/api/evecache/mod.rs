@@ -6,13 +6,15 @@ use std::sync::Arc;
 
 use chrono::Duration;
 use rfesi::groups::{
-    AllianceInfo, CharacterOrder, CharacterPublicInfo, Constellation, CorporationPublicInfo,
-    HistoryItem, IndustrialSystem, IndustryJob, LocationInfo, MarketOrder, PriceItem, Region,
-    SearchResult, Skills, Station, Structure, System, Type,
+    AllianceInfo, CharacterAttributes, CharacterOrder, CharacterPublicInfo, Constellation,
+    CorporationPublicInfo, HistoryItem, IndustrialSystem, IndustryJob, LocationInfo, MarketOrder,
+    PriceItem, Region, SearchResult, Skills, Station, Structure, System, Type,
 };
 use rfesi::prelude::Esi;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
 
-use crate::api::sde::{BlueprintActivityType, SDEBlueprint, Sde};
+use crate::api::sde::{BlueprintActivityType, SDEBlueprint, SDETypeInfo, Sde};
 use crate::authentication::tokens::TokenHelper;
 use crate::cache::CacheName::RegionIDs;
 use crate::cache::{CacheName, FSCache, FSCacheReadError, FSCacheWriteError};
@@ -39,6 +41,7 @@ pub trait EveRequester:
     + ConstellationLoader
     + RegionLoader
     + TypeLoader
+    + TypeInfoLoader
     + CorporationLoader
     + AllianceLoader
     + MarketOrderLoader
@@ -47,6 +50,8 @@ pub trait EveRequester:
     + IndustrialSystemsLoader
     + RegionIDsLoader
     + CharacterSkillsLoader
+    + CharacterAttributesLoader
+    + CharacterImplantsLoader
     + CharacterIndustryJobsLoader
     + CharacterMarketOrdersLoader
     + BlueprintsLoader
@@ -95,6 +100,19 @@ pub trait TypeLoader {
     async fn get_type(&self, id: i32) -> Result<Type, CacheError>;
 }
 
+#[async_trait]
+pub trait TypeInfoLoader {
+    async fn get_type_info(&self, id: i32) -> Result<Option<SDETypeInfo>, CacheError>;
+    /// IDs of every skill type. `None` when the type database isn't installed.
+    async fn get_skill_type_ids(&self) -> Result<Option<Vec<i32>>, CacheError>;
+    async fn search_types(
+        &self,
+        query: &str,
+        activity: &BlueprintActivityType,
+        strict: bool,
+    ) -> Result<Option<Vec<i32>>, CacheError>;
+}
+
 #[async_trait]
 pub trait CorporationLoader {
     async fn get_corporation(&self, id: i32) -> Result<CorporationPublicInfo, CacheError>;
@@ -145,6 +163,16 @@ pub trait CharacterSkillsLoader {
     async fn get_character_skill(&self, id: i32) -> Result<Skills, CacheError>;
 }
 
+#[async_trait]
+pub trait CharacterAttributesLoader {
+    async fn get_character_attributes(&self, id: i32) -> Result<CharacterAttributes, CacheError>;
+}
+
+#[async_trait]
+pub trait CharacterImplantsLoader {
+    async fn get_character_implants(&self, id: i32) -> Result<Vec<i32>, CacheError>;
+}
+
 #[async_trait]
 pub trait CharacterIndustryJobsLoader {
     async fn get_character_industry_jobs(&self, id: i32) -> Result<Vec<IndustryJob>, CacheError>;
@@ -162,7 +190,12 @@ pub trait BlueprintsLoader {
         product_id: i32,
         activity: &BlueprintActivityType,
     ) -> Result<Option<(i32, SDEBlueprint)>, CacheError>;
-    async fn get_blueprints(&self) -> Result<HashMap<i32, SDEBlueprint>, CacheError>;
+    async fn get_producing_blueprints(
+        &self,
+        product_id: i32,
+        activity: &BlueprintActivityType,
+    ) -> Result<Vec<(i32, SDEBlueprint)>, CacheError>;
+    async fn get_blueprints(&self) -> Result<Arc<HashMap<i32, SDEBlueprint>>, CacheError>;
 }
 
 #[async_trait]
@@ -216,6 +249,27 @@ impl CacheLevel {
     }
 }
 
+/// Time the Eve API caches the industry jobs of a character, polling more often returns the same
+/// jobs.
+pub const CHARACTER_INDUSTRY_JOBS_VALIDITY: std::time::Duration =
+    std::time::Duration::from_secs(300);
+
+/// Background persistence started by [`EveCache::persist_periodically`].
+pub struct PeriodicPersistence {
+    stop: Arc<Notify>,
+    handle: JoinHandle<()>,
+}
+
+impl PeriodicPersistence {
+    /// Stops the background persistence, waiting for an ongoing save to complete.
+    pub async fn stop(self) {
+        self.stop.notify_one();
+        if let Err(e) = self.handle.await {
+            logging::warning!("Periodic cache persistence failed: {}", e);
+        }
+    }
+}
+
 pub struct EveCache {
     cache_level: CacheLevel,
     esi: Esi,
@@ -234,6 +288,8 @@ pub struct EveCache {
     region_ids: Option<SingleCache<Vec<i32>>>,
     character_base_info: Option<SingleCache<CharacterBaseInfo>>,
     skills: Option<Cache<i32, Skills>>,
+    character_attributes: Option<Cache<i32, CharacterAttributes>>,
+    character_implants: Option<Cache<i32, Vec<i32>>>,
     corporations: Option<Cache<i32, CorporationPublicInfo>>,
     alliances: Option<Cache<i32, AllianceInfo>>,
     character_industry_jobs: Option<Cache<i32, Vec<IndustryJob>>>,
@@ -265,6 +321,8 @@ impl EveCache {
                 industrial_systems: None,
                 region_ids: None,
                 skills: None,
+                character_attributes: None,
+                character_implants: None,
                 corporations: None,
                 alliances: None,
                 character_industry_jobs: None,
@@ -289,9 +347,13 @@ impl EveCache {
                 region_ids: Some(SingleCache::new()),
                 character_base_info: Some(SingleCache::new()),
                 skills: Some(Cache::new()),
+                character_attributes: Some(Cache::new()),
+                character_implants: Some(Cache::new()),
                 corporations: Some(Cache::new()),
                 alliances: Some(Cache::new()),
-                character_industry_jobs: Some(Cache::new()),
+                character_industry_jobs: Some(Cache::with_validity(
+                    CHARACTER_INDUSTRY_JOBS_VALIDITY,
+                )),
                 character_orders: Some(Cache::new()),
                 market_orders: Some(Cache::new()),
             }),
@@ -390,7 +452,11 @@ impl EveCache {
                         Some(alliances) => Some(Cache::from(alliances)),
                     },
                     skills: Some(Cache::new()),
-                    character_industry_jobs: Some(Cache::new()),
+                    character_attributes: Some(Cache::new()),
+                    character_implants: Some(Cache::new()),
+                    character_industry_jobs: Some(Cache::with_validity(
+                        CHARACTER_INDUSTRY_JOBS_VALIDITY,
+                    )),
                     character_orders: Some(Cache::new()),
                     character_base_info: Some(SingleCache::new()),
                     market_orders: match market_orders {
@@ -402,131 +468,214 @@ impl EveCache {
         }
     }
 
+    /// Saves the caches at a regular interval, so an interrupted long run keeps what was already loaded.
+    pub fn persist_periodically(
+        self: Arc<Self>,
+        period: std::time::Duration,
+    ) -> PeriodicPersistence {
+        let stop = Arc::new(Notify::new());
+        let stopped = stop.clone();
+        let handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            interval.tick().await;
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    _ = stopped.notified() => break,
+                }
+                if let Err(e) = self.persist().await {
+                    logging::warning!("Could not persist cache: {}", e);
+                }
+            }
+        });
+        PeriodicPersistence { stop, handle }
+    }
+
     pub async fn persist(&self) -> Result<(), FSCacheWriteError> {
+        logging::debug!(
+            "ESI requests saved by de-duplication: {}",
+            self.saved_requests()
+        );
         if let CacheLevel::Full(fs_cache) = &self.cache_level {
-            fs_cache.save_to_cache(
-                CacheName::Stations,
-                self.stations
-                    .as_ref()
-                    .expect("Cannot have a FSCache without a memory cache.")
-                    .memory_cache
-                    .read()
-                    .await
-                    .clone(),
-            )?;
-            fs_cache.save_to_cache(
-                CacheName::Structures,
-                self.structures
-                    .as_ref()
-                    .expect("Cannot have a FSCache without a memory cache.")
-                    .memory_cache
-                    .read()
-                    .await
-                    .clone(),
-            )?;
-            fs_cache.save_to_cache(
-                CacheName::Systems,
-                self.systems
-                    .as_ref()
-                    .expect("Cannot have a FSCache without a memory cache.")
-                    .memory_cache
-                    .read()
-                    .await
-                    .clone(),
-            )?;
-            fs_cache.save_to_cache(
-                CacheName::Constellations,
-                self.constellations
-                    .as_ref()
-                    .expect("Cannot have a FSCache without a memory cache.")
-                    .memory_cache
-                    .read()
-                    .await
-                    .clone(),
-            )?;
-            fs_cache.save_to_cache(
-                CacheName::Regions,
-                self.regions
-                    .as_ref()
-                    .expect("Cannot have a FSCache without a memory cache.")
-                    .memory_cache
-                    .read()
-                    .await
-                    .clone(),
-            )?;
-            fs_cache.save_to_cache(
-                CacheName::Types,
-                self.types
-                    .as_ref()
-                    .expect("Cannot have a FSCache without a memory cache.")
-                    .memory_cache
-                    .read()
-                    .await
-                    .clone(),
-            )?;
-            fs_cache.save_to_cache(
-                CacheName::Search,
-                self.search
-                    .as_ref()
-                    .expect("Cannot have a FSCache without a memory cache.")
-                    .memory_cache
-                    .read()
-                    .await
-                    .clone(),
-            )?;
-            fs_cache.save_to_cache(
-                CacheName::MarketPrices,
-                self.market_prices
-                    .as_ref()
-                    .expect("Cannot have a FSCache without a memory cache.")
-                    .memory_cache
-                    .read()
-                    .await
-                    .clone(),
-            )?;
-            fs_cache.save_to_cache(
-                CacheName::IndustrialSystems,
-                self.industrial_systems
-                    .as_ref()
-                    .expect("Cannot have a FSCache without a memory cache.")
-                    .memory_cache
-                    .read()
-                    .await
-                    .clone(),
-            )?;
-            fs_cache.save_to_cache(
-                RegionIDs,
-                self.region_ids
-                    .as_ref()
-                    .expect("Cannot have a FSCache without a memory cache.")
-                    .memory_cache
-                    .read()
-                    .await
-                    .clone(),
-            )?;
-            fs_cache.save_to_cache(
-                CacheName::Corporations,
-                self.corporations
-                    .as_ref()
-                    .expect("Cannot have a FSCache without a memory cache.")
-                    .memory_cache
-                    .read()
-                    .await
-                    .clone(),
-            )?;
-            fs_cache.save_to_cache(
-                CacheName::MarketOrders,
-                self.market_orders
-                    .as_ref()
-                    .expect("Cannot have a FSCache without a memory cache.")
-                    .memory_cache
-                    .read()
-                    .await
-                    .clone(),
-            )?;
+            let _lock = fs_cache.lock().await?;
+            fs_cache
+                .save_to_cache(
+                    CacheName::Stations,
+                    self.stations
+                        .as_ref()
+                        .expect("Cannot have a FSCache without a memory cache.")
+                        .memory_cache
+                        .read()
+                        .await
+                        .clone(),
+                )
+                .await?;
+            fs_cache
+                .save_to_cache(
+                    CacheName::Structures,
+                    self.structures
+                        .as_ref()
+                        .expect("Cannot have a FSCache without a memory cache.")
+                        .memory_cache
+                        .read()
+                        .await
+                        .clone(),
+                )
+                .await?;
+            fs_cache
+                .save_to_cache(
+                    CacheName::Systems,
+                    self.systems
+                        .as_ref()
+                        .expect("Cannot have a FSCache without a memory cache.")
+                        .memory_cache
+                        .read()
+                        .await
+                        .clone(),
+                )
+                .await?;
+            fs_cache
+                .save_to_cache(
+                    CacheName::Constellations,
+                    self.constellations
+                        .as_ref()
+                        .expect("Cannot have a FSCache without a memory cache.")
+                        .memory_cache
+                        .read()
+                        .await
+                        .clone(),
+                )
+                .await?;
+            fs_cache
+                .save_to_cache(
+                    CacheName::Regions,
+                    self.regions
+                        .as_ref()
+                        .expect("Cannot have a FSCache without a memory cache.")
+                        .memory_cache
+                        .read()
+                        .await
+                        .clone(),
+                )
+                .await?;
+            fs_cache
+                .save_to_cache(
+                    CacheName::Types,
+                    self.types
+                        .as_ref()
+                        .expect("Cannot have a FSCache without a memory cache.")
+                        .memory_cache
+                        .read()
+                        .await
+                        .clone(),
+                )
+                .await?;
+            fs_cache
+                .save_to_cache(
+                    CacheName::Search,
+                    self.search
+                        .as_ref()
+                        .expect("Cannot have a FSCache without a memory cache.")
+                        .memory_cache
+                        .read()
+                        .await
+                        .clone(),
+                )
+                .await?;
+            fs_cache
+                .save_to_cache(
+                    CacheName::MarketPrices,
+                    self.market_prices
+                        .as_ref()
+                        .expect("Cannot have a FSCache without a memory cache.")
+                        .memory_cache
+                        .read()
+                        .await
+                        .clone(),
+                )
+                .await?;
+            fs_cache
+                .save_to_cache(
+                    CacheName::IndustrialSystems,
+                    self.industrial_systems
+                        .as_ref()
+                        .expect("Cannot have a FSCache without a memory cache.")
+                        .memory_cache
+                        .read()
+                        .await
+                        .clone(),
+                )
+                .await?;
+            fs_cache
+                .save_to_cache(
+                    RegionIDs,
+                    self.region_ids
+                        .as_ref()
+                        .expect("Cannot have a FSCache without a memory cache.")
+                        .memory_cache
+                        .read()
+                        .await
+                        .clone(),
+                )
+                .await?;
+            fs_cache
+                .save_to_cache(
+                    CacheName::Corporations,
+                    self.corporations
+                        .as_ref()
+                        .expect("Cannot have a FSCache without a memory cache.")
+                        .memory_cache
+                        .read()
+                        .await
+                        .clone(),
+                )
+                .await?;
+            fs_cache
+                .save_to_cache(
+                    CacheName::MarketOrders,
+                    self.market_orders
+                        .as_ref()
+                        .expect("Cannot have a FSCache without a memory cache.")
+                        .memory_cache
+                        .read()
+                        .await
+                        .clone(),
+                )
+                .await?;
         }
         Ok(())
     }
+
+    fn saved_requests(&self) -> usize {
+        let identified = [
+            self.stations.as_ref().map(Cache::saved_requests),
+            self.structures.as_ref().map(Cache::saved_requests),
+            self.systems.as_ref().map(Cache::saved_requests),
+            self.constellations.as_ref().map(Cache::saved_requests),
+            self.regions.as_ref().map(Cache::saved_requests),
+            self.types.as_ref().map(Cache::saved_requests),
+            self.search.as_ref().map(Cache::saved_requests),
+            self.skills.as_ref().map(Cache::saved_requests),
+            self.corporations.as_ref().map(Cache::saved_requests),
+            self.alliances.as_ref().map(Cache::saved_requests),
+            self.character_industry_jobs
+                .as_ref()
+                .map(Cache::saved_requests),
+            self.character_orders.as_ref().map(Cache::saved_requests),
+            self.market_orders.as_ref().map(Cache::saved_requests),
+        ];
+        let global = [
+            self.market_prices.as_ref().map(SingleCache::saved_requests),
+            self.industrial_systems
+                .as_ref()
+                .map(SingleCache::saved_requests),
+            self.region_ids.as_ref().map(SingleCache::saved_requests),
+            self.character_base_info
+                .as_ref()
+                .map(SingleCache::saved_requests),
+        ];
+        identified.into_iter().chain(global).flatten().sum()
+    }
 }
 
 impl EveRequester for EveCache {}
@@ -679,6 +828,32 @@ impl CharacterSkillsLoader for EveCache {
     }
 }
 
+#[async_trait]
+impl CharacterAttributesLoader for EveCache {
+    async fn get_character_attributes(&self, id: i32) -> Result<CharacterAttributes, CacheError> {
+        query_cache_with_id(
+            &self.character_attributes,
+            id,
+            EsiLoader::from(self),
+            "character_attributes",
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl CharacterImplantsLoader for EveCache {
+    async fn get_character_implants(&self, id: i32) -> Result<Vec<i32>, CacheError> {
+        query_cache_with_id(
+            &self.character_implants,
+            id,
+            EsiLoader::from(self),
+            "character_implants",
+        )
+        .await
+    }
+}
+
 #[async_trait]
 impl CharacterIndustryJobsLoader for EveCache {
     async fn get_character_industry_jobs(&self, id: i32) -> Result<Vec<IndustryJob>, CacheError> {
@@ -705,9 +880,32 @@ impl CharacterMarketOrdersLoader for EveCache {
     }
 }
 
+#[async_trait]
+impl TypeInfoLoader for EveCache {
+    async fn get_type_info(&self, id: i32) -> Result<Option<SDETypeInfo>, CacheError> {
+        let database = self.sde.load_type_database().await?;
+        Ok(database.and_then(|database| database.get(id)))
+    }
+
+    async fn get_skill_type_ids(&self) -> Result<Option<Vec<i32>>, CacheError> {
+        static SKILL_CATEGORY_ID: i32 = 16;
+        let database = self.sde.load_type_database().await?;
+        Ok(database.map(|database| database.type_ids_in_category(SKILL_CATEGORY_ID)))
+    }
+
+    async fn search_types(
+        &self,
+        query: &str,
+        activity: &BlueprintActivityType,
+        strict: bool,
+    ) -> Result<Option<Vec<i32>>, CacheError> {
+        Ok(self.sde.search_types(query, activity, strict).await?)
+    }
+}
+
 #[async_trait]
 impl BlueprintsLoader for EveCache {
-    async fn get_blueprints(&self) -> Result<HashMap<i32, SDEBlueprint>, CacheError> {
+    async fn get_blueprints(&self) -> Result<Arc<HashMap<i32, SDEBlueprint>>, CacheError> {
         Ok(self.sde.load_blueprints().await?)
     }
 
@@ -718,6 +916,17 @@ impl BlueprintsLoader for EveCache {
     ) -> Result<Option<(i32, SDEBlueprint)>, CacheError> {
         Ok(self.sde.load_blueprint(product_id, activity).await?)
     }
+
+    async fn get_producing_blueprints(
+        &self,
+        product_id: i32,
+        activity: &BlueprintActivityType,
+    ) -> Result<Vec<(i32, SDEBlueprint)>, CacheError> {
+        Ok(self
+            .sde
+            .load_producing_blueprints(product_id, activity)
+            .await?)
+    }
 }
 
 #[async_trait]
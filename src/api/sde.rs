@@ -1,10 +1,33 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 
-use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tokio::sync::RwLock;
 
 use crate::cache::FSCache;
+use crate::display::Display;
+use crate::{filesystem, logging, search};
+
+pub const BLUEPRINTS_PATH: &str = "sde/fsd/blueprints.yaml";
+pub const TYPES_PATH: &str = "sde/fsd/types.yaml";
+pub const GROUPS_PATH: &str = "sde/fsd/groups.yaml";
+pub const CATEGORIES_PATH: &str = "sde/fsd/categories.yaml";
+pub const META_GROUPS_PATH: &str = "sde/fsd/metaGroups.yaml";
+pub const TYPE_DOGMA_PATH: &str = "sde/fsd/typeDogma.yaml";
+
+pub const INDEX_PATH: &str = "sde/index.bin";
+
+const MAX_SEARCH_RESULTS: usize = 50;
+
+/// Dogma attributes kept in the index: the industry job time bonuses of implants, and the
+/// primary attribute, secondary attribute and rank of skills.
+/// https://everef.net/dogma-attributes
+const INDEXED_DOGMA_ATTRIBUTE_IDS: [i32; 7] = [440, 452, 453, 468, 180, 181, 275];
+
+/// Bumped each time the layout of the binary index changes, so indexes built by an older version are rebuilt.
+const INDEX_FORMAT_VERSION: u32 = 4;
 
 #[derive(Debug, Error)]
 pub enum SDEErrors {
@@ -18,47 +41,169 @@ pub enum SDEErrors {
         path: String,
         source: serde_yaml::Error,
     },
+    #[error("read SDE index '{path}': {source}")]
+    IndexReadFailed {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("serialize SDE index: {source}")]
+    IndexSerializationFailed { source: bincode::Error },
+    #[error("write SDE index '{path}': {source}")]
+    IndexWriteFailed {
+        path: String,
+        source: std::io::Error,
+    },
 }
 
 pub struct Sde {
     cache: FSCache,
 
-    blueprints_cache: RwLock<Option<HashMap<i32, SDEBlueprint>>>,
+    data: RwLock<Option<Arc<SDEData>>>,
+}
+
+struct SDEData {
+    blueprints: Arc<HashMap<i32, SDEBlueprint>>,
+    products: ProductIndex,
+    types: Option<Arc<TypeDatabase>>,
+}
+
+impl From<SDEIndex> for SDEData {
+    fn from(index: SDEIndex) -> Self {
+        Self {
+            blueprints: Arc::new(index.blueprints),
+            products: index.products,
+            types: index.types.map(Arc::new),
+        }
+    }
 }
 
 impl Sde {
     pub fn new(cache: FSCache) -> Self {
         Self {
             cache,
-            blueprints_cache: RwLock::new(None),
+            data: RwLock::new(None),
         }
     }
 
-    pub async fn load_blueprints(&self) -> Result<HashMap<i32, SDEBlueprint>, SDEErrors> {
-        if self.blueprints_cache.read().await.is_none() {
-            let mut cache = self.blueprints_cache.write().await;
-            if cache.is_none() {
-                let yaml_path = "sde/fsd/blueprints.yaml";
-                let content = self.cache.load_full(yaml_path).await.map_err(|source| {
-                    SDEErrors::CacheRequestFailed {
-                        path: yaml_path.to_string(),
-                        source,
-                    }
+    async fn load_yaml<T: DeserializeOwned>(&self, yaml_path: &str) -> Result<T, SDEErrors> {
+        let content = self.cache.load_full(yaml_path).await.map_err(|source| {
+            SDEErrors::CacheRequestFailed {
+                path: yaml_path.to_string(),
+                source,
+            }
+        })?;
+        serde_yaml::from_str(&content).map_err(|source| SDEErrors::YAMLDeserializationError {
+            path: yaml_path.to_string(),
+            source,
+        })
+    }
+
+    async fn index_from_yaml(&self) -> Result<SDEIndex, SDEErrors> {
+        let blueprints: HashMap<i32, SDEBlueprint> = self.load_yaml(BLUEPRINTS_PATH).await?;
+        let products = ProductIndex::new(&blueprints);
+
+        let paths = [TYPES_PATH, GROUPS_PATH, CATEGORIES_PATH, META_GROUPS_PATH];
+        let types_installed = paths
+            .iter()
+            .all(|path| self.cache.cache_directory.join(path).exists());
+        let types = if types_installed {
+            let type_dogma = if self.cache.cache_directory.join(TYPE_DOGMA_PATH).exists() {
+                self.load_yaml(TYPE_DOGMA_PATH).await?
+            } else {
+                logging::warning!(
+                    "SDE type dogma not found, run 'eve-vulcain update' to read implant bonuses offline"
+                );
+                HashMap::new()
+            };
+            Some(TypeDatabase::from_sde(
+                self.load_yaml(TYPES_PATH).await?,
+                self.load_yaml(GROUPS_PATH).await?,
+                self.load_yaml(CATEGORIES_PATH).await?,
+                self.load_yaml(META_GROUPS_PATH).await?,
+                type_dogma,
+            ))
+        } else {
+            logging::warning!(
+                "SDE type database not found, run 'eve-vulcain update' to speed up item loading"
+            );
+            None
+        };
+
+        Ok(SDEIndex {
+            format_version: INDEX_FORMAT_VERSION,
+            blueprints,
+            products,
+            types,
+        })
+    }
+
+    async fn index_from_binary(&self) -> Result<Option<SDEIndex>, SDEErrors> {
+        let index_path = self.cache.cache_directory.join(INDEX_PATH);
+        if !index_path.exists() {
+            logging::warning!("SDE index not found, run 'eve-vulcain update' to speed up startup");
+            return Ok(None);
+        }
+        let content =
+            tokio::fs::read(&index_path)
+                .await
+                .map_err(|source| SDEErrors::IndexReadFailed {
+                    path: index_path.to_display(),
+                    source,
                 })?;
-                let blueprints: HashMap<i32, SDEBlueprint> = serde_yaml::from_str(&content)
-                    .map_err(|source| SDEErrors::YAMLDeserializationError {
-                        path: yaml_path.to_string(),
-                        source,
-                    })?;
-                *cache = Some(blueprints);
+        match bincode::deserialize::<SDEIndex>(&content) {
+            Ok(index) if index.format_version == INDEX_FORMAT_VERSION => Ok(Some(index)),
+            Ok(_) => {
+                logging::warning!("SDE index is outdated, run 'eve-vulcain update' to rebuild it");
+                Ok(None)
+            }
+            Err(e) => {
+                logging::warning!("SDE index could not be read, falling back to YAML: {}", e);
+                Ok(None)
             }
         }
-        Ok(self
-            .blueprints_cache
-            .read()
+    }
+
+    /// Converts the extracted YAML files into the binary index loaded by the other commands.
+    pub async fn build_index(&self) -> Result<(), SDEErrors> {
+        let index = self.index_from_yaml().await?;
+        let content = bincode::serialize(&index)
+            .map_err(|source| SDEErrors::IndexSerializationFailed { source })?;
+        let index_path = self.cache.cache_directory.join(INDEX_PATH);
+        filesystem::write_atomically_async(&index_path, content)
             .await
-            .clone()
-            .expect("Cache should be already filled here"))
+            .map_err(|source| SDEErrors::IndexWriteFailed {
+                path: index_path.to_display(),
+                source,
+            })?;
+        logging::debug!("SDE index written: {}", index_path.to_display());
+        Ok(())
+    }
+
+    async fn load(&self) -> Result<Arc<SDEData>, SDEErrors> {
+        if let Some(data) = self.data.read().await.as_ref() {
+            return Ok(data.clone());
+        }
+        let mut data = self.data.write().await;
+        if let Some(data) = data.as_ref() {
+            return Ok(data.clone());
+        }
+        let index = match self.index_from_binary().await? {
+            Some(index) => index,
+            None => self.index_from_yaml().await?,
+        };
+        let loaded = Arc::new(SDEData::from(index));
+        *data = Some(loaded.clone());
+        Ok(loaded)
+    }
+
+    pub async fn load_blueprints(&self) -> Result<Arc<HashMap<i32, SDEBlueprint>>, SDEErrors> {
+        Ok(self.load().await?.blueprints.clone())
+    }
+
+    /// Loads the type database extracted by `update`. Returns `None` when the SDE was installed by a
+    /// version which didn't extract the type files yet.
+    pub async fn load_type_database(&self) -> Result<Option<Arc<TypeDatabase>>, SDEErrors> {
+        Ok(self.load().await?.types.clone())
     }
 
     pub async fn load_blueprint(
@@ -66,17 +211,149 @@ impl Sde {
         type_id: i32,
         activity: &BlueprintActivityType,
     ) -> Result<Option<(i32, SDEBlueprint)>, SDEErrors> {
-        let blueprints = self.load_blueprints().await?;
+        Ok(self
+            .load_producing_blueprints(type_id, activity)
+            .await?
+            .into_iter()
+            .next())
+    }
+
+    pub async fn load_producing_blueprints(
+        &self,
+        type_id: i32,
+        activity: &BlueprintActivityType,
+    ) -> Result<Vec<(i32, SDEBlueprint)>, SDEErrors> {
+        let data = self.load().await?;
+        Ok(data
+            .products
+            .blueprints(type_id, activity)
+            .iter()
+            .filter_map(|id| data.blueprints.get(id).map(|bp| (*id, bp.clone())))
+            .collect())
+    }
+
+    /// Searches type names tolerating typos, among the types which can be obtained through `activity`.
+    /// Results are ranked from the best match. Returns `None` when the type database isn't installed.
+    pub async fn search_types(
+        &self,
+        query: &str,
+        activity: &BlueprintActivityType,
+        strict: bool,
+    ) -> Result<Option<Vec<i32>>, SDEErrors> {
+        let data = self.load().await?;
+        let types = match &data.types {
+            Some(types) => types,
+            None => return Ok(None),
+        };
+        let mut found: Vec<(usize, &str, i32)> = data
+            .products
+            .searchable_types(activity)
+            .into_iter()
+            .filter_map(|type_id| {
+                let name = types.name(type_id)?;
+                let rank = search::rank(query, name)?;
+                if strict && rank > 0 {
+                    return None;
+                }
+                Some((rank, name, type_id))
+            })
+            .collect();
+        found.sort_by_key(|(rank, name, _)| (*rank, name.len(), *name));
+        Ok(Some(
+            found
+                .into_iter()
+                .take(MAX_SEARCH_RESULTS)
+                .map(|(_, _, type_id)| type_id)
+                .collect(),
+        ))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SDEIndex {
+    format_version: u32,
+    blueprints: HashMap<i32, SDEBlueprint>,
+    products: ProductIndex,
+    types: Option<TypeDatabase>,
+}
+
+/// Blueprints IDs able to produce a type, per activity.
+#[derive(Serialize, Deserialize, Default, Debug)]
+struct ProductIndex {
+    manufacturing: HashMap<i32, Vec<i32>>,
+    invention: HashMap<i32, Vec<i32>>,
+}
+
+impl ProductIndex {
+    fn new(blueprints: &HashMap<i32, SDEBlueprint>) -> Self {
+        let mut index = Self::default();
         for (blueprint_id, blueprint) in blueprints {
-            if blueprint.can_produce(type_id, activity) {
-                return Ok(Some((blueprint_id, blueprint.clone())));
+            if let Some(products) = blueprint
+                .activities
+                .manufacturing
+                .as_ref()
+                .and_then(|m| m.products.as_ref())
+            {
+                for product in products {
+                    index
+                        .manufacturing
+                        .entry(product.type_id)
+                        .or_default()
+                        .push(*blueprint_id);
+                }
+            }
+            if let Some(products) = blueprint
+                .activities
+                .invention
+                .as_ref()
+                .and_then(|i| i.products.as_ref())
+            {
+                for product in products {
+                    index
+                        .invention
+                        .entry(product.type_id)
+                        .or_default()
+                        .push(*blueprint_id);
+                }
             }
         }
-        Ok(None)
+        for ids in index
+            .manufacturing
+            .values_mut()
+            .chain(index.invention.values_mut())
+        {
+            ids.sort();
+        }
+        index
+    }
+
+    fn blueprints(&self, type_id: i32, activity: &BlueprintActivityType) -> &[i32] {
+        let index = match activity {
+            BlueprintActivityType::Manufacturing => &self.manufacturing,
+            BlueprintActivityType::Invention => &self.invention,
+        };
+        index.get(&type_id).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Types which can be manufactured, or whose blueprint can be invented.
+    fn searchable_types(&self, activity: &BlueprintActivityType) -> Vec<i32> {
+        match activity {
+            BlueprintActivityType::Manufacturing => self.manufacturing.keys().copied().collect(),
+            BlueprintActivityType::Invention => self
+                .manufacturing
+                .iter()
+                .filter(|(_, blueprints)| {
+                    blueprints
+                        .iter()
+                        .any(|blueprint_id| self.invention.contains_key(blueprint_id))
+                })
+                .map(|(type_id, _)| *type_id)
+                .collect(),
+        }
     }
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SDEBlueprint {
     pub activities: Activities,
     #[serde(rename = "blueprintTypeID")]
@@ -115,7 +392,7 @@ impl SDEBlueprint {
     }
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Activities {
     pub copying: Option<Copying>,
     pub invention: Option<Invention>,
@@ -124,12 +401,13 @@ pub struct Activities {
     pub research_time: Option<ResearchTime>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Copying {
+    pub skills: Option<Vec<Skills>>,
     pub time: i32,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Invention {
     pub materials: Option<Vec<Item>>,
     pub products: Option<Vec<ProbableMultipleItems>>,
@@ -137,31 +415,34 @@ pub struct Invention {
     pub time: i32,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Manufacturing {
     pub materials: Option<Vec<Item>>,
     pub products: Option<Vec<Item>>,
+    pub skills: Option<Vec<Skills>>,
     pub time: i32,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ResearchMaterial {
+    pub skills: Option<Vec<Skills>>,
     pub time: i32,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ResearchTime {
+    pub skills: Option<Vec<Skills>>,
     pub time: i32,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Item {
     pub quantity: i32,
     #[serde(rename = "typeID")]
     pub type_id: i32,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ProbableMultipleItems {
     pub probability: Option<f64>,
     pub quantity: i32,
@@ -169,13 +450,207 @@ pub struct ProbableMultipleItems {
     pub type_id: i32,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Skills {
     pub level: i32,
     #[serde(rename = "typeID")]
     pub type_id: i32,
 }
 
+/// Types, groups, categories and meta groups, reduced to the fields used to describe items.
+#[derive(Serialize, Deserialize)]
+pub struct TypeDatabase {
+    types: HashMap<i32, TypeRecord>,
+    groups: HashMap<i32, GroupRecord>,
+    categories: HashMap<i32, String>,
+    meta_groups: HashMap<i32, String>,
+    /// Values of the indexed dogma attributes, per type.
+    dogma: HashMap<i32, HashMap<i32, f64>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TypeRecord {
+    name: String,
+    group_id: i32,
+    volume: Option<f64>,
+    meta_group_id: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct GroupRecord {
+    name: String,
+    category_id: i32,
+}
+
+impl TypeDatabase {
+    fn from_sde(
+        types: HashMap<i32, SDEType>,
+        groups: HashMap<i32, SDEGroup>,
+        categories: HashMap<i32, SDECategory>,
+        meta_groups: HashMap<i32, SDEMetaGroup>,
+        type_dogma: HashMap<i32, SDETypeDogma>,
+    ) -> Self {
+        Self {
+            types: types
+                .into_iter()
+                .map(|(id, t)| {
+                    let record = TypeRecord {
+                        name: english_name(&t.name),
+                        group_id: t.group_id,
+                        volume: t.volume,
+                        meta_group_id: t.meta_group_id,
+                    };
+                    (id, record)
+                })
+                .collect(),
+            groups: groups
+                .into_iter()
+                .map(|(id, g)| {
+                    let record = GroupRecord {
+                        name: english_name(&g.name),
+                        category_id: g.category_id,
+                    };
+                    (id, record)
+                })
+                .collect(),
+            categories: categories
+                .into_iter()
+                .map(|(id, c)| (id, english_name(&c.name)))
+                .collect(),
+            meta_groups: meta_groups
+                .into_iter()
+                .map(|(id, m)| (id, english_name(&m.name)))
+                .collect(),
+            dogma: type_dogma
+                .into_iter()
+                .filter_map(|(id, d)| {
+                    let attributes: HashMap<i32, f64> = d
+                        .dogma_attributes
+                        .into_iter()
+                        .filter(|a| INDEXED_DOGMA_ATTRIBUTE_IDS.contains(&a.attribute_id))
+                        .map(|a| (a.attribute_id, a.value))
+                        .collect();
+                    (!attributes.is_empty()).then_some((id, attributes))
+                })
+                .collect(),
+        }
+    }
+
+    fn name(&self, type_id: i32) -> Option<&str> {
+        self.types.get(&type_id).map(|t| t.name.as_str())
+    }
+
+    pub fn type_ids_in_category(&self, category_id: i32) -> Vec<i32> {
+        self.types
+            .iter()
+            .filter(|(_, t)| {
+                self.groups.get(&t.group_id).map(|g| g.category_id) == Some(category_id)
+            })
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    pub fn get(&self, type_id: i32) -> Option<SDETypeInfo> {
+        let sde_type = self.types.get(&type_id)?;
+        let group = self.groups.get(&sde_type.group_id);
+        let category = group.and_then(|g| self.categories.get(&g.category_id));
+        let meta_group = sde_type
+            .meta_group_id
+            .and_then(|id| self.meta_groups.get(&id));
+        Some(SDETypeInfo {
+            name: sde_type.name.clone(),
+            packaged_volume: packaged_volume(sde_type.group_id).or(sde_type.volume),
+            group_name: group.map(|g| g.name.clone()),
+            category_name: category.cloned(),
+            meta_group_id: sde_type.meta_group_id,
+            meta_group_name: meta_group.cloned(),
+            dogma_attributes: self.dogma.get(&type_id).cloned().unwrap_or_default(),
+        })
+    }
+}
+
+fn english_name(names: &HashMap<String, String>) -> String {
+    names
+        .get("en")
+        .cloned()
+        .unwrap_or_else(|| "Unknown".to_string())
+}
+
+/// Ships are repackaged to a fixed volume depending on their group. The SDE only holds the
+/// assembled volume, so the values are copied from the `packaged_volume` ESI reports for the ships
+/// of each group (e.g. 2500 m3 for the frigates of group 25). A group missing here falls back to the
+/// assembled volume.
+fn packaged_volume(group_id: i32) -> Option<f64> {
+    let volume = match group_id {
+        29 | 31 | 1022 => 500.0,
+        25 | 237 | 324 | 830 | 831 | 834 | 893 | 1283 | 1527 => 2500.0,
+        463 | 543 => 3750.0,
+        420 | 541 | 963 | 1305 | 1534 => 5000.0,
+        26 | 358 | 832 | 833 | 894 | 906 | 1972 => 10000.0,
+        419 | 540 | 1201 => 15000.0,
+        28 | 380 | 1202 => 20000.0,
+        27 | 898 | 900 => 50000.0,
+        941 => 500000.0,
+        485 | 513 | 547 | 659 | 883 | 902 | 1538 => 1000000.0,
+        30 => 10000000.0,
+        _ => return None,
+    };
+    Some(volume)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SDETypeInfo {
+    pub name: String,
+    pub packaged_volume: Option<f64>,
+    pub group_name: Option<String>,
+    pub category_name: Option<String>,
+    pub meta_group_id: Option<i32>,
+    pub meta_group_name: Option<String>,
+    /// Values of the industry dogma attributes, by attribute ID. Other attributes aren't indexed.
+    pub dogma_attributes: HashMap<i32, f64>,
+}
+
+#[derive(Deserialize)]
+struct SDEType {
+    #[serde(rename = "groupID")]
+    group_id: i32,
+    name: HashMap<String, String>,
+    volume: Option<f64>,
+    #[serde(rename = "metaGroupID")]
+    meta_group_id: Option<i32>,
+}
+
+#[derive(Deserialize)]
+struct SDEGroup {
+    #[serde(rename = "categoryID")]
+    category_id: i32,
+    name: HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct SDECategory {
+    name: HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct SDEMetaGroup {
+    #[serde(rename = "nameID")]
+    name: HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct SDETypeDogma {
+    #[serde(rename = "dogmaAttributes")]
+    dogma_attributes: Vec<SDEDogmaAttribute>,
+}
+
+#[derive(Deserialize)]
+struct SDEDogmaAttribute {
+    #[serde(rename = "attributeID")]
+    attribute_id: i32,
+    value: f64,
+}
+
 #[derive(Eq, PartialEq, Hash, Debug, Clone)]
 pub enum BlueprintActivityType {
     Manufacturing,
@@ -220,4 +695,169 @@ mod tests {
         assert!(blueprint.can_produce(4, &BlueprintActivityType::Invention));
         assert!(!blueprint.can_produce(1, &BlueprintActivityType::Invention));
     }
+
+    #[test]
+    fn type_database_resolves_type_info() {
+        let types: HashMap<i32, SDEType> = serde_yaml::from_str(
+            r#"
+            587:
+                groupID: 25
+                metaGroupID: 1
+                name:
+                    de: Rifter
+                    en: Rifter
+                published: true
+                volume: 27289.0
+            2185:
+                groupID: 100
+                name:
+                    en: Hammerhead I
+                published: true
+                volume: 5.0
+            27170:
+                groupID: 300
+                name:
+                    en: Zainou 'Beancounter' Industry BX-802
+                published: true
+                volume: 1.0
+            "#,
+        )
+        .unwrap();
+        let groups: HashMap<i32, SDEGroup> = serde_yaml::from_str(
+            r#"
+            25:
+                categoryID: 6
+                name:
+                    en: Frigate
+            100:
+                categoryID: 18
+                name:
+                    en: Combat Drone
+            "#,
+        )
+        .unwrap();
+        let categories: HashMap<i32, SDECategory> = serde_yaml::from_str(
+            r#"
+            6:
+                name:
+                    en: Ship
+            "#,
+        )
+        .unwrap();
+        let meta_groups: HashMap<i32, SDEMetaGroup> = serde_yaml::from_str(
+            r#"
+            1:
+                nameID:
+                    en: Tech I
+            "#,
+        )
+        .unwrap();
+        let type_dogma: HashMap<i32, SDETypeDogma> = serde_yaml::from_str(
+            r#"
+            587:
+                dogmaAttributes:
+                -   attributeID: 9
+                    value: 350.0
+                dogmaEffects: []
+            27170:
+                dogmaAttributes:
+                -   attributeID: 331
+                    value: 8.0
+                -   attributeID: 440
+                    value: -2.0
+                dogmaEffects: []
+            "#,
+        )
+        .unwrap();
+        let database = TypeDatabase::from_sde(types, groups, categories, meta_groups, type_dogma);
+        let database: TypeDatabase =
+            bincode::deserialize(&bincode::serialize(&database).unwrap()).unwrap();
+
+        let rifter = database.get(587).unwrap();
+        assert_eq!(rifter.name, "Rifter");
+        assert_eq!(rifter.packaged_volume, Some(2500.0));
+        assert_eq!(rifter.group_name, Some("Frigate".to_string()));
+        assert_eq!(rifter.category_name, Some("Ship".to_string()));
+        assert_eq!(rifter.meta_group_name, Some("Tech I".to_string()));
+
+        let drone = database.get(2185).unwrap();
+        assert_eq!(drone.packaged_volume, Some(5.0));
+        assert_eq!(drone.group_name, Some("Combat Drone".to_string()));
+        assert_eq!(drone.category_name, None);
+        assert_eq!(drone.meta_group_id, None);
+
+        assert!(rifter.dogma_attributes.is_empty());
+        let implant = database.get(27170).unwrap();
+        assert_eq!(implant.dogma_attributes, HashMap::from([(440, -2.0)]));
+
+        assert_eq!(database.get(1), None);
+    }
+
+    #[test]
+    fn product_index_lists_blueprints_per_activity() {
+        let blueprints: HashMap<i32, SDEBlueprint> = serde_yaml::from_str(
+            r#"
+            10:
+                activities:
+                    manufacturing:
+                        products:
+                            - quantity: 1
+                              typeID: 1
+                        time: 100
+                    invention:
+                        products:
+                            - probability: 0.3
+                              quantity: 1
+                              typeID: 20
+                        time: 100
+                blueprintTypeID: 10
+                maxProductionLimit: 10
+            30:
+                activities:
+                    manufacturing:
+                        products:
+                            - quantity: 1
+                              typeID: 1
+                        time: 100
+                blueprintTypeID: 30
+                maxProductionLimit: 10
+            20:
+                activities:
+                    manufacturing:
+                        products:
+                            - quantity: 1
+                              typeID: 2
+                        time: 100
+                blueprintTypeID: 20
+                maxProductionLimit: 10
+            "#,
+        )
+        .unwrap();
+
+        let index = ProductIndex::new(&blueprints);
+
+        assert_eq!(
+            index.blueprints(1, &BlueprintActivityType::Manufacturing),
+            &[10, 30]
+        );
+        assert_eq!(
+            index.blueprints(2, &BlueprintActivityType::Manufacturing),
+            &[20]
+        );
+        assert_eq!(
+            index.blueprints(20, &BlueprintActivityType::Invention),
+            &[10]
+        );
+        assert!(index
+            .blueprints(20, &BlueprintActivityType::Manufacturing)
+            .is_empty());
+
+        let mut manufacturable = index.searchable_types(&BlueprintActivityType::Manufacturing);
+        manufacturable.sort();
+        assert_eq!(manufacturable, vec![1, 2]);
+        assert_eq!(
+            index.searchable_types(&BlueprintActivityType::Invention),
+            vec![2]
+        );
+    }
 }
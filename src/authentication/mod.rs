@@ -48,7 +48,7 @@ impl<'a, C: Configuration> Authenticator<'a, C> {
         .user_agent("Eve-Vulcain")
         .client_id(&self.cfg.api_client_id()?)
         .callback_url(&self.cfg.api_callback_url()?)
-        .scope("publicData esi-location.read_location.v1 esi-search.search_structures.v1 esi-universe.read_structures.v1 esi-skills.read_skills.v1 esi-wallet.read_character_wallet.v1 esi-industry.read_character_jobs.v1 esi-markets.read_character_orders.v1")
+        .scope("publicData esi-location.read_location.v1 esi-search.search_structures.v1 esi-universe.read_structures.v1 esi-skills.read_skills.v1 esi-clones.read_implants.v1 esi-wallet.read_character_wallet.v1 esi-industry.read_character_jobs.v1 esi-markets.read_character_orders.v1")
         .enable_application_authentication(true);
         if let Some(url) = self.cfg.base_api_url()? {
             logging::info!("Changing Base API URL: {}", url);
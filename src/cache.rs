@@ -6,7 +6,8 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::display::Display;
-use crate::logging;
+use crate::filesystem::DirectoryLock;
+use crate::{filesystem, logging};
 
 static CACHE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
 
@@ -43,6 +44,11 @@ pub enum FSCacheWriteError {
         path: String,
         source: serde_json::Error,
     },
+    #[error("lock directory '{path}': {source}")]
+    LockDirectory {
+        path: String,
+        source: std::io::Error,
+    },
 }
 
 #[derive(Clone)]
@@ -130,7 +136,18 @@ impl FSCache {
         }
     }
 
-    pub fn save_to_cache<T: Serialize>(
+    /// Locks the cache directory, so that the caches persisted together by a process aren't
+    /// interleaved with those of another process.
+    pub async fn lock(&self) -> Result<DirectoryLock, FSCacheWriteError> {
+        DirectoryLock::acquire(&self.cache_directory)
+            .await
+            .map_err(|source| FSCacheWriteError::LockDirectory {
+                path: self.cache_directory.to_display(),
+                source,
+            })
+    }
+
+    pub async fn save_to_cache<T: Serialize>(
         &self,
         name: CacheName,
         data: T,
@@ -146,10 +163,12 @@ impl FSCache {
             path: file.to_display(),
             source,
         })?;
-        std::fs::write(&file, content).map_err(|source| FSCacheWriteError::WriteFileError {
-            path: file.to_display(),
-            source,
-        })?;
+        filesystem::write_atomically_async(&file, content)
+            .await
+            .map_err(|source| FSCacheWriteError::WriteFileError {
+                path: file.to_display(),
+                source,
+            })?;
         logging::debug!("Cache saved: '{}'", name);
         Ok(())
     }
@@ -223,6 +242,7 @@ mod tests {
 
         fs_cache
             .save_to_cache(CacheName::Alliances, vec!["test", "test2", "test3"])
+            .await
             .unwrap();
 
         let content = fs_cache
@@ -247,6 +267,7 @@ mod tests {
 
         fs_cache
             .save_to_cache(CacheName::Alliances, vec!["test", "test2", "test3"])
+            .await
             .unwrap();
 
         let content = fs_cache
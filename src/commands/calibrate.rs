@@ -0,0 +1,236 @@
+use colored::{ColoredString, Colorize};
+use futures_util::future::{try_join3, TryJoinAll};
+use futures_util::TryFutureExt;
+use serde::Serialize;
+use tokio::try_join;
+
+use crate::configuration::cli::CalibrateOptions;
+use crate::display::Display;
+use crate::errors::{EveError, ModelError};
+use crate::integration::DataIntegrator;
+use crate::logging;
+use crate::logging::{Message, Stdout, Verbosity};
+use crate::model::calibration::{calibrate as calibrate_jobs, FacilityCalibration, JobCalibration};
+use crate::model::common::{Identified, Named};
+use crate::model::facility::{Facility, FacilityType};
+use crate::model::industry::{IndustryType, Job};
+use crate::model::prices::Prices;
+
+pub async fn calibrate(eve: &DataIntegrator, opts: &CalibrateOptions) -> Result<(), EveError> {
+    let (facilities, prices, jobs) = try_join3(
+        eve.load_registered_facilities()
+            .map_err(|source| ModelError::LoadingFacilities { source }),
+        eve.load_prices()
+            .map_err(|source| ModelError::LoadingPrices { source }),
+        eve.load_character_industry_jobs()
+            .map_err(|source| ModelError::LoadingIndustryJobs { source }),
+    )
+    .await?;
+    logging::info!("Data retrieved - Compute {} jobs", jobs.len());
+
+    let mut futures = vec![];
+    for job in &jobs {
+        futures.push(calibrate_job(eve, job, &facilities, &prices));
+    }
+    let all_futures = futures.into_iter().collect::<TryJoinAll<_>>();
+    let calibrations: Vec<JobCalibration> =
+        try_join!(all_futures)?.0.into_iter().flatten().collect();
+
+    let tolerance = opts.tolerance / 100.0;
+    let facilities_stdout = calibrate_jobs(calibrations)
+        .iter()
+        .map(|calibration| {
+            let facility = facilities
+                .iter()
+                .find(|facility| facility.id() == calibration.facility_id);
+            FacilityStdout::from(calibration, facility, tolerance)
+        })
+        .collect();
+    logging::stdoutln(CalibrateStdout {
+        tolerance: opts.tolerance,
+        facilities: facilities_stdout,
+    })?;
+    Ok(())
+}
+
+/// Recomputes the installation cost of a manufacturing job with the current data of the
+/// registered facility it runs in. Other activities, jobs installed in unregistered facilities and
+/// jobs without cost are skipped.
+async fn calibrate_job(
+    eve: &DataIntegrator,
+    job: &Job,
+    facilities: &[Facility],
+    prices: &Prices,
+) -> Result<Option<JobCalibration>, EveError> {
+    if job.industry_type != IndustryType::Manufacturing {
+        return Ok(None);
+    }
+    let (actual_cost, item) = match (job.cost, &job.item_produced) {
+        (Some(cost), Some(item)) => (cost, item),
+        _ => return Ok(None),
+    };
+    let manufacture = match facilities
+        .iter()
+        .find(|facility| facility.id() == job.facility_id)
+        .and_then(|facility| facility.manufacture())
+    {
+        Some(manufacture) => manufacture,
+        None => {
+            logging::debug!(
+                "Job facility not registered for manufacturing: {}",
+                job.facility_id
+            );
+            return Ok(None);
+        }
+    };
+
+    let blueprints: Vec<_> = eve
+        .load_item_blueprints(item.id(), IndustryType::Manufacturing)
+        .await?
+        .into_iter()
+        .filter_map(|blueprint| blueprint.activities.manufacturing)
+        .collect();
+    let blueprint = match blueprints
+        .iter()
+        .find(|blueprint| blueprint.blueprint_id == job.blueprint_type_id)
+        .or(blueprints.first())
+    {
+        Some(blueprint) => blueprint,
+        None => return Ok(None),
+    };
+
+    let estimated_item_value = blueprint.estimated_item_value(prices) * (job.runs as f64);
+    Ok(Some(JobCalibration {
+        facility_id: job.facility_id,
+        item_name: item.name(),
+        runs: job.runs,
+        estimated_cost: manufacture.job_installation_cost(estimated_item_value),
+        actual_cost,
+    }))
+}
+
+#[derive(Serialize)]
+pub struct CalibrateStdout {
+    /// Accepted deviation, in percent.
+    tolerance: f64,
+    facilities: Vec<FacilityStdout>,
+}
+
+impl Stdout for CalibrateStdout {}
+
+impl Message for CalibrateStdout {
+    fn standard(&self, verbosity: Verbosity) -> ColoredString {
+        let title = format!(
+            "Job installation costs calibration (tolerance: {} %):",
+            self.tolerance.to_display()
+        );
+        let mut facilities_str = String::new();
+        if self.facilities.is_empty() {
+            facilities_str += "\tNo manufacturing job with a cost in a registered facility\n";
+        }
+        for facility in &self.facilities {
+            facilities_str += facility.standard(verbosity).to_string().as_str();
+        }
+        ColoredString::from(format!("{}\n\n{}", title.bold(), facilities_str).as_str())
+    }
+}
+
+#[derive(Serialize)]
+pub struct FacilityStdout {
+    facility_name: String,
+    /// Relative difference between estimated and actual costs, in percent.
+    deviation: f64,
+    beyond_tolerance: bool,
+    /// Player structures only, taxes and modifiers of NPC stations being fixed.
+    misconfigured: bool,
+    jobs: Vec<JobStdout>,
+}
+
+impl FacilityStdout {
+    fn from(
+        calibration: &FacilityCalibration,
+        facility: Option<&Facility>,
+        tolerance: f64,
+    ) -> Self {
+        let beyond_tolerance = calibration.exceeds_tolerance(tolerance);
+        let misconfigured = beyond_tolerance
+            && facility.is_some_and(|facility| {
+                matches!(facility.facility_type(), FacilityType::Structure(_))
+            });
+        Self {
+            facility_name: facility.map_or_else(
+                || calibration.facility_id.to_string(),
+                |facility| facility.name(),
+            ),
+            deviation: calibration.deviation() * 100.0,
+            beyond_tolerance,
+            misconfigured,
+            jobs: calibration
+                .jobs
+                .iter()
+                .map(|job| JobStdout {
+                    item_name: job.item_name.clone(),
+                    runs: job.runs,
+                    estimated_cost: job.estimated_cost,
+                    actual_cost: job.actual_cost,
+                    deviation: job.deviation() * 100.0,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Message for FacilityStdout {
+    fn standard(&self, verbosity: Verbosity) -> ColoredString {
+        let status = if self.misconfigured {
+            "Check the facility tax and job cost modifier".red()
+        } else if self.beyond_tolerance {
+            "Cost index changed since installation".yellow()
+        } else {
+            "OK".green()
+        };
+        let mut jobs_str = String::new();
+        for job in &self.jobs {
+            jobs_str += job.standard(verbosity).to_string().as_str();
+        }
+        let title = format!("\t> {}:", self.facility_name);
+        ColoredString::from(
+            format!(
+                "{}{:>20} %   {}\n{}\n",
+                title.bold(),
+                self.deviation.to_display(),
+                status,
+                jobs_str
+            )
+            .as_str(),
+        )
+    }
+}
+
+#[derive(Serialize)]
+pub struct JobStdout {
+    item_name: String,
+    runs: i32,
+    estimated_cost: f64,
+    actual_cost: f64,
+    /// Relative difference between estimated and actual cost, in percent.
+    deviation: f64,
+}
+
+impl Message for JobStdout {
+    fn standard(&self, _: Verbosity) -> ColoredString {
+        let estimated_str = format!("{} ISK", self.estimated_cost.to_display());
+        let actual_str = format!("{} ISK", self.actual_cost.to_display());
+        ColoredString::from(
+            format!(
+                "\t\t{:>40}{:>10} run(s){:>25} /{:>25}{:>15} %\n",
+                self.item_name,
+                self.runs,
+                estimated_str,
+                actual_str,
+                self.deviation.to_display()
+            )
+            .as_str(),
+        )
+    }
+}
@@ -0,0 +1,371 @@
+use chrono::{Duration, TimeZone, Utc};
+use colored::{ColoredString, Colorize};
+use serde::Serialize;
+
+use crate::configuration::cli::{
+    HistoryCommands, HistoryDiffOptions, HistoryItemOptions, HistoryOptions,
+};
+use crate::display::Display;
+use crate::errors::{EveError, ModelError};
+use crate::filesystem::FSData;
+use crate::logging;
+use crate::logging::{Message, Stdout, Verbosity};
+use crate::model::history::{diff, Factor, HistoryItem, HistorySnapshot, ItemDiff};
+
+pub async fn history(fs_data: &FSData, opts: &HistoryOptions) -> Result<(), EveError> {
+    let snapshots = fs_data
+        .load_history_snapshots()
+        .await
+        .map_err(|source| ModelError::LoadingHistory { source })?;
+    logging::info!("History loaded - {} runs", snapshots.len());
+
+    match &opts.command {
+        HistoryCommands::Item(item_opts) => item_history(&snapshots, item_opts)?,
+        HistoryCommands::Diff(diff_opts) => diff_history(&snapshots, diff_opts)?,
+    }
+    Ok(())
+}
+
+fn item_history(snapshots: &[HistorySnapshot], opts: &HistoryItemOptions) -> Result<(), EveError> {
+    let search = opts.item.to_lowercase();
+    let mut names: Vec<&String> = snapshots
+        .iter()
+        .flat_map(|snapshot| &snapshot.items)
+        .map(|item| &item.name)
+        .filter(|name| name.to_lowercase().contains(&search))
+        .collect();
+    names.sort();
+    names.dedup();
+    let name = match names.iter().find(|name| name.to_lowercase() == search) {
+        Some(name) => (*name).clone(),
+        None => match names.as_slice() {
+            [name] => (*name).clone(),
+            [] => {
+                return Err(ModelError::HistoryItemNotFound {
+                    search: opts.item.clone(),
+                })?
+            }
+            _ => {
+                return Err(ModelError::AmbiguousHistoryItem {
+                    search: opts.item.clone(),
+                    names: names.iter().map(|name| name.to_string()).collect(),
+                })?
+            }
+        },
+    };
+
+    let since = Utc::now().timestamp() - Duration::days(opts.days).num_seconds();
+    let runs: Vec<HistoryRunStdout> = snapshots
+        .iter()
+        .filter(|snapshot| snapshot.timestamp >= since)
+        .filter_map(|snapshot| {
+            snapshot
+                .items
+                .iter()
+                .find(|item| item.name == name)
+                .map(|item| HistoryRunStdout::from(snapshot.timestamp, item))
+        })
+        .collect();
+    let change = match (runs.first(), runs.last()) {
+        (Some(first), Some(last)) if runs.len() > 1 => Some(HistoryChangeStdout {
+            profits_per_hour: last.profits_per_hour - first.profits_per_hour,
+            sell_price: last.sell_price - first.sell_price,
+            material_cost: last.material_cost - first.material_cost,
+        }),
+        _ => None,
+    };
+    logging::stdoutln(HistoryItemStdout {
+        name,
+        days: opts.days,
+        runs,
+        change,
+    })?;
+    Ok(())
+}
+
+fn diff_history(snapshots: &[HistorySnapshot], opts: &HistoryDiffOptions) -> Result<(), EveError> {
+    let run = |index: usize| {
+        snapshots
+            .iter()
+            .rev()
+            .nth(index)
+            .ok_or(ModelError::HistoryRunNotFound {
+                index,
+                runs: snapshots.len(),
+            })
+    };
+    let previous = run(opts.from)?;
+    let current = run(opts.to)?;
+
+    logging::stdoutln(HistoryDiffStdout {
+        from: date(previous.timestamp),
+        to: date(current.timestamp),
+        items: diff(previous, current)
+            .iter()
+            .map(DiffItemStdout::from)
+            .collect(),
+    })?;
+    Ok(())
+}
+
+fn date(timestamp: i64) -> String {
+    Utc.timestamp_opt(timestamp, 0).single().map_or_else(
+        || timestamp.to_string(),
+        |date| date.format("%Y-%m-%d %H:%M").to_string(),
+    )
+}
+
+fn factor_description(factor: Factor) -> &'static str {
+    match factor {
+        Factor::SellPrice => "sell price",
+        Factor::MaterialCost => "material cost",
+        Factor::InstallationCost => "installation cost",
+        Factor::InventionCost => "invention cost",
+        Factor::ManufacturingTime => "manufacturing time",
+    }
+}
+
+#[derive(Serialize)]
+pub struct HistoryItemStdout {
+    name: String,
+    days: i64,
+    runs: Vec<HistoryRunStdout>,
+    /// Change between the first and the last run of the period.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    change: Option<HistoryChangeStdout>,
+}
+
+impl Stdout for HistoryItemStdout {}
+
+impl Message for HistoryItemStdout {
+    fn standard(&self, verbosity: Verbosity) -> ColoredString {
+        let title = format!("{} - last {} day(s):", self.name, self.days);
+        let mut runs_str = String::new();
+        if self.runs.is_empty() {
+            runs_str += "\tNo run saved over the period\n";
+        } else {
+            runs_str += format!(
+                "\t{:<20}{:>25}{:>25}{:>25}{:>15}\n",
+                "Date", "Profits", "Sell price", "Material cost", "Cost index"
+            )
+            .as_str();
+        }
+        for run in &self.runs {
+            runs_str += run.standard(verbosity).to_string().as_str();
+        }
+        let change_str = match &self.change {
+            Some(change) => change.standard(verbosity).to_string(),
+            None => "".to_string(),
+        };
+        ColoredString::from(format!("{}\n\n{}{}", title.bold(), runs_str, change_str).as_str())
+    }
+}
+
+#[derive(Serialize)]
+pub struct HistoryRunStdout {
+    timestamp: i64,
+    date: String,
+    facility_name: String,
+    profits_per_hour: f64,
+    sell_price: f64,
+    material_cost: f64,
+    /// Manufacturing cost index of the facility system, in percent.
+    cost_index: f64,
+}
+
+impl HistoryRunStdout {
+    fn from(timestamp: i64, item: &HistoryItem) -> Self {
+        Self {
+            timestamp,
+            date: date(timestamp),
+            facility_name: item.facility_name.clone(),
+            profits_per_hour: item.profits_per_hour,
+            sell_price: item.sell_price,
+            material_cost: item.material_cost,
+            cost_index: item.cost_index * 100.0,
+        }
+    }
+}
+
+impl Message for HistoryRunStdout {
+    fn standard(&self, _: Verbosity) -> ColoredString {
+        let profits_str = format!("{} ISK/h", self.profits_per_hour.to_display());
+        let sell_price_str = format!("{} ISK", self.sell_price.to_display());
+        let material_cost_str = format!("{} ISK", self.material_cost.to_display());
+        let cost_index_str = format!("{} %", self.cost_index.to_display());
+        ColoredString::from(
+            format!(
+                "\t{:<20}{:>25}{:>25}{:>25}{:>15}\n",
+                self.date, profits_str, sell_price_str, material_cost_str, cost_index_str
+            )
+            .as_str(),
+        )
+    }
+}
+
+#[derive(Serialize)]
+pub struct HistoryChangeStdout {
+    profits_per_hour: f64,
+    sell_price: f64,
+    material_cost: f64,
+}
+
+impl Message for HistoryChangeStdout {
+    fn standard(&self, _: Verbosity) -> ColoredString {
+        let profits_str = format!("{} ISK/h", self.profits_per_hour.to_display());
+        let sell_price_str = format!("{} ISK", self.sell_price.to_display());
+        let material_cost_str = format!("{} ISK", self.material_cost.to_display());
+        ColoredString::from(
+            format!(
+                "\t{:<20}{:>25}{:>25}{:>25}\n",
+                "Change".bold(),
+                profits_str,
+                sell_price_str,
+                material_cost_str
+            )
+            .as_str(),
+        )
+    }
+}
+
+#[derive(Serialize)]
+pub struct HistoryDiffStdout {
+    from: String,
+    to: String,
+    items: Vec<DiffItemStdout>,
+}
+
+impl Stdout for HistoryDiffStdout {}
+
+impl Message for HistoryDiffStdout {
+    fn standard(&self, verbosity: Verbosity) -> ColoredString {
+        let title = format!("Ranking changes from {} to {}:", self.from, self.to);
+        let mut items_str = String::new();
+        for item in &self.items {
+            items_str += item.standard(verbosity).to_string().as_str();
+        }
+        ColoredString::from(format!("{}\n\n{}", title.bold(), items_str).as_str())
+    }
+}
+
+#[derive(Serialize)]
+pub struct DiffItemStdout {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    previous_rank: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rank: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    previous_profits_per_hour: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    profits_per_hour: Option<f64>,
+    /// Factor which moved the profits per hour the most.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    main_factor: Option<Factor>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    impacts: Vec<ImpactStdout>,
+    /// Manufacturing cost indexes of the facility systems, in percent, explaining installation cost changes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    previous_cost_index: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cost_index: Option<f64>,
+}
+
+impl From<&ItemDiff<'_>> for DiffItemStdout {
+    fn from(diff: &ItemDiff) -> Self {
+        let item = diff
+            .current
+            .or(diff.previous)
+            .map(|(_, item)| item)
+            .expect("Diffed items should be in at least one run");
+        Self {
+            name: item.name.clone(),
+            previous_rank: diff.previous.map(|(rank, _)| rank),
+            rank: diff.current.map(|(rank, _)| rank),
+            previous_profits_per_hour: diff.previous.map(|(_, item)| item.profits_per_hour),
+            profits_per_hour: diff.current.map(|(_, item)| item.profits_per_hour),
+            main_factor: diff.main_factor().map(|impact| impact.factor),
+            impacts: diff
+                .impacts
+                .iter()
+                .filter(|impact| impact.profits_per_hour != 0.0)
+                .map(|impact| ImpactStdout {
+                    factor: impact.factor,
+                    profits_per_hour: impact.profits_per_hour,
+                })
+                .collect(),
+            previous_cost_index: diff.previous.map(|(_, item)| item.cost_index * 100.0),
+            cost_index: diff.current.map(|(_, item)| item.cost_index * 100.0),
+        }
+    }
+}
+
+impl Message for DiffItemStdout {
+    fn standard(&self, verbosity: Verbosity) -> ColoredString {
+        let rank_str = match (self.previous_rank, self.rank) {
+            (Some(previous), Some(current)) if previous > current => {
+                format!("#{} (was #{})", current, previous).green()
+            }
+            (Some(previous), Some(current)) if previous < current => {
+                format!("#{} (was #{})", current, previous).red()
+            }
+            (_, Some(current)) => format!("#{}", current).normal(),
+            (Some(previous), None) => format!("removed (was #{})", previous).yellow(),
+            (None, None) => "".normal(),
+        };
+        let profits_str = match (self.previous_profits_per_hour, self.profits_per_hour) {
+            (Some(previous), Some(current)) => format!(
+                "{} ISK/h ({} ISK/h)",
+                current.to_display(),
+                (current - previous).to_display()
+            ),
+            (None, Some(current)) => format!("{} ISK/h (new)", current.to_display()),
+            (Some(previous), None) => format!("{} ISK/h", previous.to_display()),
+            (None, None) => "".to_string(),
+        };
+        let factor_str = match self.main_factor {
+            Some(factor) => format!("mostly {}", factor_description(factor)),
+            None => "".to_string(),
+        };
+        let mut impacts_str = String::new();
+        for impact in &self.impacts {
+            impacts_str += impact.standard(verbosity).to_string().as_str();
+        }
+        if self.main_factor == Some(Factor::InstallationCost) {
+            if let (Some(previous), Some(current)) = (self.previous_cost_index, self.cost_index) {
+                let cost_index_str =
+                    format!("{} % -> {} %", previous.to_display(), current.to_display());
+                impacts_str +=
+                    format!("\t\t{:>40}{:>25}\n", "Cost index:", cost_index_str).as_str();
+            }
+        }
+        ColoredString::from(
+            format!(
+                "\t{:<25}{:>40}{:>40}   {}\n{}",
+                rank_str, self.name, profits_str, factor_str, impacts_str
+            )
+            .as_str(),
+        )
+    }
+}
+
+#[derive(Serialize)]
+pub struct ImpactStdout {
+    factor: Factor,
+    /// Part of the profits per hour change caused by the factor.
+    profits_per_hour: f64,
+}
+
+impl Message for ImpactStdout {
+    fn standard(&self, _: Verbosity) -> ColoredString {
+        let factor_str = format!("{}:", factor_description(self.factor));
+        ColoredString::from(
+            format!(
+                "\t\t{:>40}{:>25} ISK/h\n",
+                factor_str,
+                self.profits_per_hour.to_display()
+            )
+            .as_str(),
+        )
+    }
+}
@@ -39,7 +39,14 @@ pub(crate) async fn init(args: Args) -> Result<(), EveError> {
     let cache_dir = configuration::get_directory(ConfigurationDirectoryType::Cache, &args)
         .await?
         .ok_or(EnvironmentError::CacheDirectoryUnknown)?;
-    update(cache_dir, &UpdateOptions { no_download: false }).await?;
+    update(
+        cache_dir,
+        &UpdateOptions {
+            no_download: false,
+            from_file: None,
+        },
+    )
+    .await?;
     let cfg = get_config_file(&args)
         .await?
         .ok_or(EnvironmentError::ConfigurationFilePathNotFound)?;
@@ -251,6 +258,7 @@ fn define_application(cfg_file: &PathBuf) -> Result<Option<bool>, EveError> {
         logging::println(Msg("\t\t\tesi-search.search_structures.v1".to_string()));
         logging::println(Msg("\t\t\tesi-universe.read_structures.v1".to_string()));
         logging::println(Msg("\t\t\tesi-skills.read_skills.v1".to_string()));
+        logging::println(Msg("\t\t\tesi-clones.read_implants.v1".to_string()));
         logging::println(Msg("\t\t\tesi-wallet.read_character_wallet.v1".to_string()));
         logging::println(Msg("\t\t\tesi-industry.read_character_jobs.v1".to_string()));
         logging::println(Msg("\t\t\tesi-markets.read_character_orders.v1".to_string()));
@@ -322,6 +330,7 @@ fn save_configuration(
             })?),
         }),
         facilities: None,
+        notifications: None,
     };
     logging::println(Msg(format!(
         "{}",
@@ -432,6 +441,7 @@ fn save_configuration(
                 callback_url: Some(callback_url.trim().to_string()),
             }),
             facilities: None,
+            notifications: None,
         };
     }
 
@@ -0,0 +1,306 @@
+use std::collections::HashSet;
+
+use chrono::Duration;
+use colored::{ColoredString, Colorize};
+use futures_util::future::{try_join3, JoinAll, TryJoinAll};
+use futures_util::TryFutureExt;
+use serde::Serialize;
+use tokio::{join, try_join};
+
+use crate::api::evecache::cache_keys::OrderType;
+use crate::configuration::cli::InventionAllOptions;
+use crate::display::Display;
+use crate::errors::{EveError, ModelError};
+use crate::integration::DataIntegrator;
+use crate::logging;
+use crate::logging::{Message, Stdout, Verbosity};
+use crate::model::blueprint::BlueprintManufacturing;
+use crate::model::character::Character;
+use crate::model::common::{Identified, Named};
+use crate::model::facility::copying::CopyingFacility;
+use crate::model::facility::invention::InventionFacility;
+use crate::model::facility::manufacture::Manufacture;
+use crate::model::facility::markets::{Market, RegionOrders};
+use crate::model::industry::IndustryType;
+use crate::model::items::Item;
+use crate::model::prices::Prices;
+
+pub async fn invention_all(
+    eve: &DataIntegrator,
+    opts: &InventionAllOptions,
+) -> Result<(), EveError> {
+    let (character, facilities, prices) = try_join3(
+        eve.load_character_with_profile(opts.skill_profile.skills.as_deref())
+            .map_err(|source| ModelError::LoadingCharacter { source }),
+        eve.load_registered_facilities()
+            .map_err(|source| ModelError::LoadingFacilities { source }),
+        eve.load_prices()
+            .map_err(|source| ModelError::LoadingPrices { source }),
+    )
+    .await?;
+
+    let items: Vec<Item> = if opts.everything {
+        eve.load_all_items_with_blueprint(IndustryType::Manufacturing)
+            .await?
+            .into_iter()
+            .filter(|item| item.tech_level.is_invented())
+            .collect()
+    } else {
+        eve.load_registered_items()
+            .await?
+            .into_iter()
+            .filter(|item| item.tech_level.is_invented())
+            .collect()
+    };
+
+    logging::info!("Data retrieved - Compute {} items", items.len());
+
+    let mut manufactures = vec![];
+    let mut invention_facilities = vec![];
+    let mut markets = vec![];
+    for facility in &facilities {
+        if let Some(manufacture) = facility.manufacture() {
+            manufactures.push(manufacture);
+        }
+        if let Some(invention) = facility.invention() {
+            invention_facilities.push(invention);
+        }
+        if let Some(market) = facility.market() {
+            markets.push(market);
+        }
+    }
+
+    let copying_facility =
+        CopyingFacility::select(&facilities, opts.copying.copy_facility.as_deref())?;
+
+    let mut region_ids = HashSet::new();
+    for market in &markets {
+        region_ids.insert(market.as_factility().location.constellation.region.id());
+    }
+
+    let mut futures = vec![];
+    for region_id in region_ids {
+        futures.push(eve.load_market_orders(region_id, OrderType::Sell));
+    }
+    let all_futures = futures.into_iter().collect::<TryJoinAll<_>>();
+    let orders = try_join!(all_futures)?.0;
+
+    logging::info!("Compute items invention");
+    let mut futures = vec![];
+    for item in items {
+        let prices = prices.clone();
+        futures.push(async {
+            let blueprints: Vec<BlueprintManufacturing> = eve
+                .load_item_blueprints(item.id(), IndustryType::Manufacturing)
+                .await
+                .map_err(|source| ModelError::LoadingBlueprint { source })?
+                .into_iter()
+                .filter_map(|b| b.activities.manufacturing)
+                .collect();
+
+            load_item(
+                item,
+                blueprints,
+                Facilities {
+                    manufactures: &manufactures,
+                    invention_facilities: &invention_facilities,
+                    copying_facility: copying_facility.as_ref(),
+                    markets: &markets,
+                },
+                &orders,
+                &character,
+                prices,
+                opts.runs,
+            )
+            .await
+        });
+    }
+    let all_futures = futures.into_iter().collect::<TryJoinAll<_>>();
+    let mut items_stdout: Vec<ItemStdout> =
+        try_join!(all_futures)?.0.into_iter().flatten().collect();
+    items_stdout.sort_by(|a, b| {
+        b.profits_per_hour
+            .unwrap_or(f64::MIN)
+            .total_cmp(&a.profits_per_hour.unwrap_or(f64::MIN))
+    });
+    logging::stdoutln(InventionAllStdout {
+        items: items_stdout,
+    })?;
+    Ok(())
+}
+
+struct Facilities<'a> {
+    manufactures: &'a Vec<Manufacture<'a>>,
+    invention_facilities: &'a Vec<InventionFacility<'a>>,
+    copying_facility: Option<&'a CopyingFacility<'a>>,
+    markets: &'a Vec<Market<'a>>,
+}
+
+/// Computes the cheapest invention of the item, and the profits of manufacturing it from the
+/// invented blueprint. Items which can't be invented in the registered facilities are skipped.
+async fn load_item<'a>(
+    item: Item,
+    blueprints: Vec<BlueprintManufacturing>,
+    facilities: Facilities<'a>,
+    orders: &[RegionOrders],
+    character: &Character,
+    prices: Prices,
+    runs: i32,
+) -> Result<Option<ItemStdout>, EveError> {
+    let copying_facility = facilities.copying_facility;
+    let mut futures = vec![];
+    for blueprint in &blueprints {
+        for invention in facilities.invention_facilities {
+            let prices = &prices;
+            futures.push(async move {
+                invention
+                    .cheapest_invention(
+                        blueprint,
+                        None,
+                        copying_facility,
+                        &character.skills,
+                        &character.implants,
+                        prices,
+                    )
+                    .await
+                    .map(|result| (blueprint, result))
+            });
+        }
+    }
+    let all_futures = futures.into_iter().collect::<JoinAll<_>>();
+    let cheapest = join!(all_futures)
+        .0
+        .into_iter()
+        .flatten()
+        .reduce(|acc, res| {
+            if acc.1.value.cost_per_run() > res.1.value.cost_per_run() {
+                res
+            } else {
+                acc
+            }
+        });
+    let (blueprint, invention) = match cheapest {
+        Some(cheapest) => cheapest,
+        None => {
+            logging::debug!("No invention path found for item: {}", item.name());
+            return Ok(None);
+        }
+    };
+
+    let mut blueprint = blueprint.clone();
+    blueprint.material_efficiency = invention.value.blueprint.material_efficiency;
+    blueprint.time_efficiency = invention.value.blueprint.time_efficiency;
+    // Jobs can't run more times than the invented copy allows
+    blueprint.set_runs(runs.min(invention.value.blueprint.runs));
+
+    let mut highest_sell_price = None;
+    for market in facilities.markets {
+        let orders = orders.iter().find(|&order| {
+            order.region.id() == market.as_factility().location.constellation.region.id()
+        });
+        let price = orders
+            .map(|orders| orders.lowest_station_sell_price(item.id(), market.as_factility().id()));
+        // A zero price means the station doesn't sell the item
+        if let Some(price) = price.filter(|price| *price > 0.0) {
+            highest_sell_price = match highest_sell_price {
+                Some(highest) if highest >= price => Some(highest),
+                _ => Some(price),
+            };
+        }
+    }
+
+    let mut profits_per_hour = None;
+    if let Some(sell_price) = highest_sell_price {
+        for manufacture in facilities.manufactures {
+            let cost_per_run = manufacture
+                .manufacture_cost_per_run(
+                    &blueprint,
+                    &character.skills,
+                    &character.implants,
+                    facilities.invention_facilities,
+                    copying_facility,
+                    &prices,
+                )
+                .await?
+                .value;
+            let profit = manufacture.profits_per_hour(
+                &blueprint,
+                item.id(),
+                cost_per_run,
+                sell_price,
+                &character.skills,
+                &character.implants,
+            );
+            if let Some(profit) = profit {
+                profits_per_hour = match profits_per_hour {
+                    Some(highest) if highest >= profit => Some(highest),
+                    _ => Some(profit),
+                };
+            }
+        }
+    }
+
+    logging::debug!("Computed invention of item: {}", item.name());
+    Ok(Some(ItemStdout {
+        name: item.name(),
+        invention_cost_per_run: invention.value.cost_per_run(),
+        success_chance: invention.details.success_probability.final_success_chance,
+        time_per_blueprint: invention.value.time_normalized as i64,
+        profits_per_hour,
+    }))
+}
+
+#[derive(Serialize)]
+pub struct InventionAllStdout {
+    items: Vec<ItemStdout>,
+}
+
+impl Stdout for InventionAllStdout {}
+
+impl Message for InventionAllStdout {
+    fn standard(&self, verbosity: Verbosity) -> ColoredString {
+        let mut items_str = format!(
+            "{:>50}{:>25}{:>12}{:>20}{:>30}\n",
+            "", "Invention", "Success", "Time per", "Manufacturing"
+        );
+        items_str += format!(
+            "{:>50}{:>25}{:>12}{:>20}{:>30}\n",
+            "", "(ISK/run)", "", "blueprint", "(ISK/h)"
+        )
+        .as_str();
+        for item in &self.items {
+            items_str += item.standard(verbosity).to_string().as_str();
+        }
+        ColoredString::from(items_str.as_str())
+    }
+}
+
+#[derive(Serialize)]
+pub struct ItemStdout {
+    name: String,
+    invention_cost_per_run: f64,
+    success_chance: f64,
+    /// Invention time of one successful blueprint copy, in seconds.
+    time_per_blueprint: i64,
+    profits_per_hour: Option<f64>,
+}
+
+impl Message for ItemStdout {
+    fn standard(&self, _: Verbosity) -> ColoredString {
+        let profits_str = match self.profits_per_hour {
+            Some(profits) => profits.to_display(),
+            None => "N/A".to_string(),
+        };
+        ColoredString::from(
+            format!(
+                "{:>50}{:>25}{:>10} %{:>20}{:>30}\n",
+                self.name.bold(),
+                self.invention_cost_per_run.to_display(),
+                (self.success_chance * 100.0).to_display(),
+                Duration::seconds(self.time_per_blueprint).to_display(),
+                profits_str
+            )
+            .as_str(),
+        )
+    }
+}
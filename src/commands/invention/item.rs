@@ -19,30 +19,30 @@ use crate::{
             InputMaterialsCostsDetails, MultipleItemsCostDetails, MultipleItemsOrdersDetails,
         },
         common::{DetailedCalculation, Identified, Named},
-        facility::invention::InventionFacilityProperties,
+        facility::copying::CopyingFacility,
         industry::IndustryType,
+        skills::MissingSkill,
     },
-    vector::UniqueElement,
 };
 
 pub async fn invention(eve: &DataIntegrator, opts: &InventionItemOptions) -> Result<(), EveError> {
-    let item_to_invent =
-        match interactive::load_item(eve, opts.item.clone(), opts.strict, None).await? {
-            Some(item_to_invent) => item_to_invent,
-            None => return Ok(()),
-        };
+    let item_to_invent = match interactive::load_item(
+        eve,
+        opts.item.clone(),
+        opts.strict,
+        IndustryType::Invention,
+        None,
+    )
+    .await?
+    {
+        Some(item_to_invent) => item_to_invent,
+        None => return Ok(()),
+    };
 
-    let manufacturing_blueprint = eve
-        .load_item_blueprints(item_to_invent.id(), IndustryType::Manufacturing)
-        .await?
-        .unique(&format!(
-            "Manufacturing blueprints: {}",
-            item_to_invent.id()
-        ))?;
-    let (mut found_blueprints, character, facilities, prices) = try_join4(
-        eve.load_item_blueprints(manufacturing_blueprint.id(), IndustryType::Invention)
+    let (manufacturing_blueprints, character, facilities, prices) = try_join4(
+        eve.load_item_blueprints(item_to_invent.id(), IndustryType::Manufacturing)
             .map_err(|source| ModelError::LoadingBlueprint { source }),
-        eve.load_character()
+        eve.load_character_with_profile(opts.skill_profile.skills.as_deref())
             .map_err(|source| ModelError::LoadingCharacter { source }),
         eve.load_registered_facilities()
             .map_err(|source| ModelError::LoadingFacilities { source }),
@@ -51,20 +51,6 @@ pub async fn invention(eve: &DataIntegrator, opts: &InventionItemOptions) -> Res
     )
     .await?;
 
-    let invention_blueprint = found_blueprints.unique(&format!(
-        "Invention blueprints: {}",
-        manufacturing_blueprint.id()
-    ))?;
-
-    let invention_blueprint = match invention_blueprint.activities.invention {
-        Some(blueprint_invention) => blueprint_invention,
-        None => {
-            return Err(ModelError::IsNotAnInventionBlueprint {
-                blueprint_id: invention_blueprint.id(),
-            })?;
-        }
-    };
-
     let mut invention_facilities = vec![];
     for facility in &facilities {
         if let Some(invention_facility) = facility.invention() {
@@ -72,9 +58,106 @@ pub async fn invention(eve: &DataIntegrator, opts: &InventionItemOptions) -> Res
         }
     }
 
+    let copying_facility =
+        CopyingFacility::select(&facilities, opts.copying.copy_facility.as_deref())?;
+
+    let mut sources = vec![];
+    for manufacturing_blueprint in manufacturing_blueprints {
+        let manufacturing = match &manufacturing_blueprint.activities.manufacturing {
+            Some(manufacturing) => manufacturing,
+            None => continue,
+        };
+        let invention_blueprints = eve
+            .load_item_blueprints(manufacturing_blueprint.id(), IndustryType::Invention)
+            .await
+            .map_err(|source| ModelError::LoadingBlueprint { source })?;
+        for invention_blueprint in invention_blueprints {
+            let invention = match invention_blueprint.activities.invention {
+                Some(blueprint_invention) => blueprint_invention,
+                None => {
+                    return Err(ModelError::IsNotAnInventionBlueprint {
+                        blueprint_id: invention_blueprint.id(),
+                    })?;
+                }
+            };
+            if opts.source.is_some() && opts.source != Some(invention.blueprint_id) {
+                continue;
+            }
+
+            let mut cost_per_run = None;
+            for facility in &invention_facilities {
+                let result = facility
+                    .invent_item(
+                        manufacturing,
+                        &invention,
+                        copying_facility.as_ref(),
+                        &character.skills,
+                        &character.implants,
+                        &prices,
+                    )
+                    .await;
+                let cost = result.value.cost_per_run();
+                cost_per_run = match cost_per_run {
+                    Some(c) if c <= cost => Some(c),
+                    _ => Some(cost),
+                };
+            }
+            sources.push((manufacturing_blueprint.clone(), invention, cost_per_run));
+        }
+    }
+    sources.sort_by(|(_, _, a), (_, _, b)| a.unwrap_or(f64::MAX).total_cmp(&b.unwrap_or(f64::MAX)));
+    if sources.is_empty() {
+        return Err(ModelError::ProductionPathNotFound {
+            name: item_to_invent.name(),
+            type_id: item_to_invent.id(),
+        })?;
+    }
+
+    let mut sources_stdout = vec![];
+    if sources.len() > 1 {
+        for (index, (_, invention, cost_per_run)) in sources.iter().enumerate() {
+            sources_stdout.push(SourceStdout {
+                blueprint_id: invention.blueprint_id,
+                relic: invention.relic.as_ref().map(|relic| relic.name()),
+                cost_per_run: *cost_per_run,
+                selected: index == 0,
+            });
+        }
+    }
+    let (manufacturing_blueprint, invention_blueprint, _) = sources.remove(0);
+
+    let missing_skills = character
+        .skills
+        .missing_skills(&invention_blueprint.skills)
+        .iter()
+        .map(MissingSkillStdout::from)
+        .collect();
+
+    let copying_stdout = match (&copying_facility, &invention_blueprint.copying) {
+        (Some(facility), Some(blueprint)) => {
+            let copying =
+                facility.copy_blueprint(blueprint, &character.skills, &character.implants, &prices);
+            Some(CopyingStdout {
+                facility_name: copying.value.facility,
+                estimated_item_value: copying.details.estimated_item_value,
+                system_cost_index: facility.facility().location.indexes.copying,
+                facility_tax: copying.details.facility_tax,
+                job_cost_modifier: copying.details.job_cost_modifier,
+                total: copying.value.cost,
+                time: copying.value.time,
+                implants: copying.value.implants,
+            })
+        }
+        _ => None,
+    };
+    let (copying_cost, copying_time) = match &copying_stdout {
+        Some(copying) => (copying.total, copying.time),
+        None => (0.0, 0),
+    };
+
     let input_materials_costs = invention_blueprint
-        .materials
-        .input_materials_cost(None, None, &prices);
+        .input_materials()
+        .input_materials_cost(None, None, 1, &prices);
     let inputs_stdout = InputsStdout::from(&input_materials_costs);
 
     let estimated_item_value = manufacturing_blueprint
@@ -144,13 +227,14 @@ pub async fn invention(eve: &DataIntegrator, opts: &InventionItemOptions) -> Res
         let success_probability_stdout = success_probability_stdout.clone();
         futures.push(async move {
             let installation_cost = facility.job_installation_cost(estimated_item_value).value;
-            let total_run = installation_cost + input_materials_costs.value;
+            let total_run = installation_cost + input_materials_costs.value + copying_cost;
             let normalization_factor = 1.0 / final_success_chance;
             let total_cost_normalized = total_run * normalization_factor;
             let total_time_run = facility
                 .time_per_run(&invention_blueprint, &manufacturing_skills)
                 .value;
-            let total_time_normalized = (total_time_run as f64) * normalization_factor;
+            let total_time_normalized =
+                ((total_time_run + copying_time) as f64) * normalization_factor;
             let total_time_normalized = total_time_normalized as i64;
 
             FacilityStdout {
@@ -183,6 +267,9 @@ pub async fn invention(eve: &DataIntegrator, opts: &InventionItemOptions) -> Res
 
     logging::stdoutln(InventionStdout {
         searched_item_name: item_to_invent.name(),
+        sources: sources_stdout,
+        missing_skills,
+        copying: copying_stdout,
         facilities,
     })?;
     Ok(())
@@ -191,6 +278,12 @@ pub async fn invention(eve: &DataIntegrator, opts: &InventionItemOptions) -> Res
 #[derive(Serialize, Debug)]
 struct InventionStdout {
     searched_item_name: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    sources: Vec<SourceStdout>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    missing_skills: Vec<MissingSkillStdout>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    copying: Option<CopyingStdout>,
     facilities: Vec<FacilityStdout>,
 }
 
@@ -198,12 +291,145 @@ impl Stdout for InventionStdout {}
 
 impl Message for InventionStdout {
     fn standard(&self, verbosity: Verbosity) -> ColoredString {
+        let mut sources = String::new();
+        if !self.sources.is_empty() {
+            sources += "\tInvention sources (cheapest facility, per invented run):\n";
+            for source in &self.sources {
+                sources += source.standard(verbosity).to_string().as_str();
+            }
+            sources += "\n";
+        }
+
+        let mut missing_skills = String::new();
+        if !self.missing_skills.is_empty() {
+            missing_skills += "\tMissing skills (trained / required):\n";
+            for skill in &self.missing_skills {
+                missing_skills += skill.standard(verbosity).to_string().as_str();
+            }
+            missing_skills += "\n";
+        }
+
+        let copying = match &self.copying {
+            None => String::new(),
+            Some(copying) => copying.standard(verbosity).to_string(),
+        };
+
         let mut facilities = String::from("\tInvention facilities:\n");
         for facility in &self.facilities {
             facilities += facility.standard(verbosity).to_string().as_str();
         }
         ColoredString::from(
-            format!("{}:\n\n{}", self.searched_item_name.bold(), facilities).as_str(),
+            format!(
+                "{}:\n\n{}{}{}{}",
+                self.searched_item_name.bold(),
+                sources,
+                missing_skills,
+                copying,
+                facilities
+            )
+            .as_str(),
+        )
+    }
+}
+
+#[derive(Serialize, Debug)]
+struct SourceStdout {
+    blueprint_id: i32,
+    relic: Option<String>,
+    cost_per_run: Option<f64>,
+    selected: bool,
+}
+
+impl Message for SourceStdout {
+    fn standard(&self, _: Verbosity) -> ColoredString {
+        let cost = match self.cost_per_run {
+            None => "N/A".to_string(),
+            Some(cost) => format!("{} ISK/run", cost.to_display()),
+        };
+        let source = match &self.relic {
+            Some(relic) => relic.clone(),
+            None => format!("Blueprint {}", self.blueprint_id),
+        };
+        let line = format!(
+            "\t\t{} {:<50}{:>34}\n",
+            if self.selected { ">" } else { " " },
+            source,
+            cost
+        );
+        if self.selected {
+            ColoredString::from(line.as_str()).bold()
+        } else {
+            ColoredString::from(line.as_str())
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+struct MissingSkillStdout {
+    name: String,
+    required_level: i32,
+    trained_level: i32,
+}
+
+impl From<&MissingSkill> for MissingSkillStdout {
+    fn from(skill: &MissingSkill) -> Self {
+        MissingSkillStdout {
+            name: skill.name.clone(),
+            required_level: skill.required_level,
+            trained_level: skill.trained_level,
+        }
+    }
+}
+
+impl Message for MissingSkillStdout {
+    fn standard(&self, _: Verbosity) -> ColoredString {
+        let levels = format!("{} / {}", self.trained_level, self.required_level);
+        ColoredString::from(format!("\t\t{:<50}{:>34}\n", self.name, levels).as_str()).red()
+    }
+}
+
+#[derive(Serialize, Debug)]
+struct CopyingStdout {
+    facility_name: String,
+    estimated_item_value: f64,
+    system_cost_index: f64,
+    facility_tax: f64,
+    job_cost_modifier: Option<f64>,
+    total: f64,
+    time: i32,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    implants: Vec<String>,
+}
+
+impl Message for CopyingStdout {
+    fn standard(&self, _: Verbosity) -> ColoredString {
+        let job_cost_mod = match self.job_cost_modifier {
+            None => String::new(),
+            Some(modifier) => {
+                format!(
+                    "\t\t\tJob cost modifier: {:>51} %\n",
+                    (modifier * 100.0).to_display()
+                )
+            }
+        };
+        let total_str = format!("{} ISK", self.total.to_display().underline());
+        let mut implants = String::new();
+        for implant in &self.implants {
+            implants += format!("\t\t\tImplant: {:>69}\n", implant).as_str();
+        }
+        ColoredString::from(
+            format!(
+                "\tBlueprint copy (included in each run):\n\t> {}:\n\t\t\tEstimated item value: {:>48} ISK\n\t\t\tSystem cost index: {:>51} %\n{}\t\t\tFacility Tax: {:>56} %\n\t\tTotal for job installation cost: {:>57}\n{}\t\tTime required per run: {:>55}\n\n",
+                self.facility_name.bold(),
+                self.estimated_item_value.to_display(),
+                (self.system_cost_index * 100.0).to_display(),
+                job_cost_mod,
+                (self.facility_tax * 100.0).to_display(),
+                total_str,
+                implants,
+                Duration::seconds(self.time as i64).to_display()
+            )
+            .as_str(),
         )
     }
 }
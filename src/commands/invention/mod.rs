@@ -4,11 +4,13 @@ use crate::{
     integration::DataIntegrator,
 };
 
+mod all;
 mod item;
 
 pub async fn invention(eve: &DataIntegrator, opts: &InventionOptions) -> Result<(), EveError> {
     match &opts.command {
         InventionCommands::Item(item_opts) => item::invention(eve, item_opts).await?,
+        InventionCommands::All(all_opts) => all::invention_all(eve, all_opts).await?,
     }
     Ok(())
 }
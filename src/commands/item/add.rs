@@ -1,28 +1,21 @@
-use std::collections::HashMap;
-
 use inquire::{min_length, required, Confirm, Select, Text};
 
-use crate::api::sde::{BlueprintActivityType, SDEBlueprint};
 use crate::configuration::cli::ItemAddOptions;
 use crate::errors::{EnvironmentError, EveError, ModelError};
-use crate::integration::{DataIntegrator, LoadFrom};
+use crate::integration::DataIntegrator;
 use crate::interactive::HandleInquireExitSignals;
 use crate::logging;
 use crate::logging::Msg;
 use crate::model::common::Identified;
+use crate::model::industry::IndustryType;
 
 pub async fn add(eve: &DataIntegrator, opts: &ItemAddOptions) -> Result<(), EveError> {
-    let blueprints = eve
-        .api()
-        .get_blueprints()
-        .await
-        .map_err(|source| ModelError::LoadBlueprints { source })?;
     logging::println(Msg(
         "You're about to add an item used by other eve-vulcain commands.".to_string(),
     ));
 
     loop {
-        if add_item(eve, opts, &blueprints).await? {
+        if add_item(eve, opts).await? {
             return Ok(());
         }
 
@@ -44,11 +37,7 @@ pub async fn add(eve: &DataIntegrator, opts: &ItemAddOptions) -> Result<(), EveE
     }
 }
 
-async fn add_item(
-    eve: &DataIntegrator,
-    opts: &ItemAddOptions,
-    blueprints: &HashMap<i32, SDEBlueprint>,
-) -> Result<bool, EveError> {
+async fn add_item(eve: &DataIntegrator, opts: &ItemAddOptions) -> Result<bool, EveError> {
     let mut default_stop = false;
     let search = match &opts.item {
         None => {
@@ -76,7 +65,7 @@ async fn add_item(
 
     logging::trace!("Search for item name: '{}'", search);
     let items = eve
-        .search_items(LoadFrom::Name(search.to_string()), false)
+        .search_producible_items(&search, IndustryType::Manufacturing, false)
         .await?;
     if items.is_empty() {
         return Err(ModelError::SearchedItemNotFound {
@@ -84,11 +73,6 @@ async fn add_item(
         })?;
     }
 
-    let items = items
-        .into_iter()
-        .filter(|i| can_manufacture(blueprints, i.id()))
-        .collect();
-
     let item = Select::new("Select one of the found items ", items)
         .prompt()
         .handle_exit_signals()
@@ -107,12 +91,3 @@ async fn add_item(
 
     Ok(default_stop)
 }
-
-fn can_manufacture(blueprints: &HashMap<i32, SDEBlueprint>, item_id: i32) -> bool {
-    for (_, blueprint) in blueprints.iter() {
-        if blueprint.can_produce(item_id, &BlueprintActivityType::Manufacturing) {
-            return true;
-        }
-    }
-    false
-}
@@ -41,16 +41,28 @@ impl Message for ItemsLSStdout {
 #[derive(Serialize)]
 pub struct ItemStdout {
     name: String,
+    category: Option<String>,
+    group: Option<String>,
 }
 
 impl ItemStdout {
     pub fn from(item: &Item) -> Self {
-        Self { name: item.name() }
+        let classification = item.classification.as_ref();
+        Self {
+            name: item.name(),
+            category: classification.and_then(|c| c.category.clone()),
+            group: classification.and_then(|c| c.group.clone()),
+        }
     }
 }
 
 impl Message for ItemStdout {
     fn standard(&self, _: Verbosity) -> ColoredString {
-        ColoredString::from(format!("{}\n", self.name).as_str())
+        match (&self.category, &self.group) {
+            (Some(category), Some(group)) => {
+                ColoredString::from(format!("{:<50}{} > {}\n", self.name, category, group).as_str())
+            }
+            _ => ColoredString::from(format!("{}\n", self.name).as_str()),
+        }
     }
 }
@@ -0,0 +1,20 @@
+use crate::commands::jobs::watch::watch;
+use crate::configuration::cli::{JobsCommands, JobsOptions};
+use crate::errors::EveError;
+use crate::integration::DataIntegrator;
+use crate::notification::Notifier;
+
+mod watch;
+
+pub async fn jobs(
+    eve: &DataIntegrator,
+    notifiers: &[Notifier],
+    opts: &JobsOptions,
+) -> Result<(), EveError> {
+    match &opts.command {
+        JobsCommands::Watch(watch_opts) => {
+            watch(eve, notifiers, watch_opts).await?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,171 @@
+use chrono::Utc;
+use colored::{ColoredString, Colorize};
+use futures_util::future::try_join;
+use futures_util::TryFutureExt;
+use serde::Serialize;
+
+use crate::configuration::cli::JobsWatchOptions;
+use crate::errors::{EveError, ModelError};
+use crate::integration::DataIntegrator;
+use crate::logging;
+use crate::logging::{Message, Stdout, Verbosity};
+use crate::model::common::Named;
+use crate::model::industry::{IndustryType, Job, JobSlots, JobTracker, JobTransition, SlotType};
+use crate::notification::{notify_all, Notification, Notifier};
+
+pub async fn watch(
+    eve: &DataIntegrator,
+    notifiers: &[Notifier],
+    opts: &JobsWatchOptions,
+) -> Result<(), EveError> {
+    let (character, jobs) = try_join(
+        eve.load_character()
+            .map_err(|source| ModelError::LoadingCharacter { source }),
+        eve.load_character_industry_jobs()
+            .map_err(|source| ModelError::LoadingIndustryJobs { source }),
+    )
+    .await?;
+    logging::stdoutln(IdleSlotsStdout::from(&character.skills.job_slots(&jobs)))?;
+
+    let mut tracker = JobTracker::default();
+    tracker.update(jobs, Utc::now());
+    loop {
+        // Jobs are cached until the Eve API refreshes them, polls in between return the same jobs
+        tokio::time::sleep(std::time::Duration::from_secs(opts.interval * 60)).await;
+        // A daemon shouldn't stop on a temporary ESI failure
+        if let Err(err) = poll(eve, notifiers, &mut tracker).await {
+            logging::warning!("Could not poll industry jobs: {}", err);
+        }
+    }
+}
+
+async fn poll(
+    eve: &DataIntegrator,
+    notifiers: &[Notifier],
+    tracker: &mut JobTracker,
+) -> Result<(), EveError> {
+    let jobs = eve
+        .load_character_industry_jobs()
+        .await
+        .map_err(|source| ModelError::LoadingIndustryJobs { source })?;
+    logging::info!("Industry jobs retrieved - {} running jobs", jobs.len());
+
+    for (job, transition) in tracker.update(jobs, Utc::now()) {
+        let stdout = JobTransitionStdout::from(&job, transition);
+        notify_all(notifiers, &stdout.notification()).await;
+        logging::stdoutln(stdout)?;
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+pub struct IdleSlotsStdout {
+    slots: Vec<IdleSlotStdout>,
+}
+
+impl IdleSlotsStdout {
+    fn from(slots: &[JobSlots]) -> Self {
+        Self {
+            slots: slots
+                .iter()
+                .map(|slots| IdleSlotStdout {
+                    slot_type: slots.slot_type,
+                    idle: slots.free(),
+                    max: slots.max,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Stdout for IdleSlotsStdout {}
+
+impl Message for IdleSlotsStdout {
+    fn standard(&self, verbosity: Verbosity) -> ColoredString {
+        let mut slots_str = String::new();
+        for slot in &self.slots {
+            slots_str += slot.standard(verbosity).to_string().as_str();
+        }
+        ColoredString::from(format!("Idle job slots:\n{slots_str}").as_str())
+    }
+}
+
+#[derive(Serialize)]
+pub struct IdleSlotStdout {
+    slot_type: SlotType,
+    idle: i32,
+    max: i32,
+}
+
+impl Message for IdleSlotStdout {
+    fn standard(&self, _: Verbosity) -> ColoredString {
+        let idle = format!("{}/{}", self.idle, self.max);
+        let idle = if self.idle > 0 {
+            idle.yellow()
+        } else {
+            idle.normal()
+        };
+        ColoredString::from(
+            format!(
+                "\t{:>40}{:>10} idle\n",
+                self.slot_type.to_string().bold(),
+                idle
+            )
+            .as_str(),
+        )
+    }
+}
+
+#[derive(Serialize)]
+pub struct JobTransitionStdout {
+    transition: JobTransition,
+    industry_type: IndustryType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    item_name: Option<String>,
+    runs: i32,
+}
+
+impl JobTransitionStdout {
+    fn from(job: &Job, transition: JobTransition) -> Self {
+        Self {
+            transition,
+            industry_type: job.industry_type,
+            item_name: job.item_produced.as_ref().map(|item| item.name()),
+            runs: job.runs,
+        }
+    }
+
+    fn description(&self) -> String {
+        match &self.item_name {
+            Some(item_name) => format!(
+                "{} of {} ({} run(s))",
+                self.industry_type, item_name, self.runs
+            ),
+            None => format!("{} ({} run(s))", self.industry_type, self.runs),
+        }
+    }
+
+    fn notification(&self) -> Notification {
+        Notification {
+            title: format!(
+                "eve-vulcain: Job {}",
+                self.transition.to_string().to_lowercase()
+            ),
+            message: self.description(),
+        }
+    }
+}
+
+impl Stdout for JobTransitionStdout {}
+
+impl Message for JobTransitionStdout {
+    fn standard(&self, _: Verbosity) -> ColoredString {
+        let transition = format!("{}:", self.transition.to_string().to_uppercase());
+        let transition = match self.transition {
+            JobTransition::Started => transition.normal(),
+            JobTransition::Ready => transition.yellow().bold(),
+            JobTransition::Delivered => transition.green(),
+        };
+        ColoredString::from(format!("{:<11}{}", transition, self.description()).as_str())
+    }
+}
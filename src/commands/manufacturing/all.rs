@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use chrono::Duration;
+use chrono::{Duration, Utc};
 use colored::{ColoredString, Colorize};
 use futures_util::future::{try_join3, TryJoinAll};
 use futures_util::TryFutureExt;
@@ -18,12 +18,15 @@ use crate::logging::{Message, Stdout, Verbosity};
 use crate::model::blueprint::BlueprintManufacturing;
 use crate::model::character::Character;
 use crate::model::common::{Identified, Named};
+use crate::model::facility::copying::CopyingFacility;
 use crate::model::facility::invention::InventionFacility;
 use crate::model::facility::manufacture::{Manufacture, ManufactureError};
 use crate::model::facility::markets::{Market, RegionOrders};
+use crate::model::history::{HistoryItem, HistorySnapshot};
 use crate::model::industry::IndustryType;
 use crate::model::items::Item;
 use crate::model::prices::Prices;
+use crate::model::skills::MissingSkill;
 
 pub async fn manufacture_all(
     eve: &DataIntegrator,
@@ -31,7 +34,7 @@ pub async fn manufacture_all(
     opts: &ManufactureAllOptions,
 ) -> Result<(), EveError> {
     let (character, facilities, prices) = try_join3(
-        eve.load_character()
+        eve.load_character_with_profile(opts_manufacturing.skill_profile.skills.as_deref())
             .map_err(|source| ModelError::LoadingCharacter { source }),
         eve.load_registered_facilities()
             .map_err(|source| ModelError::LoadingFacilities { source }),
@@ -64,6 +67,11 @@ pub async fn manufacture_all(
         }
     }
 
+    let copying_facility = CopyingFacility::select(
+        &facilities,
+        opts_manufacturing.copying.copy_facility.as_deref(),
+    )?;
+
     let mut region_ids = HashSet::new();
     for market in &markets {
         region_ids.insert(market.as_factility().location.constellation.region.id());
@@ -98,12 +106,14 @@ pub async fn manufacture_all(
             let mut blueprint = found_blueprints.remove(0);
             blueprint.material_efficiency = opts_manufacturing.material_efficiency;
             blueprint.time_efficiency = opts_manufacturing.time_efficiency;
+            blueprint.set_runs(opts_manufacturing.runs);
             load_item(
                 item,
                 blueprint,
                 Facilities {
                     manufactures: &manufactures,
                     invention_facilities: &invention_facilities,
+                    copying_facility: copying_facility.as_ref(),
                     markets: &markets,
                 },
                 &orders,
@@ -115,15 +125,29 @@ pub async fn manufacture_all(
         });
     }
     let all_futures = futures.into_iter().collect::<TryJoinAll<_>>();
-    let mut item_sdout = try_join!(all_futures)?.0;
-    item_sdout.sort_by(|a, b| b.profits_per_hour.partial_cmp(&a.profits_per_hour).unwrap());
+    let mut results = try_join!(all_futures)?.0;
+    if opts.buildable_only {
+        results.retain(|(item, _)| item.missing_skills.is_empty());
+    }
+    results.sort_by(|(a, _), (b, _)| b.profits_per_hour.partial_cmp(&a.profits_per_hour).unwrap());
+    let (item_sdout, history_items): (Vec<ItemStdout>, Vec<Option<HistoryItem>>) =
+        results.into_iter().unzip();
     logging::stdoutln(ManufactureAllStdout { items: item_sdout })?;
+
+    eve.fs()
+        .save_history_snapshot(&HistorySnapshot {
+            timestamp: Utc::now().timestamp(),
+            items: history_items.into_iter().flatten().collect(),
+        })
+        .await
+        .map_err(|source| ModelError::SavingHistorySnapshot { source })?;
     Ok(())
 }
 
 struct Facilities<'a> {
     manufactures: &'a Vec<Manufacture<'a>>,
     invention_facilities: &'a Vec<InventionFacility<'a>>,
+    copying_facility: Option<&'a CopyingFacility<'a>>,
     markets: &'a Vec<Market<'a>>,
 }
 
@@ -135,7 +159,7 @@ async fn load_item<'a>(
     character: &Character,
     prices: Prices,
     everything: bool,
-) -> Result<ItemStdout, EveError> {
+) -> Result<(ItemStdout, Option<HistoryItem>), EveError> {
     let output_quantity = blueprint.get_product(item.id())
         .unwrap_or_else(|| panic!("Product should exist because Blueprint ({}) hase been loaded based on it's ID ({})", blueprint.blueprint_id, item.id()))
         .quantity;
@@ -182,35 +206,85 @@ async fn load_item<'a>(
                 .manufacture_cost_per_run(
                     &blueprint,
                     &character.skills,
+                    &character.implants,
                     facilities.invention_facilities,
+                    facilities.copying_facility,
                     &prices,
                 )
                 .await?;
-            let cost_per_unit = cost_per_run / (output_quantity as f64);
+            let cost_per_unit = cost_per_run.value / (output_quantity as f64);
             let margin = highest_sell_price - cost_per_unit;
-            let time_per_run =
-                manufacture.time_per_run(&blueprint, &character.skills.get_manufacturing_skill());
-            let time_per_unit = time_per_run / output_quantity;
-            let unit_per_hour = 3600.0 / (time_per_unit as f64);
+            let time_per_job = manufacture.time_per_job(
+                &blueprint,
+                &character.skills.get_manufacturing_skill(),
+                &character.implants,
+            );
+            let time_per_unit =
+                time_per_job as f64 / (blueprint.runs as f64) / (output_quantity as f64);
+            let unit_per_hour = 3600.0 / time_per_unit;
             let profits_per_hour = margin * unit_per_hour;
-            Ok::<f64, ManufactureError>(profits_per_hour)
+            let invention_profits =
+                cost_per_run
+                    .details
+                    .invention_time_per_run
+                    .map(|invention_time_per_run| {
+                        let invention_time_per_unit =
+                            invention_time_per_run / (output_quantity as f64);
+                        (
+                            margin * (3600.0 / invention_time_per_unit),
+                            margin * (3600.0 / (time_per_unit + invention_time_per_unit)),
+                        )
+                    });
+            let history = HistoryItem {
+                id: item.id(),
+                name: item.name(),
+                facility_name: manufacture.name(),
+                profits_per_hour,
+                sell_price: highest_sell_price,
+                material_cost: cost_per_run.details.materials_per_run / (output_quantity as f64),
+                installation_cost: cost_per_run.details.installation_per_run
+                    / (output_quantity as f64),
+                invention_cost: cost_per_run.details.invention_per_run / (output_quantity as f64),
+                cost_index: manufacture.facility().location.indexes.manufacturing,
+                time_per_unit,
+            };
+            Ok::<(HistoryItem, Option<(f64, f64)>), ManufactureError>((history, invention_profits))
         });
     }
     let all_futures = futures.into_iter().collect::<TryJoinAll<_>>();
     let profits = try_join!(all_futures)?.0;
 
-    let mut max_profit = 0.0;
-    for profit in profits {
-        if max_profit == 0.0 || max_profit < profit {
-            max_profit = profit;
+    let mut max_profit: Option<HistoryItem> = None;
+    let mut max_invention_profits = None;
+    for (history, invention_profits) in profits {
+        let is_max = match &max_profit {
+            None => true,
+            Some(max) => max.profits_per_hour < history.profits_per_hour,
+        };
+        if is_max {
+            max_profit = Some(history);
+            max_invention_profits = invention_profits;
         }
     }
+    let missing_skills = character
+        .skills
+        .missing_skills(&blueprint.skills)
+        .iter()
+        .map(MissingSkillStdout::from)
+        .collect();
+
     logging::debug!("Computed manufacturing of item: {}", item.name());
-    Ok(ItemStdout {
-        name: item.name(),
-        profits_per_hour: max_profit,
-        regional_average_volume,
-    })
+    Ok((
+        ItemStdout {
+            name: item.name(),
+            profits_per_hour: max_profit.as_ref().map_or(0.0, |max| max.profits_per_hour),
+            profits_per_science_hour: max_invention_profits.map(|(science, _)| science),
+            profits_per_combined_hour: max_invention_profits.map(|(_, combined)| combined),
+            regional_average_volume,
+            missing_skills,
+        },
+        max_profit,
+    ))
 }
 
 #[derive(Serialize)]
@@ -235,20 +309,75 @@ pub struct ItemStdout {
     name: String,
     regional_average_volume: Option<i64>,
     profits_per_hour: f64,
+    /// Profits per hour of the science slot inventing the blueprint, for invented items.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    profits_per_science_hour: Option<f64>,
+    /// Profits per hour once the invention time is added to the manufacturing time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    profits_per_combined_hour: Option<f64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    missing_skills: Vec<MissingSkillStdout>,
 }
 
 impl Message for ItemStdout {
-    fn standard(&self, _: Verbosity) -> ColoredString {
+    fn standard(&self, verbosity: Verbosity) -> ColoredString {
         let volume_str = match self.regional_average_volume {
             Some(vol) => vol.to_display(),
             None => "".to_string(),
         };
+        let mut invention_str = String::new();
+        if let Some(profits) = self.profits_per_science_hour {
+            invention_str +=
+                format!("{:>120} ISK/h (science slot)\n", profits.to_display()).as_str();
+        }
+        if let Some(profits) = self.profits_per_combined_hour {
+            invention_str +=
+                format!("{:>120} ISK/h (with invention)\n", profits.to_display()).as_str();
+        }
+        let mut missing_skills = String::new();
+        for skill in &self.missing_skills {
+            missing_skills += skill.standard(verbosity).to_string().as_str();
+        }
         ColoredString::from(
             format!(
-                "{:>50}{:>20}{:>50} ISK/h\n",
+                "{:>50}{:>20}{:>50} ISK/h\n{}{}",
                 self.name.bold(),
                 volume_str,
-                self.profits_per_hour.to_display()
+                self.profits_per_hour.to_display(),
+                invention_str,
+                missing_skills
+            )
+            .as_str(),
+        )
+    }
+}
+
+#[derive(Serialize)]
+pub struct MissingSkillStdout {
+    name: String,
+    required_level: i32,
+    trained_level: i32,
+}
+
+impl From<&MissingSkill> for MissingSkillStdout {
+    fn from(skill: &MissingSkill) -> Self {
+        MissingSkillStdout {
+            name: skill.name.clone(),
+            required_level: skill.required_level,
+            trained_level: skill.trained_level,
+        }
+    }
+}
+
+impl Message for MissingSkillStdout {
+    fn standard(&self, _: Verbosity) -> ColoredString {
+        ColoredString::from(
+            format!(
+                "{:>50} {} ({} / {})\n",
+                "Missing skill:".red(),
+                self.name,
+                self.trained_level,
+                self.required_level
             )
             .as_str(),
         )
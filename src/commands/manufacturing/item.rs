@@ -16,10 +16,10 @@ use crate::model::blueprint::{
     MultipleItemsOrdersDetails,
 };
 use crate::model::common::{DetailedCalculation, Identified, Named};
+use crate::model::facility::copying::{CopyingFacility, CopyingResult};
 use crate::model::facility::manufacture::ManufacturingFacility;
 use crate::model::facility::markets::{Market, OrdersRange};
 use crate::model::industry::IndustryType;
-use crate::model::items::TechLevel;
 use crate::{interactive, logging};
 
 pub async fn manufacture(
@@ -29,16 +29,23 @@ pub async fn manufacture(
 ) -> Result<(), EveError> {
     logging::debug!("{:?}", opts_manufacturing);
     let item_name = opts.item.clone();
-    let item_to_manufacture =
-        match interactive::load_item(eve, item_name, opts.strict, None).await? {
-            Some(item_to_manufacture) => item_to_manufacture,
-            None => return Ok(()),
-        };
+    let item_to_manufacture = match interactive::load_item(
+        eve,
+        item_name,
+        opts.strict,
+        IndustryType::Manufacturing,
+        None,
+    )
+    .await?
+    {
+        Some(item_to_manufacture) => item_to_manufacture,
+        None => return Ok(()),
+    };
 
     let (found_blueprints, character, facilities, prices) = try_join4(
         eve.load_item_blueprints(item_to_manufacture.id(), IndustryType::Manufacturing)
             .map_err(|source| ModelError::LoadingBlueprint { source }),
-        eve.load_character()
+        eve.load_character_with_profile(opts_manufacturing.skill_profile.skills.as_deref())
             .map_err(|source| ModelError::LoadingCharacter { source }),
         eve.load_registered_facilities()
             .map_err(|source| ModelError::LoadingFacilities { source }),
@@ -47,24 +54,17 @@ pub async fn manufacture(
     )
     .await?;
 
-    let mut found_blueprints: Vec<BlueprintManufacturing> = found_blueprints
+    let found_blueprints: Vec<BlueprintManufacturing> = found_blueprints
         .iter()
         .filter_map(|b| b.activities.manufacturing.clone())
+        .filter(|b| opts.blueprint.is_none() || opts.blueprint == Some(b.blueprint_id))
         .collect();
-    if found_blueprints.is_empty() {
+    if found_blueprints.is_empty() && opts.blueprint.is_none() {
         return Err(ModelError::BlueprintMissing {
             name: item_to_manufacture.name(),
             type_id: item_to_manufacture.id(),
         })?;
-    } else if found_blueprints.len() > 1 {
-        return Err(ModelError::TooMuchBlueprint {
-            name: item_to_manufacture.name(),
-            type_id: item_to_manufacture.id(),
-        })?;
     }
-    let mut blueprint = found_blueprints.remove(0);
-    blueprint.material_efficiency = opts_manufacturing.material_efficiency;
-    blueprint.time_efficiency = opts_manufacturing.time_efficiency;
 
     let mut manufactures = vec![];
     let mut inventions = vec![];
@@ -81,37 +81,111 @@ pub async fn manufacture(
         }
     }
 
+    let copying_facility = CopyingFacility::select(
+        &facilities,
+        opts_manufacturing.copying.copy_facility.as_deref(),
+    )?;
+
+    let mut paths = vec![];
+    for mut blueprint in found_blueprints {
+        blueprint.material_efficiency = opts_manufacturing.material_efficiency;
+        blueprint.time_efficiency = opts_manufacturing.time_efficiency;
+        blueprint.set_runs(opts_manufacturing.runs);
+
+        let mut invention = None;
+        if item_to_manufacture.tech_level.is_invented() {
+            let mut futures = vec![];
+            for facility in &inventions {
+                futures.push(facility.cheapest_invention(
+                    &blueprint,
+                    opts.invention_source,
+                    copying_facility.as_ref(),
+                    &character.skills,
+                    &character.implants,
+                    &prices,
+                ));
+            }
+            let all_futures = futures.into_iter().collect::<JoinAll<_>>();
+            invention = join!(all_futures)
+                .0
+                .into_iter()
+                .flatten()
+                .reduce(|acc, res| {
+                    if acc.value.cost_per_run() > res.value.cost_per_run() {
+                        res
+                    } else {
+                        acc
+                    }
+                });
+            if invention.is_none() && opts.invention_source.is_some() {
+                continue;
+            }
+        }
+
+        let output_quantity = blueprint
+            .get_product(item_to_manufacture.id())
+            .map(|p| p.quantity)
+            .unwrap_or(1);
+        let (material_efficiency, runs) = match &invention {
+            Some(res) => (
+                res.value.blueprint.material_efficiency,
+                blueprint.runs.min(res.value.blueprint.runs),
+            ),
+            None => (blueprint.material_efficiency, blueprint.runs),
+        };
+        let materials_cost = blueprint
+            .materials
+            .input_materials_cost(None, Some(material_efficiency), runs, &prices)
+            .value
+            / (runs as f64);
+        let invention_cost = invention
+            .as_ref()
+            .map_or(0.0, |res| res.value.cost_per_run());
+        let cost_per_unit = (materials_cost + invention_cost) / (output_quantity as f64);
+        paths.push((blueprint, invention, cost_per_unit));
+    }
+    paths.sort_by(|(_, _, a), (_, _, b)| a.total_cmp(b));
+    if paths.is_empty() {
+        return Err(ModelError::ProductionPathNotFound {
+            name: item_to_manufacture.name(),
+            type_id: item_to_manufacture.id(),
+        })?;
+    }
+
+    let mut paths_stdout = vec![];
+    if paths.len() > 1 {
+        for (index, (blueprint, invention, cost_per_unit)) in paths.iter().enumerate() {
+            paths_stdout.push(PathStdout {
+                blueprint_id: blueprint.blueprint_id,
+                invention_source_id: invention.as_ref().map(|res| res.value.blueprint.source_id),
+                cost_per_unit: *cost_per_unit,
+                selected: index == 0,
+            });
+        }
+    }
+
+    let (mut blueprint, invention, _) = paths.remove(0);
     let mut blueprint_run_price = 0.0;
+    let mut invention_time_per_run = None;
     let mut invention_stdout = None;
-    if TechLevel::Two == item_to_manufacture.tech_level {
-        let mut futures = vec![];
-        for invention in &inventions {
-            futures.push(invention.invent_item(&blueprint, &character.skills, &prices));
-        }
-        let all_futures = futures.into_iter().collect::<JoinAll<_>>();
-        let invention_result = join!(all_futures).0;
-        let cheapest_invention_result = invention_result.iter().reduce(|acc, res| {
-            if acc.value.cost_normalized > res.value.cost_normalized {
-                res
-            } else {
-                acc
-            }
+    if let Some(res) = invention {
+        let total_per_run = res.value.cost_per_run();
+
+        invention_stdout = Some(InventionStdout {
+            base_cost_run: res.details.cost.total,
+            success_chance: res.details.success_probability.final_success_chance,
+            runs: res.value.blueprint.runs,
+            total_run: total_per_run,
+            time_run: res.value.time_per_run(),
+            copying: res.details.copying.as_ref().map(CopyingStdout::from),
         });
-        if let Some(res) = cheapest_invention_result {
-            let total_per_run = res.value.cost_normalized / (res.value.blueprint.runs as f64);
-
-            invention_stdout = Some(InventionStdout {
-                base_cost_run: res.details.cost.total,
-                success_chance: res.details.success_probability.final_success_chance,
-                runs: res.value.blueprint.runs,
-                total_run: total_per_run,
-            });
 
-            blueprint.material_efficiency = res.value.blueprint.material_efficiency;
-            blueprint.time_efficiency = res.value.blueprint.time_efficiency;
+        blueprint.material_efficiency = res.value.blueprint.material_efficiency;
+        blueprint.time_efficiency = res.value.blueprint.time_efficiency;
+        blueprint.runs = blueprint.runs.min(res.value.blueprint.runs);
 
-            blueprint_run_price = total_per_run;
-        }
+        blueprint_run_price = total_per_run;
+        invention_time_per_run = Some(res.value.time_per_run());
     }
 
     let manufacturing_skills = character.skills.get_manufacturing_skill();
@@ -147,22 +221,30 @@ pub async fn manufacture(
         let blueprint = &blueprint;
         let prices = &prices;
         let manufacturing_skills = &manufacturing_skills;
+        let implants = &character.implants;
         let markets_sell_prices = &markets_sell_prices;
         futures.push(async move {
+            let runs = blueprint.runs as f64;
             let input_material_cost = blueprint.materials.input_materials_cost(
                 manufacture.material_consumption_modifier(),
                 Some(blueprint.material_efficiency),
+                blueprint.runs,
                 prices,
             );
             let inputs = InputsStdout::from(&input_material_cost);
             let system = &manufacture.facility().location;
+            let estimated_item_value = estimated_item_value * runs;
             let job_installation_cost = manufacture.job_installation_cost(estimated_item_value);
-            let total_run = input_material_cost.value + job_installation_cost + blueprint_run_price;
+            let total_run =
+                (input_material_cost.value + job_installation_cost) / runs + blueprint_run_price;
             let total_per_unit = total_run / (output_quantity as f64);
-            let time_per_run = manufacture.time_per_run(blueprint, manufacturing_skills);
-            let number_of_run_per_hour = 3600.0 / (time_per_run as f64);
-            let time_per_unit = time_per_run / output_quantity;
-            let unit_per_hour = 3600.0 / (time_per_unit as f64);
+            let time_per_job = manufacture.time_per_job(blueprint, manufacturing_skills, implants);
+            let time_per_run = time_per_job / blueprint.runs;
+            let number_of_run_per_hour = 3600.0 * runs / (time_per_job as f64);
+            let time_per_unit = time_per_job as f64 / runs / (output_quantity as f64);
+            let unit_per_hour = 3600.0 / time_per_unit;
+            let invention_time_per_unit =
+                invention_time_per_run.map(|time| time / (output_quantity as f64));
 
             let mut industry_skill_level = 0;
             if let Some(skill) = &manufacturing_skills.industry {
@@ -191,6 +273,8 @@ pub async fn manufacture(
                         name,
                         profit_per_hour: None,
                         profit_per_day: None,
+                        profit_per_science_hour: None,
+                        profit_per_combined_hour: None,
                     }),
                     Some(p) => {
                         let margin = p - total_per_unit;
@@ -199,6 +283,10 @@ pub async fn manufacture(
                             name,
                             profit_per_hour: Some(profits_per_hour),
                             profit_per_day: Some(profits_per_hour * 24.0),
+                            profit_per_science_hour: invention_time_per_unit
+                                .map(|time| margin * (3600.0 / time)),
+                            profit_per_combined_hour: invention_time_per_unit
+                                .map(|time| margin * (3600.0 / (time_per_unit + time))),
                         })
                     }
                 }
@@ -223,6 +311,16 @@ pub async fn manufacture(
                     job_duration_modifier: manufacture.job_duration_modifier(),
                     industry_skill_level,
                     advanced_industry_skill_level,
+                    implants: implants
+                        .contributing(&IndustryType::Manufacturing)
+                        .into_iter()
+                        .map(|implant| ImplantStdout {
+                            name: implant.name(),
+                            time_bonus: implant
+                                .time_bonus(&IndustryType::Manufacturing)
+                                .unwrap_or(0.0),
+                        })
+                        .collect(),
                     total_time_run: time_per_run,
                     number_of_run_per_hour,
                     number_of_run_per_day: number_of_run_per_hour * 24.0,
@@ -239,6 +337,8 @@ pub async fn manufacture(
 
     logging::stdoutln(ManufactureStdout {
         searched_item_name: item_to_manufacture.name(),
+        runs: blueprint.runs,
+        paths: paths_stdout,
         markets: markets_stdout,
         invention: invention_stdout,
         facilities,
@@ -249,6 +349,10 @@ pub async fn manufacture(
 #[derive(Serialize, Debug)]
 struct ManufactureStdout {
     searched_item_name: String,
+    /// Runs of each job. Input materials and job costs are given for a whole job.
+    runs: i32,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    paths: Vec<PathStdout>,
     markets: Vec<MarketStdout>,
     invention: Option<InventionStdout>,
     facilities: Vec<FacilityStdout>,
@@ -267,19 +371,36 @@ impl Message for ManufactureStdout {
             markets += market.standard(verbosity).to_string().as_str();
         }
 
+        let mut paths = String::new();
+        if !self.paths.is_empty() {
+            paths += "\tProduction paths (input materials and invention, per unit):\n";
+            for path in &self.paths {
+                paths += path.standard(verbosity).to_string().as_str();
+            }
+            paths += "\n";
+        }
+
         let mut invention_stdout = ColoredString::from("");
         if let Some(inv) = &self.invention {
             invention_stdout = inv.standard(verbosity);
         }
 
         let mut facilities = String::from("\tManufacturing facilities:\n");
+        if self.runs > 1 {
+            facilities += format!(
+                "\tJobs of {} runs, input materials and job costs are given per job.\n",
+                self.runs
+            )
+            .as_str();
+        }
         for facility in &self.facilities {
             facilities += facility.standard(verbosity).to_string().as_str();
         }
         ColoredString::from(
             format!(
-                "{}:\n\n{}\n{}\n\n{}",
+                "{}:\n\n{}{}\n{}\n\n{}",
                 self.searched_item_name.bold(),
+                paths,
                 markets,
                 invention_stdout,
                 facilities
@@ -289,6 +410,35 @@ impl Message for ManufactureStdout {
     }
 }
 
+#[derive(Serialize, Debug)]
+struct PathStdout {
+    blueprint_id: i32,
+    invention_source_id: Option<i32>,
+    cost_per_unit: f64,
+    selected: bool,
+}
+
+impl Message for PathStdout {
+    fn standard(&self, _: Verbosity) -> ColoredString {
+        let source = match self.invention_source_id {
+            None => String::new(),
+            Some(source_id) => format!(" (invented from {})", source_id),
+        };
+        let path = format!("Blueprint {}{}", self.blueprint_id, source);
+        let line = format!(
+            "\t\t{} {:<50}{:>30} ISK/u\n",
+            if self.selected { ">" } else { " " },
+            path,
+            self.cost_per_unit.to_display()
+        );
+        if self.selected {
+            ColoredString::from(line.as_str()).bold()
+        } else {
+            ColoredString::from(line.as_str())
+        }
+    }
+}
+
 #[derive(Serialize, Debug)]
 struct MarketStdout {
     name: String,
@@ -368,17 +518,59 @@ struct InventionStdout {
     success_chance: f64,
     runs: i32,
     total_run: f64,
+    /// Science slot time per manufacturing run, in seconds.
+    time_run: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    copying: Option<CopyingStdout>,
 }
 
 impl Message for InventionStdout {
-    fn standard(&self, _: Verbosity) -> ColoredString {
+    fn standard(&self, verbosity: Verbosity) -> ColoredString {
+        let copying = match &self.copying {
+            None => String::new(),
+            Some(copying) => copying.standard(verbosity).to_string(),
+        };
         ColoredString::from(
             format!(
-                "\tInvention:\n\t\t{:>20} ISK * {:>10} %  / {:>10} = {:>30} ISK/run\n",
+                "\tInvention:\n\t\t{:>20} ISK * {:>10} %  / {:>10} = {:>30} ISK/run\n\t\t{:<20}{:>70}/run\n{}",
                 self.base_cost_run.to_display(),
                 (self.success_chance * 100.0).to_display(),
                 self.runs,
-                self.total_run.to_display()
+                self.total_run.to_display(),
+                "Science slot time:",
+                Duration::seconds(self.time_run as i64).to_display(),
+                copying
+            )
+            .as_str(),
+        )
+    }
+}
+
+#[derive(Serialize, Debug)]
+struct CopyingStdout {
+    facility_name: String,
+    cost: f64,
+    time: i32,
+}
+
+impl From<&CopyingResult> for CopyingStdout {
+    fn from(copying: &CopyingResult) -> Self {
+        CopyingStdout {
+            facility_name: copying.facility.clone(),
+            cost: copying.cost,
+            time: copying.time,
+        }
+    }
+}
+
+impl Message for CopyingStdout {
+    fn standard(&self, _: Verbosity) -> ColoredString {
+        ColoredString::from(
+            format!(
+                "\t\tIncluding blueprint copy ({}): {:>20} ISK {:>20}\n",
+                self.facility_name,
+                self.cost.to_display(),
+                Duration::seconds(self.time as i64).to_display()
             )
             .as_str(),
         )
@@ -416,13 +608,15 @@ struct TimeStdout {
     job_duration_modifier: Option<f64>,
     industry_skill_level: i32,
     advanced_industry_skill_level: i32,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    implants: Vec<ImplantStdout>,
     total_time_run: i32,
     number_of_run_per_hour: f64,
     number_of_run_per_day: f64,
 }
 
 impl Message for TimeStdout {
-    fn standard(&self, _: Verbosity) -> ColoredString {
+    fn standard(&self, verbosity: Verbosity) -> ColoredString {
         let base_time = format!(
             "\t\t\tBase time: {:>67}\n",
             Duration::seconds(self.base_time as i64).to_display()
@@ -448,6 +642,10 @@ impl Message for TimeStdout {
             "3 %",
             self.advanced_industry_skill_level * 3
         );
+        let mut implants = String::new();
+        for implant in &self.implants {
+            implants += implant.standard(verbosity).to_string().as_str();
+        }
         let total = format!(
             "\t\t\tTime required per run: {:>55}\n",
             Duration::seconds(self.total_time_run as i64).to_display()
@@ -460,7 +658,26 @@ impl Message for TimeStdout {
             "\t\t\tNumber of runs (Per Days): {:>51}\n",
             self.number_of_run_per_day.to_display()
         );
-        ColoredString::from(format!("\t\tDuration:\n{base_time}{modifier}{industry_lvl}{advanced_industry_level}{total}\n{run_per_hour}{run_per_day}\n").as_str())
+        ColoredString::from(format!("\t\tDuration:\n{base_time}{modifier}{industry_lvl}{advanced_industry_level}{implants}{total}\n{run_per_hour}{run_per_day}\n").as_str())
+    }
+}
+
+#[derive(Serialize, Debug)]
+struct ImplantStdout {
+    name: String,
+    time_bonus: f64,
+}
+
+impl Message for ImplantStdout {
+    fn standard(&self, _: Verbosity) -> ColoredString {
+        ColoredString::from(
+            format!(
+                "\t\t\tImplant {:<55} -{:>16} %\n",
+                self.name,
+                (self.time_bonus * 100.0).to_display()
+            )
+            .as_str(),
+        )
     }
 }
 
@@ -516,6 +733,10 @@ struct MarketProfitStdout {
     name: String,
     profit_per_hour: Option<f64>,
     profit_per_day: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    profit_per_science_hour: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    profit_per_combined_hour: Option<f64>,
 }
 
 impl Message for MarketProfitStdout {
@@ -529,10 +750,20 @@ impl Message for MarketProfitStdout {
             Some(ppd) => ppd.to_display(),
         };
 
+        let mut invention_str = String::new();
+        if let Some(profit) = self.profit_per_science_hour {
+            invention_str +=
+                format!("{:>102} ISK/h (science slot)\n", profit.to_display()).as_str();
+        }
+        if let Some(profit) = self.profit_per_combined_hour {
+            invention_str +=
+                format!("{:>102} ISK/h (with invention)\n", profit.to_display()).as_str();
+        }
+
         ColoredString::from(
             format!(
-                "\t\t\t{:>48}{:>30} ISK/h\n{:>102} ISK/d\n",
-                self.name, pph_str, ppd_str
+                "\t\t\t{:>48}{:>30} ISK/h\n{:>102} ISK/d\n{}",
+                self.name, pph_str, ppd_str, invention_str
             )
             .as_str(),
         )
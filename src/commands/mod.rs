@@ -1,9 +1,18 @@
+pub mod calibrate;
 pub mod facility;
+pub mod history;
 pub mod init;
 pub mod invention;
 pub mod item;
+pub mod jobs;
 pub mod login;
 pub mod logout;
 pub mod manufacturing;
+pub mod optimize;
+pub mod orders;
+pub mod research;
+pub mod schedule;
+pub mod skills;
 pub mod state;
 pub mod update;
+pub mod watch;
@@ -0,0 +1,336 @@
+use std::collections::HashSet;
+
+use chrono::{Duration, Utc};
+use colored::{ColoredString, Colorize};
+use futures_util::future::{try_join4, TryJoinAll};
+use futures_util::TryFutureExt;
+use serde::Serialize;
+use tokio::try_join;
+
+use crate::api::evecache::cache_keys::OrderType;
+use crate::configuration::cli::OptimizeOptions;
+use crate::dates::NaivePeriod;
+use crate::display::Display;
+use crate::errors::{EveError, ModelError};
+use crate::integration::DataIntegrator;
+use crate::logging;
+use crate::logging::{Message, Stdout, Verbosity};
+use crate::model::blueprint::BlueprintManufacturing;
+use crate::model::character::Character;
+use crate::model::common::{Identified, Named};
+use crate::model::facility::copying::CopyingFacility;
+use crate::model::facility::invention::InventionFacility;
+use crate::model::facility::manufacture::Manufacture;
+use crate::model::facility::markets::{Market, RegionOrders};
+use crate::model::industry::{IndustryType, JobSlots, SlotType};
+use crate::model::items::Item;
+use crate::model::markets::sell_order_fees;
+use crate::model::portfolio::{optimize as optimize_portfolio, PortfolioCandidate, PortfolioEntry};
+use crate::model::prices::Prices;
+
+pub async fn optimize(eve: &DataIntegrator, opts: &OptimizeOptions) -> Result<(), EveError> {
+    let (character, facilities, prices, jobs) = try_join4(
+        eve.load_character_with_profile(opts.skill_profile.skills.as_deref())
+            .map_err(|source| ModelError::LoadingCharacter { source }),
+        eve.load_registered_facilities()
+            .map_err(|source| ModelError::LoadingFacilities { source }),
+        eve.load_prices()
+            .map_err(|source| ModelError::LoadingPrices { source }),
+        eve.load_character_industry_jobs()
+            .map_err(|source| ModelError::LoadingIndustryJobs { source }),
+    )
+    .await?;
+
+    let items = eve.load_registered_items().await?;
+    logging::info!("Data retrieved - Compute {} items", items.len());
+
+    let mut manufactures = vec![];
+    let mut invention_facilities = vec![];
+    let mut markets = vec![];
+    for facility in &facilities {
+        if let Some(manufacture) = facility.manufacture() {
+            manufactures.push(manufacture);
+        }
+        if let Some(invention) = facility.invention() {
+            invention_facilities.push(invention);
+        }
+        if let Some(market) = facility.market() {
+            markets.push(market);
+        }
+    }
+
+    let copying_facility =
+        CopyingFacility::select(&facilities, opts.copying.copy_facility.as_deref())?;
+
+    let mut region_ids = HashSet::new();
+    for market in &markets {
+        region_ids.insert(market.as_factility().location.constellation.region.id());
+    }
+
+    let mut futures = vec![];
+    for region_id in region_ids {
+        futures.push(eve.load_market_orders(region_id, OrderType::Sell));
+    }
+    let all_futures = futures.into_iter().collect::<TryJoinAll<_>>();
+    let orders = try_join!(all_futures)?.0;
+
+    logging::info!("Compute items manufacturing");
+    let mut futures = vec![];
+    for item in items {
+        let prices = prices.clone();
+        futures.push(async {
+            let mut found_blueprints: Vec<BlueprintManufacturing> = eve
+                .load_item_blueprints(item.id(), IndustryType::Manufacturing)
+                .await?
+                .into_iter()
+                .filter_map(|b| b.activities.manufacturing)
+                .collect();
+            if found_blueprints.is_empty() {
+                return Err(ModelError::BlueprintMissing {
+                    name: item.name(),
+                    type_id: item.id(),
+                })?;
+            }
+
+            let mut blueprint = found_blueprints.remove(0);
+            blueprint.material_efficiency = opts.material_efficiency;
+            blueprint.time_efficiency = opts.time_efficiency;
+            load_candidate(
+                item,
+                blueprint,
+                Facilities {
+                    manufactures: &manufactures,
+                    invention_facilities: &invention_facilities,
+                    copying_facility: copying_facility.as_ref(),
+                    markets: &markets,
+                },
+                &orders,
+                &character,
+                prices,
+                opts.days,
+            )
+            .await
+        });
+    }
+    let all_futures = futures.into_iter().collect::<TryJoinAll<_>>();
+    let candidates: Vec<PortfolioCandidate> =
+        try_join!(all_futures)?.0.into_iter().flatten().collect();
+
+    let job_slots = match opts.slots {
+        Some(slots) => JobSlots::new(SlotType::Manufacturing, slots, &jobs),
+        None => JobSlots::new(
+            SlotType::Manufacturing,
+            character.skills.max_job_slots(SlotType::Manufacturing),
+            &jobs,
+        ),
+    };
+    let now = Utc::now();
+    let horizon_end = now + Duration::days(opts.days);
+    let slot_time = job_slots
+        .available_from(now)
+        .iter()
+        .map(|available_from| (horizon_end - *available_from).num_seconds().max(0))
+        .sum();
+    let budget = opts.budget.unwrap_or(character.isk);
+    let entries = optimize_portfolio(&candidates, budget, slot_time);
+
+    let capital_used = entries.iter().map(|entry| entry.capital).sum();
+    let expected_profit = entries.iter().map(|entry| entry.profit).sum();
+    logging::stdoutln(OptimizeStdout {
+        days: opts.days,
+        budget,
+        capital_used,
+        expected_profit,
+        return_on_investment: return_on_investment(expected_profit, capital_used),
+        items: entries.iter().map(ItemStdout::from).collect(),
+    })?;
+    Ok(())
+}
+
+struct Facilities<'a> {
+    manufactures: &'a Vec<Manufacture<'a>>,
+    invention_facilities: &'a Vec<InventionFacility<'a>>,
+    copying_facility: Option<&'a CopyingFacility<'a>>,
+    markets: &'a Vec<Market<'a>>,
+}
+
+/// Computes the manufacturing facility returning the most per invested ISK, and how many runs the
+/// item market absorbs over the planning horizon. Items which can't be sold or built with the
+/// character skills are skipped. Profits are net of the sell order fees.
+async fn load_candidate<'a>(
+    item: Item,
+    blueprint: BlueprintManufacturing,
+    facilities: Facilities<'a>,
+    orders: &[RegionOrders],
+    character: &Character,
+    prices: Prices,
+    days: i64,
+) -> Result<Option<PortfolioCandidate>, EveError> {
+    if !character
+        .skills
+        .missing_skills(&blueprint.skills)
+        .is_empty()
+    {
+        logging::debug!("Missing skills to manufacture item: {}", item.name());
+        return Ok(None);
+    }
+    let output_quantity = blueprint
+        .get_product(item.id())
+        .map(|product| product.quantity)
+        .unwrap_or(1);
+
+    let mut highest_sell_price = None;
+    for market in facilities.markets {
+        let orders = orders.iter().find(|&order| {
+            order.region.id() == market.as_factility().location.constellation.region.id()
+        });
+        let price = orders
+            .map(|orders| orders.lowest_station_sell_price(item.id(), market.as_factility().id()));
+        // A zero price means the station doesn't sell the item
+        if let Some(price) = price.filter(|price| *price > 0.0) {
+            highest_sell_price = match highest_sell_price {
+                Some((highest, highest_market)) if highest >= price => {
+                    Some((highest, highest_market))
+                }
+                _ => Some((price, market)),
+            };
+        }
+    }
+    let (sell_price, market) = match highest_sell_price {
+        Some(highest) => highest,
+        None => {
+            logging::debug!("No market sells item: {}", item.name());
+            return Ok(None);
+        }
+    };
+    let regional_average_volume = market
+        .regional_average_volume(item.id(), NaivePeriod::past(Duration::days(30)))
+        .await?;
+
+    let fees = sell_order_fees(&character.skills);
+    let mut best: Option<PortfolioCandidate> = None;
+    for manufacture in facilities.manufactures {
+        let cost_per_run = manufacture
+            .manufacture_cost_per_run(
+                &blueprint,
+                &character.skills,
+                &character.implants,
+                facilities.invention_facilities,
+                facilities.copying_facility,
+                &prices,
+            )
+            .await?
+            .value;
+        let candidate = PortfolioCandidate {
+            name: item.name(),
+            cost_per_run,
+            profit_per_run: sell_price * (1.0 - fees) * (output_quantity as f64) - cost_per_run,
+            time_per_run: manufacture.time_per_run(
+                &blueprint,
+                &character.skills.get_manufacturing_skill(),
+                &character.implants,
+            ) as i64,
+            max_runs: regional_average_volume * days / (output_quantity as i64),
+        };
+        best = match best {
+            Some(best) if best.return_on_investment() >= candidate.return_on_investment() => {
+                Some(best)
+            }
+            _ => Some(candidate),
+        };
+    }
+
+    logging::debug!("Computed manufacturing of item: {}", item.name());
+    Ok(best)
+}
+
+/// Profits per invested ISK, 0 when nothing is invested.
+fn return_on_investment(profit: f64, capital: f64) -> f64 {
+    if capital > 0.0 {
+        profit / capital
+    } else {
+        0.0
+    }
+}
+
+#[derive(Serialize)]
+pub struct OptimizeStdout {
+    days: i64,
+    budget: f64,
+    capital_used: f64,
+    expected_profit: f64,
+    return_on_investment: f64,
+    items: Vec<ItemStdout>,
+}
+
+impl Stdout for OptimizeStdout {}
+
+impl Message for OptimizeStdout {
+    fn standard(&self, verbosity: Verbosity) -> ColoredString {
+        let title = format!("Manufacturing portfolio ({} day(s)):", self.days);
+        let mut items_str = format!(
+            "{:>50}{:>10}{:>25}{:>25}{:>12}\n",
+            "", "Runs", "Capital (ISK)", "Profit (ISK)", "ROI"
+        );
+        if self.items.is_empty() {
+            items_str += "\tNothing profitable fits in the budget\n";
+        }
+        for item in &self.items {
+            items_str += item.standard(verbosity).to_string().as_str();
+        }
+        let capital_used_str = format!("{} ISK", self.capital_used.to_display());
+        let expected_profit_str = format!("{} ISK", self.expected_profit.to_display().underline());
+        ColoredString::from(
+            format!(
+                "{}\n\n{}\n\tBudget: {:>80} ISK\n\tCapital used: {:>78}\n\tExpected profit: {:>75}\n\tReturn on investment: {:>68} %\n",
+                title.bold(),
+                items_str,
+                self.budget.to_display(),
+                capital_used_str,
+                expected_profit_str,
+                (self.return_on_investment * 100.0).to_display()
+            )
+            .as_str(),
+        )
+    }
+}
+
+#[derive(Serialize)]
+pub struct ItemStdout {
+    name: String,
+    runs: i64,
+    capital: f64,
+    expected_profit: f64,
+    return_on_investment: f64,
+    /// Manufacturing slot time used by the runs, in seconds.
+    time: i64,
+}
+
+impl From<&PortfolioEntry> for ItemStdout {
+    fn from(entry: &PortfolioEntry) -> Self {
+        Self {
+            name: entry.name.clone(),
+            runs: entry.runs,
+            capital: entry.capital,
+            expected_profit: entry.profit,
+            return_on_investment: entry.return_on_investment(),
+            time: entry.time,
+        }
+    }
+}
+
+impl Message for ItemStdout {
+    fn standard(&self, _: Verbosity) -> ColoredString {
+        ColoredString::from(
+            format!(
+                "{:>50}{:>10}{:>25}{:>25}{:>10} %\n",
+                self.name.bold(),
+                self.runs.to_display(),
+                self.capital.to_display(),
+                self.expected_profit.to_display(),
+                (self.return_on_investment * 100.0).to_display()
+            )
+            .as_str(),
+        )
+    }
+}
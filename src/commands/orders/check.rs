@@ -0,0 +1,191 @@
+use std::collections::HashSet;
+
+use colored::{ColoredString, Colorize};
+use futures_util::future::{try_join3, TryJoinAll};
+use futures_util::TryFutureExt;
+use serde::Serialize;
+use tokio::try_join;
+
+use crate::api::evecache::cache_keys::OrderType;
+use crate::display::Display;
+use crate::errors::{EveError, ModelError};
+use crate::integration::DataIntegrator;
+use crate::logging;
+use crate::logging::{Message, Stdout, Verbosity};
+use crate::model::character::Skills;
+use crate::model::common::{Identified, Named};
+use crate::model::facility::markets::RegionOrders;
+use crate::model::facility::Facility;
+use crate::model::markets::{outbid_price, relist_fee, CharacterOrder};
+
+pub async fn check(eve: &DataIntegrator) -> Result<(), EveError> {
+    let (character, orders, facilities) = try_join3(
+        eve.load_character()
+            .map_err(|source| ModelError::LoadingCharacter { source }),
+        eve.load_character_orders()
+            .map_err(|source| ModelError::LoadingCharacterOrders { source }),
+        eve.load_registered_facilities()
+            .map_err(|source| ModelError::LoadingFacilities { source }),
+    )
+    .await?;
+
+    let mut books = HashSet::new();
+    for order in &orders {
+        books.insert((order.region_id, order.order_type.clone()));
+    }
+    let mut futures = vec![];
+    for (region_id, order_type) in books {
+        futures.push(
+            eve.load_market_orders(region_id, order_type.clone())
+                .map_ok(move |region_orders| (order_type, region_orders)),
+        );
+    }
+    let all_futures = futures.into_iter().collect::<TryJoinAll<_>>();
+    let books = try_join!(all_futures)?.0;
+    logging::info!("Data retrieved - Check {} orders", orders.len());
+
+    let own_order_ids: Vec<i64> = orders.iter().map(|order| order.id).collect();
+    let mut orders_stdout: Vec<OrderStdout> = orders
+        .iter()
+        .map(|order| {
+            let book = books
+                .iter()
+                .find(|(order_type, region_orders)| {
+                    *order_type == order.order_type && region_orders.region.id() == order.region_id
+                })
+                .map(|(_, region_orders)| region_orders);
+            OrderStdout::from(order, book, &own_order_ids, &facilities, &character.skills)
+        })
+        .collect();
+    orders_stdout.sort_by_key(|order| std::cmp::Reverse(order.undercut));
+    logging::stdoutln(OrdersCheckStdout {
+        orders: orders_stdout,
+    })?;
+    Ok(())
+}
+
+#[derive(Serialize)]
+pub struct OrdersCheckStdout {
+    orders: Vec<OrderStdout>,
+}
+
+impl Stdout for OrdersCheckStdout {}
+
+impl Message for OrdersCheckStdout {
+    fn standard(&self, verbosity: Verbosity) -> ColoredString {
+        let mut orders_str = String::new();
+        if self.orders.is_empty() {
+            orders_str += "\tNo market order\n";
+        }
+        for order in &self.orders {
+            orders_str += order.standard(verbosity).to_string().as_str();
+        }
+        ColoredString::from(format!("{}\n{}", "Market orders:".bold(), orders_str).as_str())
+    }
+}
+
+#[derive(Serialize)]
+pub struct OrderStdout {
+    item_name: String,
+    order_type: OrderType,
+    location_name: String,
+    price: f64,
+    volume_remain: i32,
+    /// Position of the order in the queue of its location, starting at 1.
+    position: usize,
+    undercut: bool,
+    /// Best price of the other characters orders at the location.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    competitor_price: Option<f64>,
+    /// Undercut orders only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    suggested_price: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    relist_fee: Option<f64>,
+}
+
+impl OrderStdout {
+    /// Compares the order to the orders of the same item and kind at its location. Orders missing
+    /// from the loaded order books, such as orders in private structures, are compared to no order.
+    fn from(
+        order: &CharacterOrder,
+        book: Option<&RegionOrders>,
+        own_order_ids: &[i64],
+        facilities: &[Facility],
+        skills: &Skills,
+    ) -> Self {
+        let book = book
+            .map(|book| {
+                book.station_orders(order.item.id(), order.location_id, order.order_type.clone())
+            })
+            .unwrap_or_default();
+        let position = 1 + book
+            .iter()
+            .filter(|other| other.order_id != order.id && order.is_beaten_by(other.price))
+            .count();
+        let competitor_price = book
+            .iter()
+            .find(|other| !own_order_ids.contains(&other.order_id))
+            .map(|other| other.price);
+        let undercut = competitor_price.is_some_and(|price| order.is_beaten_by(price));
+        let suggested_price = competitor_price
+            .filter(|_| undercut)
+            .map(|price| outbid_price(price, order.order_type.clone()));
+        let location_name = facilities
+            .iter()
+            .find(|facility| facility.id() == order.location_id)
+            .map(|facility| facility.name())
+            .unwrap_or_else(|| format!("Location {}", order.location_id));
+
+        Self {
+            item_name: order.item.name(),
+            order_type: order.order_type.clone(),
+            location_name,
+            price: order.price,
+            volume_remain: order.volume_remain,
+            position,
+            undercut,
+            competitor_price,
+            suggested_price,
+            relist_fee: suggested_price.map(|price| relist_fee(order, price, skills)),
+        }
+    }
+}
+
+impl Message for OrderStdout {
+    fn standard(&self, _: Verbosity) -> ColoredString {
+        let status = if self.undercut {
+            match self.order_type {
+                OrderType::Sell => "Undercut".red(),
+                OrderType::Buy => "Outbid".red(),
+            }
+        } else {
+            "OK".green()
+        };
+        let price_str = format!("{} ISK", self.price.to_display());
+        let mut order_str = format!(
+            "\t{:>40}{:>6}{:>25}{:>10}   #{:<5}{}\n",
+            self.item_name.bold(),
+            self.order_type.to_string(),
+            price_str,
+            self.volume_remain.to_display(),
+            self.position,
+            status
+        );
+        order_str += format!("\t\t{:>39}{:>25}\n", "Location:", self.location_name).as_str();
+        if let Some(competitor_price) = self.competitor_price {
+            let competitor_str = format!("{} ISK", competitor_price.to_display());
+            order_str += format!("\t\t{:>39}{:>25}\n", "Best competitor:", competitor_str).as_str();
+        }
+        if let (Some(suggested_price), Some(relist_fee)) = (self.suggested_price, self.relist_fee) {
+            let suggested_str = format!("{} ISK", suggested_price.to_display());
+            let fee_str = format!("{} ISK", relist_fee.to_display());
+            order_str += format!(
+                "\t\t{:>39}{:>25} (relist fee: {})\n",
+                "Suggested price:", suggested_str, fee_str
+            )
+            .as_str();
+        }
+        ColoredString::from(order_str.as_str())
+    }
+}
@@ -0,0 +1,15 @@
+use crate::commands::orders::check::check;
+use crate::configuration::cli::{OrdersCommands, OrdersOptions};
+use crate::errors::EveError;
+use crate::integration::DataIntegrator;
+
+mod check;
+
+pub async fn orders(eve: &DataIntegrator, opts: &OrdersOptions) -> Result<(), EveError> {
+    match &opts.command {
+        OrdersCommands::Check => {
+            check(eve).await?;
+        }
+    }
+    Ok(())
+}
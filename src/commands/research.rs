@@ -0,0 +1,363 @@
+use chrono::Duration;
+use colored::{ColoredString, Colorize};
+use futures_util::future::try_join4;
+use futures_util::TryFutureExt;
+use serde::Serialize;
+
+use crate::configuration::cli::ResearchOptions;
+use crate::display::Display;
+use crate::errors::{EveError, ModelError};
+use crate::integration::DataIntegrator;
+use crate::logging::{Message, Stdout, Verbosity};
+use crate::model::blueprint::{BlueprintManufacturing, BlueprintResearch};
+use crate::model::character::Character;
+use crate::model::common::{Identified, Named};
+use crate::model::facility::research::{material_savings_per_run, payback_runs};
+use crate::model::facility::Facility;
+use crate::model::industry::IndustryType;
+use crate::model::prices::Prices;
+use crate::{interactive, logging};
+
+pub async fn research(eve: &DataIntegrator, opts: &ResearchOptions) -> Result<(), EveError> {
+    if opts.target_material_efficiency < opts.material_efficiency {
+        return Err(ModelError::InvalidResearchTarget {
+            current: opts.material_efficiency,
+            target: opts.target_material_efficiency,
+        })?;
+    }
+    if opts.target_time_efficiency < opts.time_efficiency {
+        return Err(ModelError::InvalidResearchTarget {
+            current: opts.time_efficiency,
+            target: opts.target_time_efficiency,
+        })?;
+    }
+
+    let item = match interactive::load_item(
+        eve,
+        opts.item.clone(),
+        opts.strict,
+        IndustryType::Manufacturing,
+        None,
+    )
+    .await?
+    {
+        Some(item) => item,
+        None => return Ok(()),
+    };
+
+    let (blueprints, character, facilities, prices) = try_join4(
+        eve.load_item_blueprints(item.id(), IndustryType::Manufacturing)
+            .map_err(|source| ModelError::LoadingBlueprint { source }),
+        eve.load_character()
+            .map_err(|source| ModelError::LoadingCharacter { source }),
+        eve.load_registered_facilities()
+            .map_err(|source| ModelError::LoadingFacilities { source }),
+        eve.load_prices()
+            .map_err(|source| ModelError::LoadingPrices { source }),
+    )
+    .await?;
+
+    let blueprint = blueprints.into_iter().find(|blueprint| {
+        blueprint.activities.manufacturing.is_some()
+            && (blueprint.activities.research_material.is_some()
+                || blueprint.activities.research_time.is_some())
+    });
+    let blueprint = match blueprint {
+        Some(blueprint) => blueprint,
+        None => {
+            return Err(ModelError::BlueprintNotResearchable {
+                name: item.name(),
+                type_id: item.id(),
+            })?
+        }
+    };
+    let manufacturing = blueprint
+        .activities
+        .manufacturing
+        .as_ref()
+        .expect("Filtered on manufacturing blueprints");
+
+    let savings_per_run = material_savings_per_run(
+        manufacturing,
+        opts.runs.unwrap_or(manufacturing.max_production_limit),
+        opts.material_efficiency,
+        opts.target_material_efficiency,
+        &prices,
+    );
+    let time_saved_per_run = (manufacturing.time as i64)
+        * ((opts.target_time_efficiency - opts.time_efficiency) as i64)
+        / 100;
+
+    let researches = [
+        (
+            IndustryType::ResearchMaterialEfficiency,
+            blueprint.activities.research_material.as_ref(),
+            opts.material_efficiency,
+            opts.target_material_efficiency,
+        ),
+        // Each time efficiency level improves the blueprint by 2%.
+        (
+            IndustryType::ResearchTimeEfficiency,
+            blueprint.activities.research_time.as_ref(),
+            opts.time_efficiency / 2,
+            opts.target_time_efficiency / 2,
+        ),
+    ];
+
+    let mut facilities_stdout = vec![];
+    for facility in &facilities {
+        let mut jobs = vec![];
+        let mut material_research_cost = 0.0;
+        for (research, blueprint_research, from_level, to_level) in &researches {
+            if from_level >= to_level {
+                continue;
+            }
+            if let Some(job) = research_job(
+                facility,
+                *research,
+                manufacturing,
+                *blueprint_research,
+                (*from_level, *to_level),
+                &character,
+                &prices,
+            ) {
+                if *research == IndustryType::ResearchMaterialEfficiency {
+                    material_research_cost += job.cost;
+                }
+                jobs.push(job);
+            }
+        }
+        if jobs.is_empty() {
+            continue;
+        }
+
+        let total_cost: f64 = jobs.iter().map(|job| job.cost).sum();
+        let total_time: i64 = jobs.iter().map(|job| job.time).sum();
+        facilities_stdout.push(FacilityStdout {
+            facility_name: facility.name(),
+            jobs,
+            total_cost,
+            total_time,
+            // Time savings have no ISK value, only the material research pays for itself.
+            payback_runs: payback_runs(material_research_cost, savings_per_run),
+        });
+    }
+    facilities_stdout.sort_by(|a, b| a.total_cost.total_cmp(&b.total_cost));
+
+    logging::stdoutln(ResearchStdout {
+        item_name: item.name(),
+        blueprint_id: blueprint.id(),
+        material_savings_per_run: savings_per_run,
+        time_saved_per_run,
+        facilities: facilities_stdout,
+    })?;
+    Ok(())
+}
+
+fn research_job(
+    facility: &Facility,
+    research: IndustryType,
+    blueprint: &BlueprintManufacturing,
+    blueprint_research: Option<&BlueprintResearch>,
+    (from_level, to_level): (u8, u8),
+    character: &Character,
+    prices: &Prices,
+) -> Option<JobStdout> {
+    let research_facility = facility.research(research)?;
+    let blueprint_research = blueprint_research?;
+    let result = research_facility.research(
+        blueprint,
+        blueprint_research,
+        (from_level, to_level),
+        &character.skills,
+        &character.implants,
+        prices,
+    );
+    let name = match research {
+        IndustryType::ResearchTimeEfficiency => "Time efficiency",
+        _ => "Material efficiency",
+    };
+    let (from, to) = match research {
+        IndustryType::ResearchTimeEfficiency => (from_level * 2, to_level * 2),
+        _ => (from_level, to_level),
+    };
+    Some(JobStdout {
+        name: name.to_string(),
+        from,
+        to,
+        estimated_item_value: result.details.job_cost.estimated_item_value,
+        system_cost_index: research_facility.system_cost_index(),
+        job_cost_modifier: result.details.job_cost.job_cost_modifier,
+        facility_tax: result.details.job_cost.facility_tax,
+        base_time: result.details.time.base_time,
+        job_duration_modifier: result.details.time.job_duration_modifier,
+        research_skill_level: result.details.time.research_skill_level,
+        advanced_industry_skill_level: result.details.time.advanced_industry_skill_level,
+        implants: result.details.time.implants,
+        cost: result.value.cost,
+        time: result.value.time,
+    })
+}
+
+#[derive(Serialize, Debug)]
+struct ResearchStdout {
+    item_name: String,
+    blueprint_id: i32,
+    material_savings_per_run: f64,
+    time_saved_per_run: i64,
+    facilities: Vec<FacilityStdout>,
+}
+
+impl Stdout for ResearchStdout {}
+
+impl Message for ResearchStdout {
+    fn standard(&self, verbosity: Verbosity) -> ColoredString {
+        let savings = format!(
+            "\tMaterials saved per run: {:>62} ISK\n\tTime saved per run: {:>67}\n",
+            self.material_savings_per_run.to_display(),
+            Duration::seconds(self.time_saved_per_run).to_display()
+        );
+
+        let mut facilities = String::from("\tResearch facilities:\n");
+        if self.facilities.is_empty() {
+            facilities += "\t\tNo registered facility supports the requested research\n";
+        }
+        for facility in &self.facilities {
+            facilities += facility.standard(verbosity).to_string().as_str();
+        }
+        ColoredString::from(
+            format!(
+                "{} (Blueprint {}):\n\n{}\n{}",
+                self.item_name.bold(),
+                self.blueprint_id,
+                savings,
+                facilities
+            )
+            .as_str(),
+        )
+    }
+}
+
+#[derive(Serialize, Debug)]
+struct FacilityStdout {
+    facility_name: String,
+    jobs: Vec<JobStdout>,
+    total_cost: f64,
+    total_time: i64,
+    payback_runs: Option<i64>,
+}
+
+impl Message for FacilityStdout {
+    fn standard(&self, verbosity: Verbosity) -> ColoredString {
+        let mut jobs = String::new();
+        for job in &self.jobs {
+            jobs += job.standard(verbosity).to_string().as_str();
+        }
+
+        let total_cost_str = format!("{} ISK", self.total_cost.to_display().underline());
+        let total_cost = format!("\t\tTotal cost: {:>78}\n", total_cost_str);
+        let total_time = format!(
+            "\t\tTotal time: {:>66}\n",
+            Duration::seconds(self.total_time).to_display()
+        );
+        let payback = match self.payback_runs {
+            None => "N/A".to_string(),
+            Some(runs) => format!("{} runs", runs.to_display()),
+        };
+        let payback = format!("\t\tME payback: {:>66}\n", payback).bold();
+
+        ColoredString::from(
+            format!(
+                "\t> {}:\n{}{}{}{}\n",
+                self.facility_name.bold(),
+                jobs,
+                total_cost,
+                total_time,
+                payback
+            )
+            .as_str(),
+        )
+    }
+}
+
+#[derive(Serialize, Debug)]
+struct JobStdout {
+    name: String,
+    from: u8,
+    to: u8,
+    estimated_item_value: f64,
+    system_cost_index: f64,
+    job_cost_modifier: Option<f64>,
+    facility_tax: f64,
+    base_time: i64,
+    job_duration_modifier: Option<f64>,
+    research_skill_level: i32,
+    advanced_industry_skill_level: i32,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    implants: Vec<String>,
+    cost: f64,
+    time: i64,
+}
+
+impl Message for JobStdout {
+    fn standard(&self, _: Verbosity) -> ColoredString {
+        let title = format!("\t\t{} ({} -> {}):\n", self.name, self.from, self.to);
+        let estimated_item_value = format!(
+            "\t\t\tEstimated item value: {:>48} ISK\n",
+            self.estimated_item_value.to_display()
+        );
+        let system_cost_index = format!(
+            "\t\t\tSystem cost index: {:>51} %\n",
+            (self.system_cost_index * 100.0).to_display()
+        );
+        let job_cost_modifier = match self.job_cost_modifier {
+            None => String::new(),
+            Some(modifier) => format!(
+                "\t\t\tJob cost modifier: {:>51} %\n",
+                (modifier * 100.0).to_display()
+            ),
+        };
+        let facility_tax = format!(
+            "\t\t\tFacility Tax: {:>56} %\n",
+            (self.facility_tax * 100.0).to_display()
+        );
+        let cost_str = format!("{} ISK", self.cost.to_display().underline());
+        let cost = format!("\t\t\tJob cost: {:>72}\n", cost_str);
+
+        let base_time = format!(
+            "\t\t\tBase time: {:>67}\n",
+            Duration::seconds(self.base_time).to_display()
+        );
+        let job_duration_modifier = match self.job_duration_modifier {
+            None => String::new(),
+            Some(modifier) => format!(
+                "\t\t\tDuration modifier: {:>59} %\n",
+                (modifier * 100.0).to_display()
+            ),
+        };
+        let skills = format!(
+            "\t\t\tResearch skill: {:>30} * {:>10} = {:>16} %\n\t\t\tAdvanced Industry skill: {:>21} * {:>10} = {:>16} %\n",
+            self.research_skill_level,
+            "5 %",
+            self.research_skill_level * 5,
+            self.advanced_industry_skill_level,
+            "3 %",
+            self.advanced_industry_skill_level * 3
+        );
+        let mut implants = String::new();
+        for implant in &self.implants {
+            implants += format!("\t\t\tImplant: {:>69}\n", implant).as_str();
+        }
+        let time = format!(
+            "\t\t\tTime required: {:>63}\n",
+            Duration::seconds(self.time).to_display()
+        );
+
+        ColoredString::from(
+            format!(
+                "{title}{estimated_item_value}{system_cost_index}{job_cost_modifier}{facility_tax}{cost}{base_time}{job_duration_modifier}{skills}{implants}{time}\n"
+            )
+            .as_str(),
+        )
+    }
+}
@@ -0,0 +1,356 @@
+use std::collections::HashSet;
+
+use chrono::{Duration, Utc};
+use colored::{ColoredString, Colorize};
+use futures_util::future::{try_join4, TryJoinAll};
+use futures_util::TryFutureExt;
+use serde::Serialize;
+use tokio::try_join;
+
+use crate::api::evecache::cache_keys::OrderType;
+use crate::configuration::cli::ScheduleOptions;
+use crate::dates::NaivePeriod;
+use crate::display::Display;
+use crate::errors::{EveError, ModelError};
+use crate::integration::DataIntegrator;
+use crate::logging;
+use crate::logging::{Message, Stdout, Verbosity};
+use crate::model::blueprint::BlueprintManufacturing;
+use crate::model::character::Character;
+use crate::model::common::{Identified, Named};
+use crate::model::facility::copying::CopyingFacility;
+use crate::model::facility::invention::InventionFacility;
+use crate::model::facility::manufacture::Manufacture;
+use crate::model::facility::markets::{Market, RegionOrders};
+use crate::model::industry::{IndustryType, JobSlots, SlotType};
+use crate::model::items::Item;
+use crate::model::prices::Prices;
+use crate::model::schedule::{schedule as schedule_jobs, ScheduleCandidate, SlotSchedule};
+
+pub async fn schedule(eve: &DataIntegrator, opts: &ScheduleOptions) -> Result<(), EveError> {
+    let (character, facilities, prices, jobs) = try_join4(
+        eve.load_character_with_profile(opts.skill_profile.skills.as_deref())
+            .map_err(|source| ModelError::LoadingCharacter { source }),
+        eve.load_registered_facilities()
+            .map_err(|source| ModelError::LoadingFacilities { source }),
+        eve.load_prices()
+            .map_err(|source| ModelError::LoadingPrices { source }),
+        eve.load_character_industry_jobs()
+            .map_err(|source| ModelError::LoadingIndustryJobs { source }),
+    )
+    .await?;
+
+    let items = eve.load_registered_items().await?;
+    logging::info!("Data retrieved - Compute {} items", items.len());
+
+    let mut manufactures = vec![];
+    let mut invention_facilities = vec![];
+    let mut markets = vec![];
+    for facility in &facilities {
+        if let Some(manufacture) = facility.manufacture() {
+            manufactures.push(manufacture);
+        }
+        if let Some(invention) = facility.invention() {
+            invention_facilities.push(invention);
+        }
+        if let Some(market) = facility.market() {
+            markets.push(market);
+        }
+    }
+
+    let copying_facility =
+        CopyingFacility::select(&facilities, opts.copying.copy_facility.as_deref())?;
+
+    let mut region_ids = HashSet::new();
+    for market in &markets {
+        region_ids.insert(market.as_factility().location.constellation.region.id());
+    }
+
+    let mut futures = vec![];
+    for region_id in region_ids {
+        futures.push(eve.load_market_orders(region_id, OrderType::Sell));
+    }
+    let all_futures = futures.into_iter().collect::<TryJoinAll<_>>();
+    let orders = try_join!(all_futures)?.0;
+
+    logging::info!("Compute items manufacturing");
+    let mut futures = vec![];
+    for item in items {
+        let prices = prices.clone();
+        futures.push(async {
+            let mut found_blueprints: Vec<BlueprintManufacturing> = eve
+                .load_item_blueprints(item.id(), IndustryType::Manufacturing)
+                .await?
+                .into_iter()
+                .filter_map(|b| b.activities.manufacturing)
+                .collect();
+            if found_blueprints.is_empty() {
+                return Err(ModelError::BlueprintMissing {
+                    name: item.name(),
+                    type_id: item.id(),
+                })?;
+            }
+
+            let mut blueprint = found_blueprints.remove(0);
+            blueprint.material_efficiency = opts.material_efficiency;
+            blueprint.time_efficiency = opts.time_efficiency;
+            load_candidate(
+                item,
+                blueprint,
+                Facilities {
+                    manufactures: &manufactures,
+                    invention_facilities: &invention_facilities,
+                    copying_facility: copying_facility.as_ref(),
+                    markets: &markets,
+                },
+                &orders,
+                &character,
+                prices,
+                opts.days,
+            )
+            .await
+        });
+    }
+    let all_futures = futures.into_iter().collect::<TryJoinAll<_>>();
+    let candidates: Vec<ScheduleCandidate> =
+        try_join!(all_futures)?.0.into_iter().flatten().collect();
+
+    let job_slots = match opts.slots {
+        Some(slots) => JobSlots::new(SlotType::Manufacturing, slots, &jobs),
+        None => JobSlots::new(
+            SlotType::Manufacturing,
+            character.skills.max_job_slots(SlotType::Manufacturing),
+            &jobs,
+        ),
+    };
+    let now = Utc::now();
+    let slots = schedule_jobs(
+        &candidates,
+        &job_slots.available_from(now),
+        now + Duration::days(opts.days),
+    );
+
+    let slots_stdout: Vec<SlotStdout> = slots
+        .iter()
+        .enumerate()
+        .map(|(index, slot)| SlotStdout::from(index + 1, slot, now))
+        .collect();
+    logging::stdoutln(ScheduleStdout {
+        days: opts.days,
+        total_profit: slots_stdout.iter().map(|slot| slot.profit).sum(),
+        slots: slots_stdout,
+    })?;
+    Ok(())
+}
+
+struct Facilities<'a> {
+    manufactures: &'a Vec<Manufacture<'a>>,
+    invention_facilities: &'a Vec<InventionFacility<'a>>,
+    copying_facility: Option<&'a CopyingFacility<'a>>,
+    markets: &'a Vec<Market<'a>>,
+}
+
+/// Computes the most profitable way to manufacture the item in the registered facilities, and how
+/// many runs its market absorbs over the planning horizon. Items which can't be sold or built with
+/// the character skills are skipped.
+async fn load_candidate<'a>(
+    item: Item,
+    blueprint: BlueprintManufacturing,
+    facilities: Facilities<'a>,
+    orders: &[RegionOrders],
+    character: &Character,
+    prices: Prices,
+    days: i64,
+) -> Result<Option<ScheduleCandidate>, EveError> {
+    if !character
+        .skills
+        .missing_skills(&blueprint.skills)
+        .is_empty()
+    {
+        logging::debug!("Missing skills to manufacture item: {}", item.name());
+        return Ok(None);
+    }
+    let output_quantity = blueprint
+        .get_product(item.id())
+        .map(|product| product.quantity)
+        .unwrap_or(1);
+
+    let mut highest_sell_price = None;
+    for market in facilities.markets {
+        let orders = orders.iter().find(|&order| {
+            order.region.id() == market.as_factility().location.constellation.region.id()
+        });
+        let price = orders
+            .map(|orders| orders.lowest_station_sell_price(item.id(), market.as_factility().id()));
+        // A zero price means the station doesn't sell the item
+        if let Some(price) = price.filter(|price| *price > 0.0) {
+            highest_sell_price = match highest_sell_price {
+                Some((highest, highest_market)) if highest >= price => {
+                    Some((highest, highest_market))
+                }
+                _ => Some((price, market)),
+            };
+        }
+    }
+    let (sell_price, market) = match highest_sell_price {
+        Some(highest) => highest,
+        None => {
+            logging::debug!("No market sells item: {}", item.name());
+            return Ok(None);
+        }
+    };
+    let regional_average_volume = market
+        .regional_average_volume(item.id(), NaivePeriod::past(Duration::days(30)))
+        .await?;
+
+    let mut best: Option<(f64, i64)> = None;
+    for manufacture in facilities.manufactures {
+        let cost_per_run = manufacture
+            .manufacture_cost_per_run(
+                &blueprint,
+                &character.skills,
+                &character.implants,
+                facilities.invention_facilities,
+                facilities.copying_facility,
+                &prices,
+            )
+            .await?
+            .value;
+        let profit_per_run = sell_price * (output_quantity as f64) - cost_per_run;
+        let time_per_run = manufacture.time_per_run(
+            &blueprint,
+            &character.skills.get_manufacturing_skill(),
+            &character.implants,
+        ) as i64;
+        if time_per_run <= 0 {
+            continue;
+        }
+        best = match best {
+            Some((profit, time))
+                if profit / (time as f64) >= profit_per_run / (time_per_run as f64) =>
+            {
+                Some((profit, time))
+            }
+            _ => Some((profit_per_run, time_per_run)),
+        };
+    }
+
+    logging::debug!("Computed manufacturing of item: {}", item.name());
+    Ok(
+        best.map(|(profit_per_run, time_per_run)| ScheduleCandidate {
+            name: item.name(),
+            profit_per_run,
+            time_per_run,
+            max_runs: regional_average_volume * days / (output_quantity as i64),
+        }),
+    )
+}
+
+#[derive(Serialize)]
+pub struct ScheduleStdout {
+    days: i64,
+    total_profit: f64,
+    slots: Vec<SlotStdout>,
+}
+
+impl Stdout for ScheduleStdout {}
+
+impl Message for ScheduleStdout {
+    fn standard(&self, verbosity: Verbosity) -> ColoredString {
+        let mut slots_str = String::new();
+        for slot in &self.slots {
+            slots_str += slot.standard(verbosity).to_string().as_str();
+        }
+        let title = format!("Manufacturing schedule ({} day(s)):", self.days);
+        let total_str = format!("{} ISK", self.total_profit.to_display().underline());
+        ColoredString::from(
+            format!(
+                "{}\n\n{}\tTotal profit: {:>76}\n",
+                title.bold(),
+                slots_str,
+                total_str
+            )
+            .as_str(),
+        )
+    }
+}
+
+#[derive(Serialize)]
+pub struct SlotStdout {
+    slot: usize,
+    /// Time left before the running job frees the slot, in seconds.
+    free_in: i64,
+    profit: f64,
+    jobs: Vec<JobStdout>,
+}
+
+impl SlotStdout {
+    fn from(slot: usize, schedule: &SlotSchedule, now: chrono::DateTime<Utc>) -> Self {
+        Self {
+            slot,
+            free_in: (schedule.free_from - now).num_seconds(),
+            profit: schedule.jobs.iter().map(|job| job.profit).sum(),
+            jobs: schedule
+                .jobs
+                .iter()
+                .map(|job| JobStdout {
+                    item_name: job.name.clone(),
+                    runs: job.runs,
+                    start_in: (job.start - now).num_seconds(),
+                    end_in: (job.end - now).num_seconds(),
+                    profit: job.profit,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Message for SlotStdout {
+    fn standard(&self, verbosity: Verbosity) -> ColoredString {
+        let title = if self.free_in > 0 {
+            format!(
+                "\tSlot {} (free in {}):\n",
+                self.slot,
+                Duration::seconds(self.free_in).to_display()
+            )
+        } else {
+            format!("\tSlot {}:\n", self.slot)
+        };
+        let mut jobs_str = String::new();
+        if self.jobs.is_empty() {
+            jobs_str += "\t\tNothing profitable to queue\n";
+        }
+        for job in &self.jobs {
+            jobs_str += job.standard(verbosity).to_string().as_str();
+        }
+        ColoredString::from(format!("{}{}\n", title.bold(), jobs_str).as_str())
+    }
+}
+
+#[derive(Serialize)]
+pub struct JobStdout {
+    item_name: String,
+    runs: i64,
+    /// Time left before the job starts, in seconds.
+    start_in: i64,
+    /// Time left before the job ends, in seconds.
+    end_in: i64,
+    profit: f64,
+}
+
+impl Message for JobStdout {
+    fn standard(&self, _: Verbosity) -> ColoredString {
+        let profit_str = format!("{} ISK", self.profit.to_display());
+        ColoredString::from(
+            format!(
+                "\t\t{:>40}{:>10} run(s){:>15} -> {:<15}{:>30}\n",
+                self.item_name.bold(),
+                self.runs,
+                Duration::seconds(self.start_in).to_display(),
+                Duration::seconds(self.end_in).to_display(),
+                profit_str
+            )
+            .as_str(),
+        )
+    }
+}
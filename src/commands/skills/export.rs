@@ -0,0 +1,73 @@
+use colored::{ColoredString, Colorize};
+use serde::Serialize;
+
+use crate::configuration::cli::SkillsExportOptions;
+use crate::display::Display;
+use crate::errors::{EveError, ModelError};
+use crate::filesystem::{SkillProfile, SkillProfileEntry};
+use crate::integration::DataIntegrator;
+use crate::logging;
+use crate::logging::{Message, Stdout, Verbosity};
+use crate::model::common::{Identified, Named};
+
+pub async fn skills_export(
+    eve: &DataIntegrator,
+    opts: &SkillsExportOptions,
+) -> Result<(), EveError> {
+    let character = eve
+        .load_character()
+        .await
+        .map_err(|source| ModelError::LoadingCharacter { source })?;
+
+    let profile = SkillProfile {
+        all: None,
+        skills: character
+            .skills
+            .skills
+            .iter()
+            .map(|skill| SkillProfileEntry {
+                id: skill.id(),
+                name: Some(skill.name()),
+                level: skill.trained_level,
+            })
+            .collect(),
+    };
+    let path = eve
+        .fs()
+        .save_skill_profile(&opts.name, &profile)
+        .await
+        .map_err(|source| ModelError::SavingSkillProfile { source })?;
+
+    logging::stdoutln(SkillsExportStdout {
+        character_name: character.name(),
+        profile_name: opts.name.clone(),
+        path: path.to_display(),
+        nb_skills: profile.skills.len(),
+    })?;
+    Ok(())
+}
+
+#[derive(Serialize)]
+pub struct SkillsExportStdout {
+    character_name: String,
+    profile_name: String,
+    path: String,
+    nb_skills: usize,
+}
+
+impl Stdout for SkillsExportStdout {}
+
+impl Message for SkillsExportStdout {
+    fn standard(&self, _: Verbosity) -> ColoredString {
+        ColoredString::from(
+            format!(
+                "{} skills of {} saved to skill profile '{}' ({})\n",
+                self.nb_skills,
+                self.character_name.bold(),
+                self.profile_name.bold(),
+                self.path
+            )
+            .as_str(),
+        )
+    }
+}
@@ -0,0 +1,16 @@
+use crate::{
+    configuration::cli::{SkillsCommands, SkillsOptions},
+    errors::EveError,
+    integration::DataIntegrator,
+};
+
+mod export;
+mod roi;
+
+pub async fn skills(eve: &DataIntegrator, opts: &SkillsOptions) -> Result<(), EveError> {
+    match &opts.command {
+        SkillsCommands::Roi(roi_opts) => roi::skills_roi(eve, roi_opts).await?,
+        SkillsCommands::Export(export_opts) => export::skills_export(eve, export_opts).await?,
+    }
+    Ok(())
+}
@@ -0,0 +1,341 @@
+use std::collections::HashSet;
+
+use chrono::Duration;
+use colored::{ColoredString, Colorize};
+use futures_util::future::{try_join4, TryJoinAll};
+use futures_util::TryFutureExt;
+use serde::Serialize;
+use tokio::try_join;
+
+use crate::api::evecache::cache_keys::OrderType;
+use crate::configuration::cli::SkillsRoiOptions;
+use crate::display::Display;
+use crate::errors::{EveError, ModelError};
+use crate::integration::DataIntegrator;
+use crate::logging;
+use crate::logging::{Message, Stdout, Verbosity};
+use crate::model::blueprint::BlueprintManufacturing;
+use crate::model::character::Skills;
+use crate::model::common::{Identified, Named};
+use crate::model::facility::invention::InventionFacility;
+use crate::model::facility::manufacture::Manufacture;
+use crate::model::implants::Implants;
+use crate::model::industry::IndustryType;
+use crate::model::prices::Prices;
+use crate::model::skills::MAX_SKILL_LEVEL;
+
+/// Industry, Advanced Industry, Accounting and Broker Relations. Science and encryption skills are
+/// taken from the invention blueprints of the registered items.
+static INDUSTRY_SKILL_IDS: [i32; 4] = [3380, 3388, 16622, 3446];
+
+pub async fn skills_roi(eve: &DataIntegrator, opts: &SkillsRoiOptions) -> Result<(), EveError> {
+    let (character, facilities, prices, attributes) = try_join4(
+        eve.load_character_with_profile(opts.skill_profile.skills.as_deref())
+            .map_err(|source| ModelError::LoadingCharacter { source }),
+        eve.load_registered_facilities()
+            .map_err(|source| ModelError::LoadingFacilities { source }),
+        eve.load_prices()
+            .map_err(|source| ModelError::LoadingPrices { source }),
+        eve.load_character_attributes()
+            .map_err(|source| ModelError::LoadingCharacterAttributes { source }),
+    )
+    .await?;
+
+    let items = eve.load_registered_items().await?;
+
+    let mut manufactures = vec![];
+    let mut invention_facilities = vec![];
+    let mut markets = vec![];
+    for facility in &facilities {
+        if let Some(manufacture) = facility.manufacture() {
+            manufactures.push(manufacture);
+        }
+        if let Some(invention) = facility.invention() {
+            invention_facilities.push(invention);
+        }
+        if let Some(market) = facility.market() {
+            markets.push(market);
+        }
+    }
+
+    let mut region_ids = HashSet::new();
+    for market in &markets {
+        region_ids.insert(market.as_factility().location.constellation.region.id());
+    }
+    let mut futures = vec![];
+    for region_id in region_ids {
+        futures.push(eve.load_market_orders(region_id, OrderType::Sell));
+    }
+    let all_futures = futures.into_iter().collect::<TryJoinAll<_>>();
+    let orders = try_join!(all_futures)?.0;
+
+    logging::info!("Data retrieved - Load {} items", items.len());
+    let mut products = vec![];
+    for item in items {
+        let blueprints: Vec<BlueprintManufacturing> = eve
+            .load_item_blueprints(item.id(), IndustryType::Manufacturing)
+            .await
+            .map_err(|source| ModelError::LoadingBlueprint { source })?
+            .into_iter()
+            .filter_map(|blueprint| blueprint.activities.manufacturing)
+            .collect();
+        if blueprints.is_empty() {
+            continue;
+        }
+
+        let mut highest_sell_price = None;
+        for market in &markets {
+            let orders = orders.iter().find(|&order| {
+                order.region.id() == market.as_factility().location.constellation.region.id()
+            });
+            let price = orders.map(|orders| {
+                orders.lowest_station_sell_price(item.id(), market.as_factility().id())
+            });
+            // A zero price means the station doesn't sell the item
+            if let Some(price) = price.filter(|price| *price > 0.0) {
+                highest_sell_price = match highest_sell_price {
+                    Some(highest) if highest >= price => Some(highest),
+                    _ => Some(price),
+                };
+            }
+        }
+        if let Some(sell_price) = highest_sell_price {
+            products.push(Product {
+                name: item.name(),
+                id: item.id(),
+                blueprints,
+                sell_price,
+            });
+        }
+    }
+
+    let mut skill_ids = INDUSTRY_SKILL_IDS.to_vec();
+    for product in &products {
+        for blueprint in &product.blueprints {
+            for invention in &blueprint.invention_blueprint {
+                for skill in &invention.skills {
+                    if !skill_ids.contains(&skill.id()) {
+                        skill_ids.push(skill.id());
+                    }
+                }
+            }
+        }
+    }
+
+    let facilities = Facilities {
+        manufactures: &manufactures,
+        invention_facilities: &invention_facilities,
+    };
+    logging::info!("Compute profits with current skills");
+    let current_profits = profits_per_hour(
+        &products,
+        &facilities,
+        &character.skills,
+        &character.implants,
+        &prices,
+    )
+    .await?;
+
+    let mut skills_stdout = vec![];
+    for skill_id in skill_ids {
+        let training = match eve.load_skill_training(skill_id).await? {
+            Some(training) => training,
+            None => {
+                logging::debug!("Not a trainable skill: {}", skill_id);
+                continue;
+            }
+        };
+        let current_level = character
+            .skills
+            .get_skill(skill_id)
+            .map(|skill| skill.trained_level)
+            .unwrap_or(0);
+        if current_level >= MAX_SKILL_LEVEL {
+            continue;
+        }
+
+        logging::info!("Compute profits with skill raised: {}", training.name());
+        let raised_skills = character
+            .skills
+            .with_raised_skill(skill_id, &training.name());
+        let raised_profits = profits_per_hour(
+            &products,
+            &facilities,
+            &raised_skills,
+            &character.implants,
+            &prices,
+        )
+        .await?;
+
+        let mut gain_per_hour = 0.0;
+        let mut best_item: Option<(String, f64)> = None;
+        for ((product, current), raised) in
+            products.iter().zip(&current_profits).zip(&raised_profits)
+        {
+            let gain = match (current, raised) {
+                (Some(current), Some(raised)) => raised - current,
+                _ => continue,
+            };
+            gain_per_hour += gain;
+            best_item = match best_item {
+                Some((name, best_gain)) if best_gain >= gain => Some((name, best_gain)),
+                _ => Some((product.name.clone(), gain)),
+            };
+        }
+        let (best_item, best_item_gain_per_hour) = best_item.unzip();
+        skills_stdout.push(SkillStdout {
+            name: training.name(),
+            current_level,
+            next_level: current_level + 1,
+            gain_per_hour,
+            best_item,
+            best_item_gain_per_hour,
+            training_time: training.training_time(current_level + 1, &attributes),
+        });
+    }
+    skills_stdout.sort_by(|a, b| b.gain_per_hour.total_cmp(&a.gain_per_hour));
+
+    let mut best_current: Option<(String, f64)> = None;
+    for (product, profits) in products.iter().zip(&current_profits) {
+        if let Some(profits) = *profits {
+            best_current = match best_current {
+                Some((name, best)) if best >= profits => Some((name, best)),
+                _ => Some((product.name.clone(), profits)),
+            };
+        }
+    }
+    let (best_current_item, current_profits_per_hour) = best_current.unzip();
+    logging::stdoutln(SkillsRoiStdout {
+        best_current_item,
+        current_profits_per_hour,
+        skills: skills_stdout,
+    })?;
+    Ok(())
+}
+
+struct Product {
+    name: String,
+    id: i32,
+    blueprints: Vec<BlueprintManufacturing>,
+    sell_price: f64,
+}
+
+struct Facilities<'a> {
+    manufactures: &'a Vec<Manufacture<'a>>,
+    invention_facilities: &'a Vec<InventionFacility<'a>>,
+}
+
+/// Profits per hour of each registered item, with its most profitable blueprint and facility, once
+/// sold through a sell order. `None` for items which can't be manufactured.
+async fn profits_per_hour(
+    products: &[Product],
+    facilities: &Facilities<'_>,
+    skills: &Skills,
+    implants: &Implants,
+    prices: &Prices,
+) -> Result<Vec<Option<f64>>, EveError> {
+    let mut all_profits = vec![];
+    for product in products {
+        let mut best = None;
+        for blueprint in &product.blueprints {
+            for manufacture in facilities.manufactures {
+                let cost_per_run = manufacture
+                    .manufacture_cost_per_run(
+                        blueprint,
+                        skills,
+                        implants,
+                        facilities.invention_facilities,
+                        None,
+                        prices,
+                    )
+                    .await?
+                    .value;
+                let profits = manufacture.profits_per_hour(
+                    blueprint,
+                    product.id,
+                    cost_per_run,
+                    product.sell_price,
+                    skills,
+                    implants,
+                );
+                if let Some(profits) = profits {
+                    best = match best {
+                        Some(highest) if highest >= profits => Some(highest),
+                        _ => Some(profits),
+                    };
+                }
+            }
+        }
+        all_profits.push(best);
+    }
+    Ok(all_profits)
+}
+
+#[derive(Serialize)]
+pub struct SkillsRoiStdout {
+    best_current_item: Option<String>,
+    current_profits_per_hour: Option<f64>,
+    skills: Vec<SkillStdout>,
+}
+
+impl Stdout for SkillsRoiStdout {}
+
+impl Message for SkillsRoiStdout {
+    fn standard(&self, verbosity: Verbosity) -> ColoredString {
+        let current = match (&self.best_current_item, self.current_profits_per_hour) {
+            (Some(item), Some(profits)) => format!("{} ISK/h ({})", profits.to_display(), item),
+            _ => "N/A".to_string(),
+        };
+        let mut skills_str = format!(
+            "{} {}\n\n{:>40}{:>10}{:>30}{:>40}{:>25}{:>25}\n",
+            "Best profits with current skills:".bold(),
+            current,
+            "Skill",
+            "Level",
+            "Gain, all items (ISK/h)",
+            "Best item",
+            "Best item gain (ISK/h)",
+            "Training time"
+        );
+        for skill in &self.skills {
+            skills_str += skill.standard(verbosity).to_string().as_str();
+        }
+        ColoredString::from(skills_str.as_str())
+    }
+}
+
+#[derive(Serialize)]
+pub struct SkillStdout {
+    name: String,
+    current_level: i32,
+    next_level: i32,
+    /// Sum of the gains of all registered items.
+    gain_per_hour: f64,
+    /// Registered item gaining the most profits per hour.
+    best_item: Option<String>,
+    best_item_gain_per_hour: Option<f64>,
+    /// Time to train the next level, in seconds.
+    training_time: i64,
+}
+
+impl Message for SkillStdout {
+    fn standard(&self, _: Verbosity) -> ColoredString {
+        let levels = format!("{} -> {}", self.current_level, self.next_level);
+        let best_item_gain = match self.best_item_gain_per_hour {
+            Some(gain) => gain.to_display(),
+            None => "N/A".to_string(),
+        };
+        ColoredString::from(
+            format!(
+                "{:>40}{:>10}{:>30}{:>40}{:>25}{:>25}\n",
+                self.name.bold(),
+                levels,
+                self.gain_per_hour.to_display(),
+                self.best_item.as_deref().unwrap_or("N/A"),
+                best_item_gain,
+                Duration::seconds(self.training_time).to_display()
+            )
+            .as_str(),
+        )
+    }
+}
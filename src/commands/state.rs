@@ -1,4 +1,4 @@
-use chrono::Duration;
+use chrono::{Duration, Utc};
 use colored::{ColoredString, Colorize};
 use futures_util::future::try_join3;
 use futures_util::TryFutureExt;
@@ -12,7 +12,7 @@ use crate::logging::{Message, Stdout, Verbosity};
 use crate::model::character::{Character, CharacterLocation, Corporation};
 use crate::model::common::Named;
 use crate::model::facility::Facility;
-use crate::model::industry::Job;
+use crate::model::industry::{Job, JobSlots, SlotType};
 use crate::model::locations::{Constellation, Region, SolarSystem};
 use crate::model::markets::CharacterOrder;
 
@@ -38,6 +38,7 @@ pub struct StateStdout {
     location: CharacterLocationStdout,
     corporation: CorporationStdout,
     jobs: JobsStdout,
+    job_slots: JobSlotsStdout,
     orders: OrdersStdout,
 }
 
@@ -54,6 +55,13 @@ impl StateStdout {
             })
             .collect();
 
+        let job_slots = character
+            .skills
+            .job_slots(&jobs)
+            .iter()
+            .map(SlotsStdout::from)
+            .collect();
+
         let mut jobs: Vec<JobStdout> = jobs
             .into_iter()
             .filter(|j| j.item_produced.is_some())
@@ -72,6 +80,7 @@ impl StateStdout {
             location: CharacterLocationStdout::from(&character.location),
             corporation: CorporationStdout::from(&character.corporation),
             jobs: JobsStdout { jobs },
+            job_slots: JobSlotsStdout { slots: job_slots },
             orders: OrdersStdout { orders },
         }
     }
@@ -122,6 +131,68 @@ impl Message for JobStdout {
     }
 }
 
+#[derive(Serialize)]
+pub struct JobSlotsStdout {
+    slots: Vec<SlotsStdout>,
+}
+
+impl Message for JobSlotsStdout {
+    fn standard(&self, verbosity: Verbosity) -> ColoredString {
+        let mut slots_str = String::new();
+        for slots in &self.slots {
+            slots_str += slots.standard(verbosity).to_string().as_str();
+        }
+        ColoredString::from(format!("Job slots:\n{slots_str}").as_str())
+    }
+}
+
+#[derive(Serialize)]
+pub struct SlotsStdout {
+    slot_type: SlotType,
+    used: i32,
+    max: i32,
+    /// Time left before each occupied slot becomes free, in seconds.
+    free_in: Vec<i64>,
+}
+
+impl From<&JobSlots> for SlotsStdout {
+    fn from(value: &JobSlots) -> Self {
+        let now = Utc::now();
+        Self {
+            slot_type: value.slot_type,
+            used: value.used(),
+            max: value.max,
+            free_in: value
+                .end_dates
+                .iter()
+                .map(|end_date| (*end_date - now).num_seconds().max(0))
+                .collect(),
+        }
+    }
+}
+
+impl Message for SlotsStdout {
+    fn standard(&self, _: Verbosity) -> ColoredString {
+        let free = format!("{} free", (self.max - self.used).max(0));
+        let mut slots_str = format!(
+            "\t{:>40}{:>10}/{:<5}{:>15}\n",
+            self.slot_type.to_string().bold(),
+            self.used,
+            self.max,
+            free
+        );
+        for free_in in &self.free_in {
+            let duration_str = if *free_in == 0 {
+                String::from("Done")
+            } else {
+                Duration::seconds(*free_in).to_display()
+            };
+            slots_str += format!("\t{:>40}{:>30}\n", "Slot free in:", duration_str).as_str();
+        }
+        ColoredString::from(slots_str.as_str())
+    }
+}
+
 #[derive(Serialize)]
 pub struct OrdersStdout {
     orders: Vec<OrderStdout>,
@@ -323,9 +394,11 @@ impl Message for StateStdout {
             let corporation = self.corporation.standard(verbosity);
 
             let job = self.jobs.standard(verbosity);
+            let job_slots = self.job_slots.standard(verbosity);
             let orders = self.orders.standard(verbosity);
 
-            extra_info = format!("{isk}\n{location}\n{corporation}\n\n{job}\n\n{orders}\n")
+            extra_info =
+                format!("{isk}\n{location}\n{corporation}\n\n{job}\n{job_slots}\n\n{orders}\n")
         }
 
         ColoredString::from(format!("{name}\n{extra_info}").as_str())
@@ -1,7 +1,10 @@
 use std::fs;
 use std::io::Read;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
+use crate::api::sde::{self, Sde};
+use crate::cache::FSCache;
 use crate::configuration::cli::UpdateOptions;
 use crate::display::Display;
 use crate::errors::{EnvironmentError, EveApiError, EveError};
@@ -16,21 +19,59 @@ use zip::ZipArchive;
 
 use crate::{filesystem, logging};
 
-const SDE_FILES_PATHS: [&str; 2] = ["sde/fsd/blueprints.yaml", "sde/fsd/typeMaterials.yaml"];
+const SDE_FILES_PATHS: [&str; 7] = [
+    sde::BLUEPRINTS_PATH,
+    "sde/fsd/typeMaterials.yaml",
+    sde::TYPES_PATH,
+    sde::GROUPS_PATH,
+    sde::CATEGORIES_PATH,
+    sde::META_GROUPS_PATH,
+    sde::TYPE_DOGMA_PATH,
+];
+
+const SDE_URL: &str =
+    "https://eve-static-data-export.s3-eu-west-1.amazonaws.com/tranquility/sde.zip";
+const SDE_CHECKSUM_URL: &str =
+    "https://eve-static-data-export.s3-eu-west-1.amazonaws.com/tranquility/checksum";
+
+/// Checksum of the archive the installed SDE was extracted from.
+const INSTALLED_CHECKSUM_PATH: &str = "sde/checksum";
+/// Last checksum published by CCP, kept to avoid querying it on every command.
+const LATEST_CHECKSUM_PATH: &str = "sde/latest-checksum";
+const LATEST_CHECKSUM_VALIDITY: Duration = Duration::from_secs(24 * 60 * 60);
 
 pub async fn update(cache_dir: PathBuf, opts: &UpdateOptions) -> Result<(), EveError> {
     let installed = check_installed(&cache_dir).await?;
-    if installed {
-        let redownload = Confirm::new("SDE is already installed. Do you want to update it ?")
-            .with_default(false)
-            .prompt()
-            .handle_exit_signals()
-            .map_err(|source| EnvironmentError::SpecificInputError {
-                description: "SDE redownload confirmation".to_string(),
-                source,
-            })?;
-        if let None | Some(false) = redownload {
-            return Ok(());
+    if installed && opts.from_file.is_none() {
+        if let Some(up_to_date) = is_up_to_date(&cache_dir).await {
+            if up_to_date {
+                let reinstall =
+                    Confirm::new("SDE is already up to date. Do you want to reinstall it ?")
+                        .with_default(false)
+                        .prompt()
+                        .handle_exit_signals()
+                        .map_err(|source| EnvironmentError::SpecificInputError {
+                            description: "SDE reinstall confirmation".to_string(),
+                            source,
+                        })?;
+                if let None | Some(false) = reinstall {
+                    return Ok(());
+                }
+            } else {
+                logging::info!("A newer SDE has been published");
+            }
+        } else {
+            let redownload = Confirm::new("SDE is already installed. Do you want to update it ?")
+                .with_default(false)
+                .prompt()
+                .handle_exit_signals()
+                .map_err(|source| EnvironmentError::SpecificInputError {
+                    description: "SDE redownload confirmation".to_string(),
+                    source,
+                })?;
+            if let None | Some(false) = redownload {
+                return Ok(());
+            }
         }
     }
 
@@ -38,24 +79,29 @@ pub async fn update(cache_dir: PathBuf, opts: &UpdateOptions) -> Result<(), EveE
     let mut download_destination = cache_dir.clone();
     download_destination.push("sde/sde.zip");
 
-    if !opts.no_download {
-        if download_destination.exists() {
-            tokio::fs::remove_file(&download_destination)
-                .await
-                .map_err(|source| EveApiError::SDEDeletePreviousZip { source })?
+    let archive = match &opts.from_file {
+        Some(from_file) => {
+            logging::debug!("Using SDE archive {}", from_file.to_display());
+            from_file.clone()
         }
-        logging::debug!("Downloading SDE");
-        download_sde(&download_destination).await?;
-    }
+        None => {
+            if !opts.no_download {
+                if download_destination.exists() {
+                    tokio::fs::remove_file(&download_destination)
+                        .await
+                        .map_err(|source| EveApiError::SDEDeletePreviousZip { source })?
+                }
+                logging::debug!("Downloading SDE");
+                download_sde(&download_destination).await?;
+            }
+            download_destination
+        }
+    };
 
     logging::debug!("Extracting from SDE");
     let mut futures = vec![];
     for to_extract_path in SDE_FILES_PATHS {
-        futures.push(extract_sde_files(
-            &cache_dir,
-            &download_destination,
-            to_extract_path,
-        ));
+        futures.push(extract_sde_files(&cache_dir, &archive, to_extract_path));
     }
     let all_extract_operations = futures.into_iter().collect::<TryJoinAll<_>>();
 
@@ -72,12 +118,134 @@ pub async fn update(cache_dir: PathBuf, opts: &UpdateOptions) -> Result<(), EveE
 
     try_join!(all_operations).map_err(|source| EveApiError::SDESplitFile { source })?;
 
+    logging::debug!("Indexing SDE");
+    Sde::new(FSCache::new(cache_dir.clone()))
+        .build_index()
+        .await
+        .map_err(|source| EveApiError::SDEIndexBuildFailed { source })?;
+
+    let checksum = archive_checksum(&archive)?;
+    logging::debug!("Installed SDE checksum: {}", checksum);
+    write_checksum(&cache_dir.join(INSTALLED_CHECKSUM_PATH), &checksum).await?;
+    if opts.from_file.is_none() && !opts.no_download {
+        write_checksum(&cache_dir.join(LATEST_CHECKSUM_PATH), &checksum).await?;
+    }
+
     logging::info!("SDE is ready to use");
     Ok(())
 }
 
+/// Warns when a newer SDE than the installed one has been published. Failures are only logged, as
+/// the installed SDE stays usable.
+pub async fn warn_if_outdated(cache_dir: &Path) {
+    if let Some(false) = is_up_to_date(cache_dir).await {
+        logging::warning!("A newer SDE is available, run 'eve-vulcain update' to install it");
+    }
+}
+
+/// Compares the installed SDE with the published one. Returns `None` when any of them is unknown.
+async fn is_up_to_date(cache_dir: &Path) -> Option<bool> {
+    let installed = match read_checksum(&cache_dir.join(INSTALLED_CHECKSUM_PATH)).await {
+        Ok(installed) => installed?,
+        Err(e) => {
+            logging::debug!("{}", e);
+            return None;
+        }
+    };
+    match latest_checksum(cache_dir).await {
+        Ok(latest) => Some(latest == installed),
+        Err(e) => {
+            logging::debug!("Could not check for SDE updates: {}", e);
+            None
+        }
+    }
+}
+
+async fn latest_checksum(cache_dir: &Path) -> Result<String, EveApiError> {
+    let path = cache_dir.join(LATEST_CHECKSUM_PATH);
+    let fresh = tokio::fs::metadata(&path)
+        .await
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .map(|elapsed| elapsed < LATEST_CHECKSUM_VALIDITY)
+        .unwrap_or(false);
+    if fresh {
+        if let Some(checksum) = read_checksum(&path).await? {
+            return Ok(checksum);
+        }
+    }
+
+    logging::debug!("Downloading SDE checksum from {}", SDE_CHECKSUM_URL);
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(5))
+        .build()
+        .map_err(|source| EveApiError::SDEChecksumDownloadFailed { source })?;
+    let content = client
+        .get(SDE_CHECKSUM_URL)
+        .send()
+        .await
+        .and_then(|resp| resp.error_for_status())
+        .map_err(|source| EveApiError::SDEChecksumDownloadFailed { source })?
+        .text()
+        .await
+        .map_err(|source| EveApiError::SDEChecksumDownloadFailed { source })?;
+    let checksum = parse_checksum(&content).ok_or(EveApiError::SDEChecksumInvalid)?;
+    write_checksum(&path, &checksum).await?;
+    Ok(checksum)
+}
+
+async fn read_checksum(path: &Path) -> Result<Option<String>, EveApiError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = tokio::fs::read_to_string(path).await.map_err(|source| {
+        EveApiError::SDEChecksumReadFailed {
+            path: path.to_display(),
+            source,
+        }
+    })?;
+    Ok(parse_checksum(&content))
+}
+
+async fn write_checksum(path: &Path, checksum: &str) -> Result<(), EveApiError> {
+    filesystem::write_atomically_async(path, checksum)
+        .await
+        .map_err(|source| EveApiError::SDEChecksumWriteFailed {
+            path: path.to_display(),
+            source,
+        })
+}
+
+fn parse_checksum(content: &str) -> Option<String> {
+    content
+        .split_whitespace()
+        .next()
+        .map(|checksum| checksum.to_lowercase())
+}
+
+fn archive_checksum(archive: &Path) -> Result<String, EveApiError> {
+    let mut file =
+        fs::File::open(archive).map_err(|source| EveApiError::SDEZipOpenError { source })?;
+    let mut context = md5::Context::new();
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .map_err(|source| EveApiError::SDEChecksumReadFailed {
+                path: archive.to_display(),
+                source,
+            })?;
+        if read == 0 {
+            break;
+        }
+        context.consume(&buffer[..read]);
+    }
+    Ok(format!("{:x}", context.compute()))
+}
+
 async fn download_sde(destination_path: &PathBuf) -> Result<(), EveError> {
-    let url = "https://eve-static-data-export.s3-eu-west-1.amazonaws.com/tranquility/sde.zip";
+    let url = SDE_URL;
     logging::debug!("Downloading from {}", url);
 
     create_parent(destination_path).await?;
@@ -171,7 +339,7 @@ async fn create_parent(destination_path: &Path) -> Result<(), EveError> {
 }
 
 async fn check_installed(cache_dir: &Path) -> Result<bool, EveError> {
-    for p in SDE_FILES_PATHS {
+    for p in SDE_FILES_PATHS.iter().chain([&sde::INDEX_PATH]) {
         let mut path = cache_dir.to_path_buf();
         path.push(p);
         let exist = tokio::fs::try_exists(&path).await.map_err(|source| {
@@ -187,3 +355,29 @@ async fn check_installed(cache_dir: &Path) -> Result<bool, EveError> {
 
     Ok(true)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn archive_checksum_matches_md5_of_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("sde.zip");
+        fs::write(&archive, "eve-vulcain").unwrap();
+
+        let checksum = archive_checksum(&archive).unwrap();
+
+        assert_eq!(checksum, format!("{:x}", md5::compute("eve-vulcain")));
+        assert_eq!(parse_checksum(&checksum.to_uppercase()), Some(checksum));
+    }
+
+    #[test]
+    fn parse_checksum_ignores_trailing_content() {
+        assert_eq!(
+            parse_checksum("9E107D9D372BB6826BD81D3542A419D6  sde.zip\n"),
+            Some("9e107d9d372bb6826bd81d3542a419d6".to_string())
+        );
+        assert_eq!(parse_checksum("  \n"), None);
+    }
+}
@@ -0,0 +1,79 @@
+use futures_util::TryFutureExt;
+
+use crate::configuration::cli::{WatchAddCommands, WatchThresholdOptions};
+use crate::errors::{EveError, ModelError};
+use crate::integration::DataIntegrator;
+use crate::interactive;
+use crate::model::common::{Identified, Named};
+use crate::model::industry::IndustryType;
+use crate::model::watch::{Comparison, WatchMetric, WatchRule};
+
+pub async fn add(eve: &DataIntegrator, opts: &WatchAddCommands) -> Result<(), EveError> {
+    let threshold_opts = match opts {
+        WatchAddCommands::Profit(threshold_opts) => threshold_opts,
+        WatchAddCommands::SellPrice(market_opts) | WatchAddCommands::Competitors(market_opts) => {
+            &market_opts.threshold
+        }
+    };
+
+    let item = match interactive::load_item(
+        eve,
+        threshold_opts.item.clone(),
+        threshold_opts.strict,
+        IndustryType::Manufacturing,
+        None,
+    )
+    .await?
+    {
+        Some(item) => item,
+        None => return Ok(()),
+    };
+
+    let metric = match opts {
+        WatchAddCommands::Profit(_) => WatchMetric::ProfitPerHour,
+        WatchAddCommands::SellPrice(market_opts) => WatchMetric::SellPrice {
+            market_id: find_market(eve, &market_opts.market).await?,
+        },
+        WatchAddCommands::Competitors(market_opts) => WatchMetric::Competitors {
+            market_id: find_market(eve, &market_opts.market).await?,
+        },
+    };
+    let (comparison, threshold) = comparison(threshold_opts);
+
+    eve.fs()
+        .add_watch_rule(WatchRule {
+            item_id: item.id(),
+            metric,
+            comparison,
+            threshold,
+        })
+        .await
+        .map_err(|source| ModelError::SaveWatchRuleError { source })?;
+    Ok(())
+}
+
+/// Finds the registered market whose name contains `name`, ignoring case.
+async fn find_market(eve: &DataIntegrator, name: &str) -> Result<i64, EveError> {
+    let facilities = eve
+        .load_registered_facilities()
+        .map_err(|source| ModelError::LoadingFacilities { source })
+        .await?;
+    let search = name.to_lowercase();
+    facilities
+        .iter()
+        .filter(|facility| facility.name().to_lowercase().contains(&search))
+        .find_map(|facility| facility.market())
+        .map(|market| market.as_factility().id())
+        .ok_or_else(|| ModelError::MarketNotFound {
+            name: name.to_string(),
+        })
+        .map_err(EveError::from)
+}
+
+fn comparison(opts: &WatchThresholdOptions) -> (Comparison, f64) {
+    match (opts.above, opts.below) {
+        (Some(above), _) => (Comparison::Above, above),
+        (None, Some(below)) => (Comparison::Below, below),
+        (None, None) => unreachable!("Clap requires either --above or --below"),
+    }
+}
@@ -0,0 +1,134 @@
+use std::fmt::{Display as FmtDisplay, Formatter};
+
+use colored::ColoredString;
+use futures_util::future::TryJoinAll;
+use futures_util::TryFutureExt;
+use serde::Serialize;
+use tokio::try_join;
+
+use crate::display::Display;
+use crate::errors::{EveError, ModelError};
+use crate::integration::DataIntegrator;
+use crate::logging;
+use crate::logging::{Message, Stdout, Verbosity};
+use crate::model::common::{Identified, Named};
+use crate::model::facility::Facility;
+use crate::model::watch::{Comparison, WatchMetric, WatchRule};
+
+pub async fn ls(eve: &DataIntegrator) -> Result<(), EveError> {
+    let rules = eve
+        .fs()
+        .load_watch_rules()
+        .await
+        .map_err(|source| ModelError::LoadingWatchRules { source })?
+        .rules;
+    let facilities = eve
+        .load_registered_facilities()
+        .map_err(|source| ModelError::LoadingFacilities { source })
+        .await?;
+    logging::stdoutln(WatchLSStdout {
+        rules: RuleStdout::load(eve, &rules, &facilities).await?,
+    })?;
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct WatchLSStdout {
+    rules: Vec<RuleStdout>,
+}
+
+impl Stdout for WatchLSStdout {}
+
+impl Message for WatchLSStdout {
+    fn standard(&self, verbosity: Verbosity) -> ColoredString {
+        let mut all_rules = String::new();
+        for rule in &self.rules {
+            all_rules += rule.standard(verbosity).to_string().as_str();
+        }
+        ColoredString::from(all_rules.as_str())
+    }
+}
+
+#[derive(Serialize)]
+pub struct RuleStdout {
+    pub item_name: String,
+    pub metric: WatchMetric,
+    /// Name of the market of sell price and competitors rules.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub market_name: Option<String>,
+    pub comparison: Comparison,
+    pub threshold: f64,
+}
+
+impl RuleStdout {
+    pub async fn load(
+        eve: &DataIntegrator,
+        rules: &[WatchRule],
+        facilities: &[Facility],
+    ) -> Result<Vec<Self>, EveError> {
+        let mut futures = vec![];
+        for rule in rules {
+            futures.push(async move {
+                let item = eve.load_item(rule.item_id).await?;
+                let market_name = match rule.metric {
+                    WatchMetric::ProfitPerHour => None,
+                    WatchMetric::SellPrice { market_id }
+                    | WatchMetric::Competitors { market_id } => Some(
+                        facilities
+                            .iter()
+                            .find(|facility| facility.id() == market_id)
+                            .map_or_else(|| market_id.to_string(), |facility| facility.name()),
+                    ),
+                };
+                Ok::<RuleStdout, EveError>(RuleStdout {
+                    item_name: item.name(),
+                    metric: rule.metric.clone(),
+                    market_name,
+                    comparison: rule.comparison,
+                    threshold: rule.threshold,
+                })
+            });
+        }
+        let all_futures = futures.into_iter().collect::<TryJoinAll<_>>();
+        Ok(try_join!(all_futures)?.0)
+    }
+
+    pub fn condition(&self) -> String {
+        let comparison = match self.comparison {
+            Comparison::Above => "above",
+            Comparison::Below => "below",
+        };
+        let market_name = self.market_name.clone().unwrap_or_default();
+        match self.metric {
+            WatchMetric::ProfitPerHour => format!(
+                "profits per hour {} {} ISK/h",
+                comparison,
+                self.threshold.to_display()
+            ),
+            WatchMetric::SellPrice { .. } => format!(
+                "sell price at {} {} {} ISK",
+                market_name,
+                comparison,
+                self.threshold.to_display()
+            ),
+            WatchMetric::Competitors { .. } => format!(
+                "sell orders at {} {} {}",
+                market_name,
+                comparison,
+                self.threshold.to_display()
+            ),
+        }
+    }
+}
+
+impl Message for RuleStdout {
+    fn standard(&self, _: Verbosity) -> ColoredString {
+        ColoredString::from(format!("{:<50}{}\n", self.item_name, self.condition()).as_str())
+    }
+}
+
+impl FmtDisplay for RuleStdout {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} - {}", self.item_name, self.condition())
+    }
+}
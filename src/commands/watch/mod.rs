@@ -0,0 +1,35 @@
+use crate::commands::watch::add::add;
+use crate::commands::watch::ls::ls;
+use crate::commands::watch::rm::rm;
+use crate::commands::watch::run::run;
+use crate::configuration::cli::{WatchCommands, WatchOptions};
+use crate::errors::EveError;
+use crate::integration::DataIntegrator;
+use crate::notification::Notifier;
+
+mod add;
+mod ls;
+mod rm;
+mod run;
+
+pub async fn watch(
+    eve: &DataIntegrator,
+    notifiers: &[Notifier],
+    opts: &WatchOptions,
+) -> Result<(), EveError> {
+    match &opts.command {
+        WatchCommands::Add(add_opts) => {
+            add(eve, add_opts).await?;
+        }
+        WatchCommands::Ls => {
+            ls(eve).await?;
+        }
+        WatchCommands::Rm => {
+            rm(eve).await?;
+        }
+        WatchCommands::Run(run_opts) => {
+            run(eve, notifiers, run_opts).await?;
+        }
+    }
+    Ok(())
+}
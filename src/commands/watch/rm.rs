@@ -0,0 +1,39 @@
+use futures_util::TryFutureExt;
+use inquire::Select;
+
+use crate::commands::watch::ls::RuleStdout;
+use crate::errors::{EnvironmentError, EveError, ModelError};
+use crate::integration::DataIntegrator;
+use crate::interactive::HandleInquireExitSignals;
+
+pub async fn rm(eve: &DataIntegrator) -> Result<(), EveError> {
+    let rules = eve
+        .fs()
+        .load_watch_rules()
+        .await
+        .map_err(|source| ModelError::LoadingWatchRules { source })?
+        .rules;
+    let facilities = eve
+        .load_registered_facilities()
+        .map_err(|source| ModelError::LoadingFacilities { source })
+        .await?;
+    let descriptions = RuleStdout::load(eve, &rules, &facilities).await?;
+
+    let selected = Select::new("Please choose a watch rule to remove: ", descriptions)
+        .raw_prompt()
+        .handle_exit_signals()
+        .map_err(|source| EnvironmentError::SpecificInputError {
+            description: "select watch rule".to_string(),
+            source,
+        })?;
+    let selected = match selected {
+        Some(selected) => selected,
+        None => return Ok(()),
+    };
+
+    eve.fs()
+        .rm_watch_rule(&rules[selected.index])
+        .await
+        .map_err(|source| ModelError::RemovingWatchRule { source })?;
+    Ok(())
+}
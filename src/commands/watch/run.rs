@@ -0,0 +1,260 @@
+use std::collections::HashSet;
+
+use colored::{ColoredString, Colorize};
+use futures_util::future::{try_join3, TryJoinAll};
+use futures_util::TryFutureExt;
+use serde::Serialize;
+use tokio::try_join;
+
+use crate::api::evecache::cache_keys::OrderType;
+use crate::commands::watch::ls::RuleStdout;
+use crate::configuration::cli::WatchRunOptions;
+use crate::display::Display;
+use crate::errors::{EveError, ModelError};
+use crate::integration::DataIntegrator;
+use crate::logging;
+use crate::logging::{Message, Stdout, Verbosity};
+use crate::model::blueprint::BlueprintManufacturing;
+use crate::model::character::Character;
+use crate::model::common::Identified;
+use crate::model::facility::markets::RegionOrders;
+use crate::model::facility::Facility;
+use crate::model::industry::IndustryType;
+use crate::model::prices::Prices;
+use crate::model::watch::{WatchMetric, WatchRule, WatchTracker};
+use crate::notification::{notify_all, Notification, Notifier};
+
+pub async fn run(
+    eve: &DataIntegrator,
+    notifiers: &[Notifier],
+    opts: &WatchRunOptions,
+) -> Result<(), EveError> {
+    let mut tracker = WatchTracker::default();
+    loop {
+        match check(eve, notifiers, &mut tracker).await {
+            // A daemon shouldn't stop on a temporary ESI failure
+            Err(err) if !opts.once => logging::warning!("Could not check watch rules: {}", err),
+            result => result?,
+        }
+        if opts.once {
+            return Ok(());
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(opts.interval * 60)).await;
+    }
+}
+
+/// Evaluates every rule, reloaded so rules added since the last check are included, and alerts on
+/// the rules which started being triggered.
+async fn check(
+    eve: &DataIntegrator,
+    notifiers: &[Notifier],
+    tracker: &mut WatchTracker,
+) -> Result<(), EveError> {
+    let rules = eve
+        .fs()
+        .load_watch_rules()
+        .await
+        .map_err(|source| ModelError::LoadingWatchRules { source })?
+        .rules;
+    let (character, facilities, prices) = try_join3(
+        eve.load_character()
+            .map_err(|source| ModelError::LoadingCharacter { source }),
+        eve.load_registered_facilities()
+            .map_err(|source| ModelError::LoadingFacilities { source }),
+        eve.load_prices()
+            .map_err(|source| ModelError::LoadingPrices { source }),
+    )
+    .await?;
+
+    let mut region_ids = HashSet::new();
+    for facility in &facilities {
+        if facility.market().is_some() {
+            region_ids.insert(facility.location.constellation.region.id());
+        }
+    }
+    let mut futures = vec![];
+    for region_id in region_ids {
+        futures.push(eve.load_market_orders(region_id, OrderType::Sell));
+    }
+    let all_futures = futures.into_iter().collect::<TryJoinAll<_>>();
+    let orders = try_join!(all_futures)?.0;
+    logging::info!("Data retrieved - Check {} watch rules", rules.len());
+
+    let mut futures = vec![];
+    for rule in &rules {
+        futures.push(rule_value(
+            eve,
+            rule,
+            &facilities,
+            &orders,
+            &character,
+            &prices,
+        ));
+    }
+    let all_futures = futures.into_iter().collect::<TryJoinAll<_>>();
+    let values = try_join!(all_futures)?.0;
+
+    let mut triggered_rules = vec![];
+    let mut triggered_values = vec![];
+    for (rule, value) in rules.iter().zip(values) {
+        if tracker.update(rule, value.map(|value| rule.is_triggered(value))) {
+            triggered_rules.push(rule.clone());
+            triggered_values.push(value.unwrap_or_default());
+        }
+    }
+    let descriptions = RuleStdout::load(eve, &triggered_rules, &facilities).await?;
+    for (rule, value) in descriptions.into_iter().zip(triggered_values) {
+        let alert = AlertStdout { rule, value };
+        notify_all(notifiers, &alert.notification()).await;
+        logging::stdoutln(alert)?;
+    }
+    Ok(())
+}
+
+/// Current value of the rule metric, if the item is sold at the markets.
+async fn rule_value(
+    eve: &DataIntegrator,
+    rule: &WatchRule,
+    facilities: &[Facility],
+    orders: &[RegionOrders],
+    character: &Character,
+    prices: &Prices,
+) -> Result<Option<f64>, EveError> {
+    let market_orders = |market_id: i64| {
+        facilities
+            .iter()
+            .find(|facility| facility.id() == market_id)
+            .and_then(|facility| {
+                orders.iter().find(|orders| {
+                    orders.region.id() == facility.location.constellation.region.id()
+                })
+            })
+    };
+    match rule.metric {
+        WatchMetric::ProfitPerHour => {
+            profits_per_hour(eve, rule.item_id, facilities, orders, character, prices).await
+        }
+        WatchMetric::SellPrice { market_id } => Ok(market_orders(market_id)
+            .map(|orders| orders.lowest_station_sell_price(rule.item_id, market_id))
+            // A zero price means the station doesn't sell the item
+            .filter(|price| *price > 0.0)),
+        WatchMetric::Competitors { market_id } => Ok(market_orders(market_id)
+            .map(|orders| orders.station_sell_orders_count(rule.item_id, market_id) as f64)),
+    }
+}
+
+/// Highest profits per hour of the item over the registered manufacturing facilities, sold at the
+/// registered market with the highest price.
+async fn profits_per_hour(
+    eve: &DataIntegrator,
+    item_id: i32,
+    facilities: &[Facility],
+    orders: &[RegionOrders],
+    character: &Character,
+    prices: &Prices,
+) -> Result<Option<f64>, EveError> {
+    let mut highest_sell_price = None;
+    for facility in facilities
+        .iter()
+        .filter(|facility| facility.market().is_some())
+    {
+        let price = orders
+            .iter()
+            .find(|orders| orders.region.id() == facility.location.constellation.region.id())
+            .map(|orders| orders.lowest_station_sell_price(item_id, facility.id()))
+            .filter(|price| *price > 0.0);
+        if let Some(price) = price {
+            highest_sell_price =
+                Some(highest_sell_price.map_or(price, |highest: f64| highest.max(price)));
+        }
+    }
+    let sell_price = match highest_sell_price {
+        Some(sell_price) => sell_price,
+        None => return Ok(None),
+    };
+
+    let blueprint: Option<BlueprintManufacturing> = eve
+        .load_item_blueprints(item_id, IndustryType::Manufacturing)
+        .await?
+        .into_iter()
+        .find_map(|blueprint| blueprint.activities.manufacturing);
+    let blueprint = match blueprint {
+        Some(blueprint) => blueprint,
+        None => return Ok(None),
+    };
+    let output_quantity = blueprint
+        .get_product(item_id)
+        .map(|product| product.quantity)
+        .unwrap_or(1);
+
+    let invention_facilities: Vec<_> = facilities
+        .iter()
+        .filter_map(|facility| facility.invention())
+        .collect();
+    let mut best = None;
+    for manufacture in facilities
+        .iter()
+        .filter_map(|facility| facility.manufacture())
+    {
+        let cost_per_run = manufacture
+            .manufacture_cost_per_run(
+                &blueprint,
+                &character.skills,
+                &character.implants,
+                &invention_facilities,
+                None,
+                prices,
+            )
+            .await?
+            .value;
+        let time_per_run = manufacture.time_per_run(
+            &blueprint,
+            &character.skills.get_manufacturing_skill(),
+            &character.implants,
+        );
+        if time_per_run <= 0 {
+            continue;
+        }
+        let time_per_unit = time_per_run as f64 / output_quantity as f64;
+        let margin = sell_price - cost_per_run / (output_quantity as f64);
+        let profits_per_hour = margin * (3600.0 / time_per_unit);
+        best = Some(best.map_or(profits_per_hour, |best: f64| best.max(profits_per_hour)));
+    }
+    Ok(best)
+}
+
+#[derive(Serialize)]
+pub struct AlertStdout {
+    rule: RuleStdout,
+    value: f64,
+}
+
+impl AlertStdout {
+    fn notification(&self) -> Notification {
+        Notification {
+            title: format!("eve-vulcain: {}", self.rule.item_name),
+            message: format!(
+                "{} (current: {})",
+                self.rule.condition(),
+                self.value.to_display()
+            ),
+        }
+    }
+}
+
+impl Stdout for AlertStdout {}
+
+impl Message for AlertStdout {
+    fn standard(&self, _: Verbosity) -> ColoredString {
+        ColoredString::from(
+            format!(
+                "{} {:<50}{} (current: {})",
+                "ALERT:".yellow().bold(),
+                self.rule.item_name,
+                self.rule.condition(),
+                self.value.to_display()
+            )
+            .as_str(),
+        )
+    }
+}
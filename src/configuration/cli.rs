@@ -65,6 +65,24 @@ pub enum Commands {
     Manufacture(ManufacturingOptions),
     /// Compute costs linked to invention of tech 2 blueprints and items
     Invent(InventionOptions),
+    /// Compute the time and costs to research the material and time efficiency of a blueprint
+    Research(ResearchOptions),
+    /// Plan the jobs of the manufacturing slots maximizing the profits over the coming days
+    Schedule(ScheduleOptions),
+    /// Choose the items and quantities to manufacture maximizing the profits of the available ISK
+    Optimize(OptimizeOptions),
+    /// Compare the estimated installation costs of running jobs to the costs actually charged
+    Calibrate(CalibrateOptions),
+    /// Show how the profits of manufactured items moved between the 'manufacture all' runs
+    History(HistoryOptions),
+    /// Watch the profits, prices and competition of items, and alert when they cross thresholds
+    Watch(WatchOptions),
+    /// Follow the industry jobs of the character
+    Jobs(JobsOptions),
+    /// Check the market orders of the character against the competition
+    Orders(OrdersOptions),
+    /// Compare the industry skills worth training next
+    Skills(SkillsOptions),
     /// Manage registered markets and industry facilities
     Facility(FacilityOptions),
     /// Manage registered items
@@ -83,6 +101,39 @@ pub struct UpdateOptions {
     /// Prevent the download of a new SDE archive.
     #[arg(long)]
     pub no_download: bool,
+    /// Install the SDE from an already downloaded archive.
+    #[arg(long, value_name = "ZIP")]
+    pub from_file: Option<PathBuf>,
+}
+
+#[derive(clap::Args, Debug, PartialEq, Clone)]
+pub struct SkillsOptions {
+    /// Generate command result and details as JSON output on stdout.
+    #[arg(global = true, long)]
+    pub json: bool,
+    #[command(subcommand)]
+    pub command: SkillsCommands,
+}
+
+#[derive(Subcommand, Debug, PartialEq, Clone)]
+pub enum SkillsCommands {
+    /// Compute the profits per hour gained by training each relevant industry skill one more level, against the time to train it
+    Roi(SkillsRoiOptions),
+    /// Save the skills of the current character as a skill profile, usable with '--skills'
+    Export(SkillsExportOptions),
+}
+
+#[derive(clap::Args, Debug, PartialEq, Clone)]
+pub struct SkillsRoiOptions {
+    #[command(flatten)]
+    pub skill_profile: SkillProfileOptions,
+}
+
+#[derive(clap::Args, Debug, PartialEq, Clone)]
+pub struct SkillsExportOptions {
+    /// Name of the skill profile to write. An existing profile with the same name is replaced.
+    #[clap(index = 1)]
+    pub name: String,
 }
 
 #[derive(clap::Args, Debug, PartialEq, Clone)]
@@ -98,6 +149,22 @@ pub struct InventionOptions {
 pub enum InventionCommands {
     /// Compute invention cost related to a specific tech 2 item
     Item(InventionItemOptions),
+    /// Compute the invention costs and manufacturing profits of all registered tech 2 and tech 3 items and sort them (ISK/h)
+    All(InventionAllOptions),
+}
+
+#[derive(clap::Args, Debug, PartialEq, Clone)]
+pub struct InventionAllOptions {
+    /// Experimental. Instead of computing registered items, this will load all inventable items available in Eve Online. Takes a long time.
+    #[arg(long)]
+    pub everything: bool,
+    /// Runs of each manufacturing job the profits are computed on. Materials are rounded for the whole job, like in game. Bounded by the blueprint production limit, and by the runs of invented copies.
+    #[arg(long, default_value = "1", value_parser = clap::value_parser!(i32).range(1..))]
+    pub runs: i32,
+    #[command(flatten)]
+    pub copying: CopyingOptions,
+    #[command(flatten)]
+    pub skill_profile: SkillProfileOptions,
 }
 
 #[derive(clap::Args, Debug, PartialEq, Clone)]
@@ -108,6 +175,270 @@ pub struct InventionItemOptions {
     /// If specified, the item name will be searched for exact match
     #[arg(long)]
     pub strict: bool,
+    /// ID of the blueprint to invent from, when several invention sources exist. Defaults to the cheapest one.
+    #[arg(long)]
+    pub source: Option<i32>,
+    #[command(flatten)]
+    pub copying: CopyingOptions,
+    #[command(flatten)]
+    pub skill_profile: SkillProfileOptions,
+}
+
+#[derive(clap::Args, Debug, PartialEq, Clone)]
+pub struct SkillProfileOptions {
+    /// Name of a skill profile replacing the character skills (see 'skills export').
+    #[arg(long, global = true)]
+    pub skills: Option<String>,
+}
+
+#[derive(clap::Args, Debug, PartialEq, Clone)]
+pub struct CopyingOptions {
+    /// Name of the registered facility copying the blueprints consumed by invention. The copy cost and time are added to each invention attempt. Defaults to the first registered facility that can both invent and copy, then to the first one that can copy.
+    #[arg(long, global = true)]
+    pub copy_facility: Option<String>,
+}
+
+#[derive(clap::Args, Debug, PartialEq, Clone)]
+pub struct ResearchOptions {
+    /// Generate command result and details as JSON output on stdout.
+    #[arg(long)]
+    pub json: bool,
+    /// Partial or full name of an item (not a blueprint!) whose blueprint is researched
+    #[clap(index = 1)]
+    pub item: String,
+    /// If specified, the item name will be searched for exact match
+    #[arg(long)]
+    pub strict: bool,
+    /// Current material efficiency of the blueprint
+    #[arg(long, default_value = "0", value_parser = clap::value_parser!(u8).range(0..=10))]
+    pub material_efficiency: u8,
+    /// Material efficiency to research up to (Max: 10)
+    #[arg(long, default_value = "10", value_parser = clap::value_parser!(u8).range(0..=10))]
+    pub target_material_efficiency: u8,
+    /// Current time efficiency of the blueprint
+    #[arg(long, default_value = "0", value_parser = clap::value_parser!(u8).range(0..=20))]
+    pub time_efficiency: u8,
+    /// Time efficiency to research up to, by steps of 2 (Max: 20)
+    #[arg(long, default_value = "20", value_parser = clap::value_parser!(u8).range(0..=20))]
+    pub target_time_efficiency: u8,
+    /// Runs of the manufacturing jobs the material savings are computed on. Materials are rounded for the whole job, like in game. Defaults to the blueprint production limit.
+    #[arg(long, value_parser = clap::value_parser!(i32).range(1..))]
+    pub runs: Option<i32>,
+}
+
+#[derive(clap::Args, Debug, PartialEq, Clone)]
+pub struct ScheduleOptions {
+    /// Generate command result and details as JSON output on stdout.
+    #[arg(long)]
+    pub json: bool,
+    /// Number of days to plan. The runs of each item are limited to what its market sells over this period.
+    #[arg(long, default_value = "1", value_parser = clap::value_parser!(i64).range(1..))]
+    pub days: i64,
+    /// Number of manufacturing slots to plan. Defaults to the slots allowed by the character skills.
+    #[arg(long)]
+    pub slots: Option<i32>,
+    /// Force a specific blueprint material efficiency to compute the manufacturing cost of items (Max: 10)
+    #[arg(long, default_value = "0")]
+    pub material_efficiency: u8,
+    /// Force a specific blueprint time efficiency to compute the manufacturing time of items (Max: 20)
+    #[arg(long, default_value = "0")]
+    pub time_efficiency: u8,
+    #[command(flatten)]
+    pub copying: CopyingOptions,
+    #[command(flatten)]
+    pub skill_profile: SkillProfileOptions,
+}
+
+#[derive(clap::Args, Debug, PartialEq, Clone)]
+pub struct OptimizeOptions {
+    /// Generate command result and details as JSON output on stdout.
+    #[arg(long)]
+    pub json: bool,
+    /// ISK available to buy materials and install jobs. Defaults to the character wallet balance.
+    #[arg(long)]
+    pub budget: Option<f64>,
+    /// Number of days to plan. The runs of each item are limited to what its market sells and to the manufacturing slot time over this period.
+    #[arg(long, default_value = "7", value_parser = clap::value_parser!(i64).range(1..))]
+    pub days: i64,
+    /// Number of manufacturing slots available. Defaults to the slots allowed by the character skills.
+    #[arg(long)]
+    pub slots: Option<i32>,
+    /// Force a specific blueprint material efficiency to compute the manufacturing cost of items (Max: 10)
+    #[arg(long, default_value = "0")]
+    pub material_efficiency: u8,
+    /// Force a specific blueprint time efficiency to compute the manufacturing time of items (Max: 20)
+    #[arg(long, default_value = "0")]
+    pub time_efficiency: u8,
+    #[command(flatten)]
+    pub copying: CopyingOptions,
+    #[command(flatten)]
+    pub skill_profile: SkillProfileOptions,
+}
+
+#[derive(clap::Args, Debug, PartialEq, Clone)]
+pub struct CalibrateOptions {
+    /// Generate command result and details as JSON output on stdout.
+    #[arg(long)]
+    pub json: bool,
+    /// Deviation between estimated and actual costs accepted before flagging a facility, in percent. Cost indexes change daily, so estimates are rarely exact.
+    #[arg(long, default_value = "5")]
+    pub tolerance: f64,
+}
+
+#[derive(clap::Args, Debug, PartialEq, Clone)]
+pub struct HistoryOptions {
+    /// Generate command result and details as JSON output on stdout.
+    #[arg(global = true, long)]
+    pub json: bool,
+    #[command(subcommand)]
+    pub command: HistoryCommands,
+}
+
+#[derive(Subcommand, Debug, PartialEq, Clone)]
+pub enum HistoryCommands {
+    /// Show the profits per hour, sell price and material cost of an item over the saved runs
+    Item(HistoryItemOptions),
+    /// Compare the ranking of two saved runs and show which factor moved each item
+    Diff(HistoryDiffOptions),
+}
+
+#[derive(clap::Args, Debug, PartialEq, Clone)]
+pub struct HistoryItemOptions {
+    /// Partial or full name of the item, as saved in the runs
+    #[clap(index = 1)]
+    pub item: String,
+    /// Number of days of history to show.
+    #[arg(long, default_value = "30", value_parser = clap::value_parser!(i64).range(1..))]
+    pub days: i64,
+}
+
+#[derive(clap::Args, Debug, PartialEq, Clone)]
+pub struct HistoryDiffOptions {
+    /// Run to compare from, counted back from the latest run (0 being the latest run).
+    #[arg(long, default_value = "1")]
+    pub from: usize,
+    /// Run to compare to, counted back from the latest run (0 being the latest run).
+    #[arg(long, default_value = "0")]
+    pub to: usize,
+}
+
+#[derive(clap::Args, Debug, PartialEq, Clone)]
+pub struct WatchOptions {
+    /// Generate command result and details as JSON output on stdout.
+    #[arg(global = true, long)]
+    pub json: bool,
+    #[command(subcommand)]
+    pub command: WatchCommands,
+}
+
+#[derive(Subcommand, Debug, PartialEq, Clone)]
+pub enum WatchCommands {
+    /// Add a rule watching an item
+    #[command(subcommand)]
+    Add(WatchAddCommands),
+    /// List all watch rules
+    Ls,
+    /// Remove a watch rule
+    Rm,
+    /// Check the watch rules periodically, and alert when a rule starts being triggered
+    Run(WatchRunOptions),
+}
+
+#[derive(Subcommand, Debug, PartialEq, Clone)]
+pub enum WatchAddCommands {
+    /// Watch the highest profits per hour of the item over the registered facilities and markets
+    Profit(WatchThresholdOptions),
+    /// Watch the lowest sell price of the item at a registered market
+    SellPrice(WatchMarketOptions),
+    /// Watch the number of sell orders of the item at a registered market
+    Competitors(WatchMarketOptions),
+}
+
+#[derive(clap::Args, Debug, PartialEq, Clone)]
+pub struct WatchThresholdOptions {
+    /// Partial or full name of the item to watch
+    #[clap(index = 1)]
+    pub item: String,
+    /// If specified, the item name will be searched for exact match
+    #[arg(long)]
+    pub strict: bool,
+    /// Alert when the value goes above this threshold.
+    #[arg(long, conflicts_with = "below", required_unless_present = "below")]
+    pub above: Option<f64>,
+    /// Alert when the value goes below this threshold.
+    #[arg(long)]
+    pub below: Option<f64>,
+}
+
+#[derive(clap::Args, Debug, PartialEq, Clone)]
+pub struct WatchMarketOptions {
+    #[command(flatten)]
+    pub threshold: WatchThresholdOptions,
+    /// Partial or full name of the registered market.
+    #[arg(long)]
+    pub market: String,
+}
+
+#[derive(clap::Args, Debug, PartialEq, Clone)]
+pub struct WatchRunOptions {
+    /// Minutes between two checks of the rules.
+    #[arg(long, default_value = "30", value_parser = clap::value_parser!(u64).range(1..))]
+    pub interval: u64,
+    /// Check the rules a single time, then exit.
+    #[arg(long)]
+    pub once: bool,
+    /// Command run for each alert, with the alert title and message as last arguments (such as 'notify-send'). Replaces the configured command.
+    #[arg(long)]
+    pub notify_command: Option<String>,
+    /// URL receiving each alert as a JSON POST request. Replaces the configured URL.
+    #[arg(long)]
+    pub webhook_url: Option<String>,
+}
+
+#[derive(clap::Args, Debug, PartialEq, Clone)]
+pub struct JobsOptions {
+    /// Generate command result and details as JSON output on stdout.
+    #[arg(global = true, long)]
+    pub json: bool,
+    #[command(subcommand)]
+    pub command: JobsCommands,
+}
+
+#[derive(Subcommand, Debug, PartialEq, Clone)]
+pub enum JobsCommands {
+    /// Poll the industry jobs periodically, and notify when a job is started, ready or delivered
+    Watch(JobsWatchOptions),
+}
+
+#[derive(clap::Args, Debug, PartialEq, Clone)]
+pub struct JobsWatchOptions {
+    /// Minutes between two polls of the jobs. The Eve API refreshes the jobs every 5 minutes.
+    #[arg(long, default_value = "5", value_parser = clap::value_parser!(u64).range(5..))]
+    pub interval: u64,
+    /// Command run for each job transition, with the notification title and message as last arguments (such as 'notify-send'). Replaces the configured command.
+    #[arg(long)]
+    pub notify_command: Option<String>,
+    /// URL receiving each job transition as a JSON POST request. Replaces the configured URL.
+    #[arg(long)]
+    pub webhook_url: Option<String>,
+    /// File each job transition is appended to. Replaces the configured file.
+    #[arg(long)]
+    pub log_file: Option<PathBuf>,
+}
+
+#[derive(clap::Args, Debug, PartialEq, Clone)]
+pub struct OrdersOptions {
+    /// Generate command result and details as JSON output on stdout.
+    #[arg(global = true, long)]
+    pub json: bool,
+    #[command(subcommand)]
+    pub command: OrdersCommands,
+}
+
+#[derive(Subcommand, Debug, PartialEq, Clone)]
+pub enum OrdersCommands {
+    /// Flag the orders undercut or outbid at their location, and suggest a new price
+    Check,
 }
 
 #[derive(clap::Args, Debug, PartialEq, Clone)]
@@ -123,6 +454,13 @@ pub struct ManufacturingOptions {
     /// Force a specific blueprint time efficiency to compute the manufacturing cost of an item (Max: 20)
     #[arg(long, global = true, default_value = "0")]
     pub time_efficiency: u8,
+    /// Runs of each manufacturing job. Materials are rounded for the whole job, like in game. Bounded by the blueprint production limit, and by the runs of invented copies.
+    #[arg(long, global = true, default_value = "1", value_parser = clap::value_parser!(i32).range(1..))]
+    pub runs: i32,
+    #[command(flatten)]
+    pub copying: CopyingOptions,
+    #[command(flatten)]
+    pub skill_profile: SkillProfileOptions,
 }
 
 #[derive(Subcommand, Debug, PartialEq, Clone)]
@@ -138,6 +476,9 @@ pub struct ManufactureAllOptions {
     /// Experimental. Instead of computing registered items, this will load all manufacturable items available in Eve Online. Takes a long time.
     #[arg(long)]
     pub everything: bool,
+    /// Hide items the character doesn't have the skills to manufacture.
+    #[arg(long)]
+    pub buildable_only: bool,
 }
 
 #[derive(clap::Args, Debug, PartialEq, Clone)]
@@ -148,6 +489,12 @@ pub struct MultipleItemsOptions {
     /// If specified, the item name will be searched for exact match
     #[arg(long)]
     pub strict: bool,
+    /// ID of the blueprint to manufacture from, when several blueprints produce the item. Defaults to the cheapest one.
+    #[arg(long)]
+    pub blueprint: Option<i32>,
+    /// ID of the blueprint to invent from, when several invention sources exist. Defaults to the cheapest one.
+    #[arg(long)]
+    pub invention_source: Option<i32>,
 }
 
 #[derive(clap::Args, Debug, PartialEq, Clone)]
@@ -290,4 +637,53 @@ impl<T: Configuration> Configuration for CLIConfiguration<T> {
     fn spec_url(&self) -> Result<Option<String>, ConfigurationError> {
         self.default.spec_url()
     }
+
+    fn notification_command(&self) -> Result<Option<String>, ConfigurationError> {
+        let command = match &self.args.command {
+            Commands::Watch(WatchOptions {
+                command: WatchCommands::Run(opts),
+                ..
+            }) => opts.notify_command.clone(),
+            Commands::Jobs(JobsOptions {
+                command: JobsCommands::Watch(opts),
+                ..
+            }) => opts.notify_command.clone(),
+            _ => None,
+        };
+        if let Some(command) = command {
+            return Ok(Some(command));
+        }
+        self.default.notification_command()
+    }
+
+    fn notification_webhook_url(&self) -> Result<Option<String>, ConfigurationError> {
+        let url = match &self.args.command {
+            Commands::Watch(WatchOptions {
+                command: WatchCommands::Run(opts),
+                ..
+            }) => opts.webhook_url.clone(),
+            Commands::Jobs(JobsOptions {
+                command: JobsCommands::Watch(opts),
+                ..
+            }) => opts.webhook_url.clone(),
+            _ => None,
+        };
+        if let Some(url) = url {
+            return Ok(Some(url));
+        }
+        self.default.notification_webhook_url()
+    }
+
+    fn notification_log_file(&self) -> Result<Option<PathBuf>, ConfigurationError> {
+        if let Commands::Jobs(JobsOptions {
+            command: JobsCommands::Watch(opts),
+            ..
+        }) = &self.args.command
+        {
+            if let Some(path) = opts.log_file.clone() {
+                return Ok(Some(path));
+            }
+        }
+        self.default.notification_log_file()
+    }
 }
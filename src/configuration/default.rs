@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use crate::authentication::RefreshToken;
 use crate::configuration::{Configuration, ConfigurationError};
 
@@ -35,4 +37,16 @@ impl Configuration for DefaultConfiguration {
     fn spec_url(&self) -> Result<Option<String>, ConfigurationError> {
         Ok(None)
     }
+
+    fn notification_command(&self) -> Result<Option<String>, ConfigurationError> {
+        Ok(None)
+    }
+
+    fn notification_webhook_url(&self) -> Result<Option<String>, ConfigurationError> {
+        Ok(None)
+    }
+
+    fn notification_log_file(&self) -> Result<Option<PathBuf>, ConfigurationError> {
+        Ok(None)
+    }
 }
@@ -139,6 +139,27 @@ impl<T: Configuration> Configuration for EnvironmentVariablesConfiguration<T> {
         }
         self.default.spec_url()
     }
+
+    fn notification_command(&self) -> Result<Option<String>, ConfigurationError> {
+        if let Some(command) = var(&with_prefix("NOTIFICATION_COMMAND"))? {
+            return Ok(Some(command));
+        }
+        self.default.notification_command()
+    }
+
+    fn notification_webhook_url(&self) -> Result<Option<String>, ConfigurationError> {
+        if let Some(url) = var(&with_prefix("NOTIFICATION_WEBHOOK_URL"))? {
+            return Ok(Some(url));
+        }
+        self.default.notification_webhook_url()
+    }
+
+    fn notification_log_file(&self) -> Result<Option<PathBuf>, ConfigurationError> {
+        if let Some(path) = var(&with_prefix("NOTIFICATION_LOG_FILE"))? {
+            return Ok(Some(PathBuf::from(path)));
+        }
+        self.default.notification_log_file()
+    }
 }
 
 #[cfg(test)]
@@ -112,6 +112,39 @@ impl<T: Configuration> Configuration for FSConfiguration<T> {
     fn spec_url(&self) -> Result<Option<String>, ConfigurationError> {
         self.default.spec_url()
     }
+
+    fn notification_command(&self) -> Result<Option<String>, ConfigurationError> {
+        if let Some(cfg) = &self.cfg {
+            if let Some(notifications) = &cfg.notifications {
+                if let Some(command) = &notifications.command {
+                    return Ok(Some(command.to_string()));
+                }
+            }
+        }
+        self.default.notification_command()
+    }
+
+    fn notification_webhook_url(&self) -> Result<Option<String>, ConfigurationError> {
+        if let Some(cfg) = &self.cfg {
+            if let Some(notifications) = &cfg.notifications {
+                if let Some(url) = &notifications.webhook_url {
+                    return Ok(Some(url.to_string()));
+                }
+            }
+        }
+        self.default.notification_webhook_url()
+    }
+
+    fn notification_log_file(&self) -> Result<Option<PathBuf>, ConfigurationError> {
+        if let Some(cfg) = &self.cfg {
+            if let Some(notifications) = &cfg.notifications {
+                if let Some(path) = &notifications.log_file {
+                    return Ok(Some(path.clone()));
+                }
+            }
+        }
+        self.default.notification_log_file()
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -124,6 +157,7 @@ pub struct MainConfiguration {
     pub authentication_server: Option<AuthenticationServerConfiguration>,
     pub api: Option<APIConfiguration>,
     pub facilities: Option<FacilitiesConfiguration>,
+    pub notifications: Option<NotificationsConfiguration>,
 }
 
 #[derive(Debug, Error)]
@@ -182,6 +216,16 @@ pub struct APIConfiguration {
     pub callback_url: Option<String>,
 }
 
+#[derive(Deserialize, Serialize)]
+pub struct NotificationsConfiguration {
+    /// Command run with the title and message of each notification as last arguments.
+    pub command: Option<String>,
+    /// URL receiving each notification as a JSON POST request.
+    pub webhook_url: Option<String>,
+    /// File each notification is appended to, as a line.
+    pub log_file: Option<PathBuf>,
+}
+
 #[derive(Deserialize, Serialize)]
 pub struct AuthenticationServerConfiguration {
     pub port: Option<u16>,
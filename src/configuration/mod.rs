@@ -133,4 +133,10 @@ pub trait Configuration {
     fn token_url(&self) -> Result<Option<String>, ConfigurationError>;
 
     fn spec_url(&self) -> Result<Option<String>, ConfigurationError>;
+
+    fn notification_command(&self) -> Result<Option<String>, ConfigurationError>;
+
+    fn notification_webhook_url(&self) -> Result<Option<String>, ConfigurationError>;
+
+    fn notification_log_file(&self) -> Result<Option<PathBuf>, ConfigurationError>;
 }
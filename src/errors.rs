@@ -8,7 +8,7 @@ use thiserror::Error;
 use url::Url;
 use zip::result::ZipError;
 
-use crate::api::evecache::cache::CacheError;
+use crate::api::sde::SDEErrors;
 use crate::authentication::tokens::TokenError;
 use crate::cache::{FSCacheReadError, FSCacheWriteError};
 use crate::configuration::{
@@ -21,6 +21,7 @@ use crate::integration::{
     DataLoadError, FacilityLoadingError, ItemLoadingError, SystemLoadingError,
 };
 use crate::logging::StdoutError;
+use crate::model::facility::copying::CopyingFacilityNotFound;
 use crate::model::facility::manufacture::ManufactureError;
 use crate::model::facility::markets::{MarketError, VolumesError};
 use crate::model::facility::IdentifierTypeConversionFailed;
@@ -73,6 +74,8 @@ pub enum EveError {
     VolumesError(#[from] VolumesError),
     #[error(transparent)]
     MarketError(#[from] MarketError),
+    #[error(transparent)]
+    CopyingFacilityNotFound(#[from] CopyingFacilityNotFound),
 }
 
 impl Advice for EveError {
@@ -148,6 +151,16 @@ pub enum EveApiError {
     },
     #[error("Could not split larde SDE file into subfiles: {source}")]
     SDESplitFile { source: filesystem::SplitError },
+    #[error("Could not build SDE index: {source}")]
+    SDEIndexBuildFailed { source: SDEErrors },
+    #[error("Could not download SDE checksum: {source}")]
+    SDEChecksumDownloadFailed { source: reqwest::Error },
+    #[error("Published SDE checksum is empty")]
+    SDEChecksumInvalid,
+    #[error("Could not read SDE checksum from '{path}': {source}")]
+    SDEChecksumReadFailed { path: String, source: io::Error },
+    #[error("Could not write SDE checksum to '{path}': {source}")]
+    SDEChecksumWriteFailed { path: String, source: io::Error },
     #[error("Could not initialize ESI builder: {source}")]
     ESIBuilderInitError { source: ConfigurationError },
     #[error("Could not initialize ESI: {source}")]
@@ -230,8 +243,6 @@ pub enum ModelError {
     LoadingFacilities {
         source: crate::integration::FacilityLoadingError,
     },
-    #[error("Could not load blueprints: {source}")]
-    LoadBlueprints { source: CacheError },
     #[error(transparent)]
     LoadingBlueprint {
         source: crate::integration::DataLoadError,
@@ -248,10 +259,14 @@ pub enum ModelError {
     RemovingPlayerStructure { source: filesystem::FSError },
     #[error("Could not remove item: {source}")]
     RemovingItem { source: filesystem::FSError },
-    #[error("Too much blueprints found for {name} ({type_id})")]
-    TooMuchBlueprint { name: String, type_id: i32 },
+    #[error("No blueprint matching the selected production path for {name} ({type_id})")]
+    ProductionPathNotFound { name: String, type_id: i32 },
     #[error("the blueprint doesn't have any invention info: {blueprint_id}")]
     IsNotAnInventionBlueprint { blueprint_id: i32 },
+    #[error("The blueprint of {name} ({type_id}) can't be researched")]
+    BlueprintNotResearchable { name: String, type_id: i32 },
+    #[error("Research target {target} is lower than the current efficiency {current}")]
+    InvalidResearchTarget { current: u8, target: u8 },
     #[error("Loading skill '{skill_id}': {source}")]
     LoadSkill {
         skill_id: i32,
@@ -265,4 +280,36 @@ pub enum ModelError {
     LoadingCharacterOrders {
         source: crate::integration::DataLoadError,
     },
+    #[error(transparent)]
+    LoadingCharacterAttributes {
+        source: crate::integration::DataLoadError,
+    },
+    #[error(transparent)]
+    SavingSkillProfile {
+        source: crate::filesystem::FSWriteError,
+    },
+    #[error("Could not save manufacturing history: {source}")]
+    SavingHistorySnapshot {
+        source: crate::filesystem::FSWriteError,
+    },
+    #[error("Could not load manufacturing history: {source}")]
+    LoadingHistory {
+        source: crate::filesystem::FSReadError,
+    },
+    #[error("No saved run found for item '{search}'")]
+    HistoryItemNotFound { search: String },
+    #[error("Several items match '{search}': {names:?}")]
+    AmbiguousHistoryItem { search: String, names: Vec<String> },
+    #[error("Run {index} not found, {runs} run(s) saved")]
+    HistoryRunNotFound { index: usize, runs: usize },
+    #[error("No registered facility named '{name}' is a market")]
+    MarketNotFound { name: String },
+    #[error("Error saving watch rule: {source}")]
+    SaveWatchRuleError { source: crate::filesystem::FSError },
+    #[error("Could not remove watch rule: {source}")]
+    RemovingWatchRule { source: filesystem::FSError },
+    #[error("Could not load watch rules: {source}")]
+    LoadingWatchRules {
+        source: crate::filesystem::FSReadError,
+    },
 }
@@ -1,12 +1,14 @@
 use std::collections::HashMap;
+use std::fs::{File, OpenOptions, TryLockError};
 use std::io;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_yaml::Value;
 use thiserror::Error;
-use tokio::io::AsyncBufReadExt;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt};
 use tokio::sync::RwLock;
 
 use crate::authentication::RefreshToken;
@@ -15,7 +17,9 @@ use crate::display::Display;
 use crate::logging;
 use crate::model::facility::playerstructure::PlayerStructureStats;
 use crate::model::facility::FacilityUsage;
+use crate::model::history::HistorySnapshot;
 use crate::model::industry::IndustryType;
+use crate::model::watch::WatchRule;
 
 #[derive(Error, Debug)]
 #[error("Directory could not be created (Path: '{path})': {source}")]
@@ -38,6 +42,70 @@ pub async fn create_directory(dir_to_create: &PathBuf) -> Result<(), DirectoryCr
     Ok(())
 }
 
+static LOCK_FILE_NAME: &str = ".eve-vulcain.lock";
+
+/// Advisory lock on a directory, held until dropped. Another eve-vulcain process trying to lock the
+/// same directory waits for its release.
+pub struct DirectoryLock {
+    _file: File,
+}
+
+impl DirectoryLock {
+    pub async fn acquire(directory: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(directory.join(LOCK_FILE_NAME))?;
+        let file = match file.try_lock() {
+            Ok(()) => file,
+            Err(TryLockError::WouldBlock) => {
+                logging::debug!(
+                    "Waiting for another eve-vulcain process to release '{}'",
+                    directory.to_display()
+                );
+                tokio::task::spawn_blocking(move || file.lock().map(|()| file))
+                    .await
+                    .map_err(io::Error::other)??
+            }
+            Err(TryLockError::Error(source)) => return Err(source),
+        };
+        Ok(Self { _file: file })
+    }
+}
+
+static TEMPORARY_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+fn temporary_path(path: &Path) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    path.with_file_name(format!(
+        ".{}.{}-{}.tmp",
+        file_name,
+        std::process::id(),
+        TEMPORARY_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ))
+}
+
+/// Writes the content into a temporary file next to `path` and renames it over `path`, so an
+/// interrupted write never leaves a truncated file behind and readers always see a complete file.
+pub async fn write_atomically_async(path: &Path, content: impl AsRef<[u8]>) -> io::Result<()> {
+    let temporary_path = temporary_path(path);
+    let result = async {
+        let mut file = tokio::fs::File::create(&temporary_path).await?;
+        file.write_all(content.as_ref()).await?;
+        file.sync_all().await?;
+        tokio::fs::rename(&temporary_path, path).await
+    }
+    .await;
+    if result.is_err() {
+        let _ = tokio::fs::remove_file(&temporary_path).await;
+    }
+    result
+}
+
 #[derive(Error, Debug)]
 pub enum FSError {
     #[error(transparent)]
@@ -69,6 +137,13 @@ pub enum FSReadError {
         path: String,
         source: serde_json::Error,
     },
+    #[error("deserialize toml content of '{path}': {source}")]
+    TOMLDeserialization {
+        path: String,
+        source: Box<toml::de::Error>,
+    },
+    #[error("invalid skill profile name '{name}'")]
+    InvalidSkillProfileName { name: String },
 }
 
 #[derive(Error, Debug)]
@@ -83,6 +158,13 @@ pub enum FSWriteError {
         path: String,
         source: serde_json::Error,
     },
+    #[error("lock directory '{path}': {source}")]
+    LockDirectory {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("invalid skill profile name '{name}'")]
+    InvalidSkillProfileName { name: String },
 }
 
 #[derive(Error, Debug)]
@@ -107,12 +189,24 @@ impl FSData {
             facilities_cache: RwLock::new(None),
         }
     }
+
+    /// Locks the data directory for a whole load, update and save of a data file, so concurrent
+    /// eve-vulcain processes don't lose each other's updates.
+    async fn lock(&self) -> Result<DirectoryLock, FSWriteError> {
+        DirectoryLock::acquire(&self.data_directory)
+            .await
+            .map_err(|source| FSWriteError::LockDirectory {
+                path: self.data_directory.to_display(),
+                source,
+            })
+    }
 }
 
 // Facilities
 impl FSData {
     pub async fn add_station(&self, to_insert: &NPCStation) -> Result<(), FSError> {
-        let mut facilities = self.load_facilities().await?;
+        let _lock = self.lock().await?;
+        let mut facilities = self.reload_facilities().await?;
         for station in &facilities.stations {
             if station.id == to_insert.id {
                 logging::info!("Duplicate found for {:?}", station);
@@ -125,7 +219,8 @@ impl FSData {
     }
 
     pub async fn rm_station(&self, type_id: i32) -> Result<(), FSError> {
-        let mut facilities = self.load_facilities().await?;
+        let _lock = self.lock().await?;
+        let mut facilities = self.reload_facilities().await?;
 
         let index = facilities.stations.iter().position(|t| t.id == type_id);
         if let Some(i) = index {
@@ -138,7 +233,8 @@ impl FSData {
 
     pub async fn add_structure(&self, to_insert: &PlayerStructure) -> Result<(), FSError> {
         logging::info!("Saving structure: {:?}", to_insert);
-        let mut facilities = self.load_facilities().await?;
+        let _lock = self.lock().await?;
+        let mut facilities = self.reload_facilities().await?;
         for structure in &facilities.structures {
             if structure.id == to_insert.id {
                 logging::info!("Duplicate found for {:?}", structure);
@@ -151,7 +247,8 @@ impl FSData {
     }
 
     pub async fn rm_structure(&self, type_id: i64) -> Result<(), FSError> {
-        let mut facilities = self.load_facilities().await?;
+        let _lock = self.lock().await?;
+        let mut facilities = self.reload_facilities().await?;
 
         let index = facilities.structures.iter().position(|t| t.id == type_id);
         if let Some(i) = index {
@@ -223,6 +320,13 @@ impl FSData {
             .expect("Cache should be already filled here"))
     }
 
+    /// Loads the facilities from their file, another process may have changed them since they were
+    /// cached.
+    async fn reload_facilities(&self) -> Result<Facilities, FSReadError> {
+        *self.facilities_cache.write().await = None;
+        self.load_facilities().await
+    }
+
     async fn save_facilities(&self, facilities: &Facilities) -> Result<(), FSWriteError> {
         let mut cache = self.facilities_cache.write().await;
         *cache = Some(facilities.clone());
@@ -235,7 +339,7 @@ impl FSData {
                 source,
             }
         })?;
-        tokio::fs::write(&facilities_file, facilities_content)
+        write_atomically_async(&facilities_file, facilities_content)
             .await
             .map_err(|source| FSWriteError::WriteFileError {
                 path: facilities_file.to_display(),
@@ -249,6 +353,7 @@ impl FSData {
 // Items
 impl FSData {
     pub async fn add_item(&self, item_id: i32) -> Result<(), FSError> {
+        let _lock = self.lock().await?;
         let mut items = self.load_items().await?;
         if !items.items.contains(&item_id) {
             items.items.push(item_id);
@@ -258,6 +363,7 @@ impl FSData {
     }
 
     pub async fn rm_item(&self, item_id: i32) -> Result<(), FSError> {
+        let _lock = self.lock().await?;
         let mut items = self.load_items().await?;
 
         let index = items.items.iter().position(|id| id == &item_id);
@@ -299,7 +405,7 @@ impl FSData {
                 source,
             }
         })?;
-        tokio::fs::write(&items_file, items_content)
+        write_atomically_async(&items_file, items_content)
             .await
             .map_err(|source| FSWriteError::WriteFileError {
                 path: items_file.to_display(),
@@ -310,6 +416,242 @@ impl FSData {
     }
 }
 
+// Watch rules
+impl FSData {
+    pub async fn add_watch_rule(&self, rule: WatchRule) -> Result<(), FSError> {
+        let _lock = self.lock().await?;
+        let mut rules = self.load_watch_rules().await?;
+        if !rules.rules.contains(&rule) {
+            rules.rules.push(rule);
+            self.save_watch_rules(&rules).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn rm_watch_rule(&self, rule: &WatchRule) -> Result<(), FSError> {
+        let _lock = self.lock().await?;
+        let mut rules = self.load_watch_rules().await?;
+        rules.rules.retain(|registered| registered != rule);
+        self.save_watch_rules(&rules).await?;
+        Ok(())
+    }
+
+    pub async fn load_watch_rules(&self) -> Result<WatchRules, FSReadError> {
+        let rules_file = self.data_directory.join(WATCH_RULES_FILE);
+        if !rules_file.exists() {
+            return Ok(WatchRules { rules: vec![] });
+        }
+        let rules_content = tokio::fs::read_to_string(&rules_file)
+            .await
+            .map_err(|source| FSReadError::ReadFile {
+                path: rules_file.to_display(),
+                source,
+            })?;
+        let rules: WatchRules = serde_json::from_str(&rules_content).map_err(|source| {
+            FSReadError::JSONDeserialization {
+                path: rules_file.to_display(),
+                source,
+            }
+        })?;
+        Ok(rules)
+    }
+
+    async fn save_watch_rules(&self, rules: &WatchRules) -> Result<(), FSWriteError> {
+        let rules_file = self.data_directory.join(WATCH_RULES_FILE);
+        let rules_content = serde_json::to_string_pretty(&rules).map_err(|source| {
+            FSWriteError::JSONSeserializationError {
+                path: rules_file.to_display(),
+                source,
+            }
+        })?;
+        write_atomically_async(&rules_file, rules_content)
+            .await
+            .map_err(|source| FSWriteError::WriteFileError {
+                path: rules_file.to_display(),
+                source,
+            })?;
+        logging::info!("File written: {}", &rules_file.to_display());
+        Ok(())
+    }
+}
+
+// Skill profiles
+impl FSData {
+    /// Loads the skill profile named `name`, written either in TOML (`<name>.toml`) or in JSON
+    /// (`<name>.json`) in the skill profiles directory.
+    pub async fn load_skill_profile(
+        &self,
+        name: &str,
+    ) -> Result<Option<SkillProfile>, FSReadError> {
+        if !is_valid_skill_profile_name(name) {
+            return Err(FSReadError::InvalidSkillProfileName {
+                name: name.to_string(),
+            });
+        }
+        let profiles_directory = self.data_directory.join(SKILL_PROFILES_DIRECTORY);
+
+        let toml_file = profiles_directory.join(format!("{name}.toml"));
+        if toml_file.exists() {
+            let content = tokio::fs::read_to_string(&toml_file)
+                .await
+                .map_err(|source| FSReadError::ReadFile {
+                    path: toml_file.to_display(),
+                    source,
+                })?;
+            let profile: SkillProfile =
+                toml::from_str(&content).map_err(|source| FSReadError::TOMLDeserialization {
+                    path: toml_file.to_display(),
+                    source: Box::new(source),
+                })?;
+            return Ok(Some(profile));
+        }
+
+        let json_file = profiles_directory.join(format!("{name}.json"));
+        if json_file.exists() {
+            let content = tokio::fs::read_to_string(&json_file)
+                .await
+                .map_err(|source| FSReadError::ReadFile {
+                    path: json_file.to_display(),
+                    source,
+                })?;
+            let profile: SkillProfile = serde_json::from_str(&content).map_err(|source| {
+                FSReadError::JSONDeserialization {
+                    path: json_file.to_display(),
+                    source,
+                }
+            })?;
+            return Ok(Some(profile));
+        }
+        Ok(None)
+    }
+
+    /// Saves the skill profile as JSON, replacing any existing profile with the same name.
+    pub async fn save_skill_profile(
+        &self,
+        name: &str,
+        profile: &SkillProfile,
+    ) -> Result<PathBuf, FSWriteError> {
+        if !is_valid_skill_profile_name(name) {
+            return Err(FSWriteError::InvalidSkillProfileName {
+                name: name.to_string(),
+            });
+        }
+        let profiles_directory = self.data_directory.join(SKILL_PROFILES_DIRECTORY);
+        tokio::fs::create_dir_all(&profiles_directory)
+            .await
+            .map_err(|source| FSWriteError::WriteFileError {
+                path: profiles_directory.to_display(),
+                source,
+            })?;
+
+        let profile_file = profiles_directory.join(format!("{name}.json"));
+        let content = serde_json::to_string_pretty(profile).map_err(|source| {
+            FSWriteError::JSONSeserializationError {
+                path: profile_file.to_display(),
+                source,
+            }
+        })?;
+        write_atomically_async(&profile_file, content)
+            .await
+            .map_err(|source| FSWriteError::WriteFileError {
+                path: profile_file.to_display(),
+                source,
+            })?;
+        logging::info!("File written: {}", &profile_file.to_display());
+        Ok(profile_file)
+    }
+}
+
+// History
+impl FSData {
+    /// Saves a `manufacture all` run in the history directory, one JSON file per run.
+    pub async fn save_history_snapshot(
+        &self,
+        snapshot: &HistorySnapshot,
+    ) -> Result<PathBuf, FSWriteError> {
+        let _lock = self.lock().await?;
+        let history_directory = self.data_directory.join(HISTORY_DIRECTORY);
+        tokio::fs::create_dir_all(&history_directory)
+            .await
+            .map_err(|source| FSWriteError::WriteFileError {
+                path: history_directory.to_display(),
+                source,
+            })?;
+
+        // Runs saved within the same second are told apart by a suffix. Writers hold the directory
+        // lock, so the free name can't be taken in between.
+        let mut snapshot_file = history_directory.join(format!("{}.json", snapshot.timestamp));
+        let mut suffix = 1;
+        while snapshot_file.exists() {
+            snapshot_file =
+                history_directory.join(format!("{}-{}.json", snapshot.timestamp, suffix));
+            suffix += 1;
+        }
+        let content = serde_json::to_string(snapshot).map_err(|source| {
+            FSWriteError::JSONSeserializationError {
+                path: snapshot_file.to_display(),
+                source,
+            }
+        })?;
+        write_atomically_async(&snapshot_file, content)
+            .await
+            .map_err(|source| FSWriteError::WriteFileError {
+                path: snapshot_file.to_display(),
+                source,
+            })?;
+        logging::info!("File written: {}", &snapshot_file.to_display());
+        Ok(snapshot_file)
+    }
+
+    /// Loads every saved run, oldest first.
+    pub async fn load_history_snapshots(&self) -> Result<Vec<HistorySnapshot>, FSReadError> {
+        let history_directory = self.data_directory.join(HISTORY_DIRECTORY);
+        if !history_directory.exists() {
+            return Ok(vec![]);
+        }
+
+        let mut entries = tokio::fs::read_dir(&history_directory)
+            .await
+            .map_err(|source| FSReadError::ReadFile {
+                path: history_directory.to_display(),
+                source,
+            })?;
+        let mut snapshots = vec![];
+        while let Some(entry) =
+            entries
+                .next_entry()
+                .await
+                .map_err(|source| FSReadError::ReadFile {
+                    path: history_directory.to_display(),
+                    source,
+                })?
+        {
+            let path = entry.path();
+            if path
+                .extension()
+                .is_some_and(|extension| extension == "json")
+            {
+                let content = tokio::fs::read_to_string(&path).await.map_err(|source| {
+                    FSReadError::ReadFile {
+                        path: path.to_display(),
+                        source,
+                    }
+                })?;
+                let snapshot: HistorySnapshot =
+                    serde_json::from_str(&content).map_err(|source| {
+                        FSReadError::JSONDeserialization {
+                            path: path.to_display(),
+                            source,
+                        }
+                    })?;
+                snapshots.push(snapshot);
+            }
+        }
+        snapshots.sort_by_key(|snapshot| snapshot.timestamp);
+        Ok(snapshots)
+    }
+}
+
 // Refresh Token
 impl FSData {
     pub fn load_refresh_token(&self) -> Result<Option<String>, FSReadError> {
@@ -354,7 +696,7 @@ impl FSData {
             "Save refresh token to {}",
             refresh_token_store_path.to_display()
         );
-        tokio::fs::write(&refresh_token_store_path, refresh_token_store_str)
+        write_atomically_async(&refresh_token_store_path, refresh_token_store_str)
             .await
             .map_err(|e| FSWriteError::WriteFileError {
                 path: refresh_token_store_path.to_display(),
@@ -544,6 +886,41 @@ pub struct Items {
     pub items: Vec<i32>,
 }
 
+static WATCH_RULES_FILE: &str = "watch_data.json";
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct WatchRules {
+    pub rules: Vec<WatchRule>,
+}
+
+static SKILL_PROFILES_DIRECTORY: &str = "skill_profiles";
+
+/// Profile names are file names, they must not lead outside the skill profiles directory.
+fn is_valid_skill_profile_name(name: &str) -> bool {
+    !name.is_empty() && !name.contains(['/', '\\']) && !name.contains("..")
+}
+
+/// Skill levels replacing the character skills. Skills not listed are trained to `all`, or not
+/// trained when `all` isn't set.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct SkillProfile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub all: Option<i32>,
+    #[serde(default)]
+    pub skills: Vec<SkillProfileEntry>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct SkillProfileEntry {
+    pub id: i32,
+    /// Informative only, the skill is identified by its ID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub level: i32,
+}
+
+static HISTORY_DIRECTORY: &str = "history";
+
 #[derive(Serialize, Deserialize, Debug)]
 struct RefreshTokenStore {
     refresh_token: String,
@@ -586,8 +963,13 @@ pub mod testutils {
 
 #[cfg(test)]
 mod tests {
+    use std::fs;
+
     use tests::testutils::*;
 
+    use crate::model::history::HistoryItem;
+    use crate::model::watch::{Comparison, WatchMetric};
+
     use super::*;
 
     #[tokio::test]
@@ -1314,4 +1696,200 @@ t: et
 "#
         );
     }
+
+    #[tokio::test]
+    async fn write_atomically_replaces_content_without_leftover() {
+        let directory = tempfile::tempdir().unwrap().into_path();
+        let file = directory.join("test.json");
+        prewrite(&file, "previous content");
+
+        write_atomically_async(&file, "new content").await.unwrap();
+        write_atomically_async(&file, "newest content")
+            .await
+            .unwrap();
+
+        assert_eq!(fs::read_to_string(&file).unwrap(), "newest content");
+        assert_eq!(fs::read_dir(&directory).unwrap().count(), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn concurrent_updates_keep_every_item() {
+        let (_, data_directory) = create_test_fs_data();
+
+        // Each FSData stands for another eve-vulcain process sharing the data directory
+        let mut handles = vec![];
+        for item_id in 0..10 {
+            let fs_data = FSData::new(data_directory.clone());
+            handles.push(tokio::spawn(async move { fs_data.add_item(item_id).await }));
+        }
+        for handle in handles {
+            handle.await.unwrap().unwrap();
+        }
+
+        let mut items = FSData::new(data_directory)
+            .load_items()
+            .await
+            .unwrap()
+            .items;
+        items.sort();
+        assert_eq!(items, (0..10).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn directory_lock_waits_for_release() {
+        let directory = tempfile::tempdir().unwrap().into_path();
+
+        let lock = DirectoryLock::acquire(&directory).await.unwrap();
+        let second_lock = tokio::time::timeout(
+            std::time::Duration::from_millis(100),
+            DirectoryLock::acquire(&directory),
+        )
+        .await;
+        assert!(second_lock.is_err());
+
+        drop(lock);
+        DirectoryLock::acquire(&directory).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn load_skill_profile_toml() {
+        let (fs_data, data_directory) = create_test_fs_data();
+        let profiles_directory = data_directory.join("skill_profiles");
+        fs::create_dir(&profiles_directory).unwrap();
+        prewrite(
+            &profiles_directory.join("alt.toml"),
+            r#"all = 5
+
+[[skills]]
+id = 3380
+level = 3
+"#,
+        );
+
+        let profile = fs_data.load_skill_profile("alt").await.unwrap();
+
+        assert_eq!(
+            profile,
+            Some(SkillProfile {
+                all: Some(5),
+                skills: vec![SkillProfileEntry {
+                    id: 3380,
+                    name: None,
+                    level: 3,
+                }],
+            })
+        );
+        assert_eq!(fs_data.load_skill_profile("unknown").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn save_skill_profile() {
+        let (fs_data, _) = create_test_fs_data();
+        let profile = SkillProfile {
+            all: None,
+            skills: vec![SkillProfileEntry {
+                id: 3380,
+                name: Some("Industry".to_string()),
+                level: 5,
+            }],
+        };
+
+        fs_data.save_skill_profile("main", &profile).await.unwrap();
+
+        assert_eq!(
+            fs_data.load_skill_profile("main").await.unwrap(),
+            Some(profile)
+        );
+    }
+
+    #[tokio::test]
+    async fn skill_profile_name_with_path_rejected() {
+        let (fs_data, _) = create_test_fs_data();
+        let profile = SkillProfile {
+            all: Some(5),
+            skills: vec![],
+        };
+
+        for name in ["../main", "alts/main", "alts\\main", "..", ""] {
+            assert!(matches!(
+                fs_data.save_skill_profile(name, &profile).await,
+                Err(FSWriteError::InvalidSkillProfileName { .. })
+            ));
+            assert!(matches!(
+                fs_data.load_skill_profile(name).await,
+                Err(FSReadError::InvalidSkillProfileName { .. })
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn save_history_snapshots() {
+        let (fs_data, _) = create_test_fs_data();
+        assert_eq!(fs_data.load_history_snapshots().await.unwrap(), vec![]);
+
+        let snapshot = |timestamp| HistorySnapshot {
+            timestamp,
+            items: vec![HistoryItem {
+                id: 2048,
+                name: "Damage Control I".to_string(),
+                facility_name: "Jita IV - Moon 4".to_string(),
+                profits_per_hour: 10000.0,
+                sell_price: 5000.0,
+                material_cost: 3000.0,
+                installation_cost: 100.0,
+                invention_cost: 0.0,
+                cost_index: 0.05,
+                time_per_unit: 600.0,
+            }],
+        };
+        fs_data
+            .save_history_snapshot(&snapshot(1700086400))
+            .await
+            .unwrap();
+        fs_data
+            .save_history_snapshot(&snapshot(1700000000))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            fs_data.load_history_snapshots().await.unwrap(),
+            vec![snapshot(1700000000), snapshot(1700086400)]
+        );
+
+        let first = fs_data
+            .save_history_snapshot(&snapshot(1700086400))
+            .await
+            .unwrap();
+        assert_ne!(
+            first,
+            fs_data
+                .save_history_snapshot(&snapshot(1700086400))
+                .await
+                .unwrap()
+        );
+        assert_eq!(fs_data.load_history_snapshots().await.unwrap().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn add_and_rm_watch_rules() {
+        let (fs_data, _) = create_test_fs_data();
+        let rule = WatchRule {
+            item_id: 2048,
+            metric: WatchMetric::Competitors {
+                market_id: 60003760,
+            },
+            comparison: Comparison::Below,
+            threshold: 3.0,
+        };
+
+        fs_data.add_watch_rule(rule.clone()).await.unwrap();
+        fs_data.add_watch_rule(rule.clone()).await.unwrap();
+        assert_eq!(
+            fs_data.load_watch_rules().await.unwrap().rules,
+            vec![rule.clone()]
+        );
+
+        fs_data.rm_watch_rule(&rule).await.unwrap();
+        assert_eq!(fs_data.load_watch_rules().await.unwrap().rules, vec![]);
+    }
 }
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
-use futures_util::future::{try_join, try_join3, TryJoinAll};
+use futures_util::future::{try_join, try_join4, TryJoinAll};
 use futures_util::TryFutureExt;
 use rfesi::prelude::EsiError;
 use thiserror::Error;
@@ -11,23 +11,26 @@ use tokio::try_join;
 use crate::api::evecache::cache::CacheError;
 use crate::api::evecache::cache_keys::OrderType;
 use crate::api::evecache::{self, EveRequester};
-use crate::api::sde::{BlueprintActivityType, SDEBlueprint};
+use crate::api::sde::{BlueprintActivityType, SDEBlueprint, Skills as SDESkills};
 
 use crate::filesystem::{FSData, FSFacilityType, FSReadError};
 use crate::logging;
 use crate::model::blueprint::{
-    Activities, Blueprint, BlueprintInvention, BlueprintManufacturing, Materials,
+    Activities, Blueprint, BlueprintCopying, BlueprintInvention, BlueprintManufacturing,
+    BlueprintResearch, Materials,
 };
 use crate::model::character::{Alliance, Character, CharacterLocation, Corporation, Skills};
+use crate::model::common::Identified;
 use crate::model::facility::markets::RegionOrders;
 use crate::model::facility::playerstructure::PlayerStructureStats;
 use crate::model::facility::{Facility, FacilityUsage};
+use crate::model::implants::{Implant, Implants};
 use crate::model::industry::{IndustryType, Job};
-use crate::model::items::{Item, TechLevel};
+use crate::model::items::{Item, ItemClassification, TechLevel};
 use crate::model::locations::{Constellation, CostIndexes, Region, SolarSystem};
 use crate::model::markets::CharacterOrder;
 use crate::model::prices::{ItemPrice, Prices};
-use crate::model::skills::{Skill, TrainedSkill};
+use crate::model::skills::{Attribute, Attributes, RequiredSkill, SkillTraining, TrainedSkill};
 
 #[derive(Debug, Error)]
 pub enum DataLoadError {
@@ -36,6 +39,8 @@ pub enum DataLoadError {
         #[from]
         source: evecache::cache::CacheError,
     },
+    #[error("Blueprints can't be searched by {industry_type} products")]
+    UnsupportedBlueprintActivity { industry_type: IndustryType },
 }
 
 #[derive(Debug, Error)]
@@ -46,6 +51,8 @@ pub enum CharacterLocationError {
     FacilityLoadingError(#[from] FacilityLoadingError),
     #[error(transparent)]
     SystemLoadingError(#[from] SystemLoadingError),
+    #[error(transparent)]
+    SkillProfileLoadingError(#[from] SkillProfileLoadingError),
     #[error("Location type is not supported or unknown")]
     UnknownLocationType,
 }
@@ -75,6 +82,18 @@ pub enum ItemLoadingError {
     LoadingRegisteredItemError { source: FSReadError },
 }
 
+#[derive(Debug, Error)]
+pub enum SkillProfileLoadingError {
+    #[error(transparent)]
+    DataLoadError(#[from] DataLoadError),
+    #[error("Skill profile '{name}' could not be loaded: {source}")]
+    LoadingSkillProfileError { name: String, source: FSReadError },
+    #[error("Skill profile '{name}' not found")]
+    SkillProfileNotFound { name: String },
+    #[error("Skill profile '{name}' trains all skills, which requires the SDE types. Run the 'update' command to install them.")]
+    SkillTypesNotInstalled { name: String },
+}
+
 #[derive(Debug, Error)]
 pub enum SystemLoadingError {
     #[error(transparent)]
@@ -136,12 +155,14 @@ impl DataIntegrator {
             CharacterLocationError::DataLoadError(DataLoadError::CacheError { source })
         })?;
 
-        let (location, corporation, skills) = try_join3(
+        let (location, corporation, skills, implants) = try_join4(
             self.load_character_location(),
             self.load_corporation(character_info.corporation_id)
                 .map_err(CharacterLocationError::DataLoadError),
             self.load_character_skills()
                 .map_err(CharacterLocationError::DataLoadError),
+            self.load_character_implants(character.id)
+                .map_err(CharacterLocationError::DataLoadError),
         )
         .await?;
 
@@ -152,9 +173,23 @@ impl DataIntegrator {
             location,
             corporation,
             skills,
+            implants,
         ))
     }
 
+    /// Loads the character, with the skills of the named skill profile instead of its own ones when
+    /// specified.
+    pub async fn load_character_with_profile(
+        &self,
+        skill_profile: Option<&str>,
+    ) -> Result<Character, CharacterLocationError> {
+        let mut character = self.load_character().await?;
+        if let Some(name) = skill_profile {
+            character.skills = self.load_skill_profile(name).await?;
+        }
+        Ok(character)
+    }
+
     async fn load_character_skills(&self) -> Result<Skills, DataLoadError> {
         let character = self
             .eve_cache
@@ -184,6 +219,167 @@ impl DataIntegrator {
         Ok(Skills { skills })
     }
 
+    /// Loads the implants plugged in the character active clone, keeping the industry job time
+    /// bonuses of their dogma attributes. Boosters aren't exposed by ESI and are thus ignored.
+    async fn load_character_implants(&self, character_id: i32) -> Result<Implants, DataLoadError> {
+        // https://everef.net/dogma-attributes
+        static TIME_BONUS_ATTRIBUTE_IDS: [(i32, IndustryType); 4] = [
+            (440, IndustryType::Manufacturing),
+            (452, IndustryType::Copying),
+            (453, IndustryType::ResearchTimeEfficiency),
+            (468, IndustryType::ResearchMaterialEfficiency),
+        ];
+
+        let implant_ids = self.eve_cache.get_character_implants(character_id).await?;
+
+        let mut futures = vec![];
+        for implant_id in implant_ids {
+            futures.push(async move {
+                // The SDE holds the attributes, ESI is only used when its types aren't installed
+                let (name, attributes) = match self.eve_cache.get_type_info(implant_id).await? {
+                    Some(info) => (info.name, info.dogma_attributes),
+                    None => {
+                        let implant_type = self.eve_cache.get_type(implant_id).await?;
+                        let attributes = implant_type
+                            .dogma_attributes
+                            .iter()
+                            .flatten()
+                            .map(|dogma| (dogma.attribute_id, dogma.value))
+                            .collect();
+                        (implant_type.name, attributes)
+                    }
+                };
+                let mut time_bonuses = HashMap::new();
+                for (attribute_id, industry_type) in TIME_BONUS_ATTRIBUTE_IDS {
+                    // Bonuses are stored as negative percentages, e.g. -4.0 for 4%.
+                    if let Some(value) = attributes.get(&attribute_id).filter(|v| **v != 0.0) {
+                        time_bonuses.insert(industry_type, value.abs() / 100.0);
+                    }
+                }
+                Ok::<Implant, DataLoadError>(Implant::new(implant_id, &name, time_bonuses))
+            })
+        }
+        let all_futures = futures.into_iter().collect::<TryJoinAll<_>>();
+        let implants = try_join!(all_futures)?
+            .0
+            .into_iter()
+            .filter(|implant| !implant.time_bonuses.is_empty())
+            .collect();
+        Ok(Implants { implants })
+    }
+
+    /// Loads the skills described by the named skill profile, to be used instead of the character
+    /// skills.
+    pub async fn load_skill_profile(&self, name: &str) -> Result<Skills, SkillProfileLoadingError> {
+        let profile = self
+            .fs_data
+            .load_skill_profile(name)
+            .await
+            .map_err(
+                |source| SkillProfileLoadingError::LoadingSkillProfileError {
+                    name: name.to_string(),
+                    source,
+                },
+            )?
+            .ok_or_else(|| SkillProfileLoadingError::SkillProfileNotFound {
+                name: name.to_string(),
+            })?;
+
+        let mut levels = HashMap::new();
+        if let Some(all) = profile.all {
+            let skill_ids = self
+                .eve_cache
+                .get_skill_type_ids()
+                .await
+                .map_err(DataLoadError::from)?
+                .ok_or_else(|| SkillProfileLoadingError::SkillTypesNotInstalled {
+                    name: name.to_string(),
+                })?;
+            for skill_id in skill_ids {
+                levels.insert(skill_id, all);
+            }
+        }
+        for skill in &profile.skills {
+            levels.insert(skill.id, skill.level);
+        }
+
+        let mut futures = vec![];
+        for (skill_id, level) in levels {
+            futures.push(async move {
+                let name = match self.eve_cache.get_type_info(skill_id).await? {
+                    Some(type_info) => type_info.name,
+                    None => self.eve_cache.get_type(skill_id).await?.name,
+                };
+                Ok::<TrainedSkill, DataLoadError>(TrainedSkill::new(skill_id, &name, level))
+            });
+        }
+        let all_futures = futures.into_iter().collect::<TryJoinAll<_>>();
+        let mut skills = try_join!(all_futures)?.0;
+        skills.sort_by_key(|skill| skill.id());
+        Ok(Skills { skills })
+    }
+
+    pub async fn load_character_attributes(&self) -> Result<Attributes, DataLoadError> {
+        let character = self.eve_cache.get_character_basic_info().await?;
+        let attributes = self
+            .eve_cache
+            .get_character_attributes(character.id)
+            .await?;
+        Ok(Attributes {
+            charisma: attributes.charisma,
+            intelligence: attributes.intelligence,
+            memory: attributes.memory,
+            perception: attributes.perception,
+            willpower: attributes.willpower,
+        })
+    }
+
+    /// Loads the rank and attributes of a skill from its dogma attributes. `None` when the type
+    /// isn't a skill.
+    pub async fn load_skill_training(
+        &self,
+        skill_id: i32,
+    ) -> Result<Option<SkillTraining>, DataLoadError> {
+        // https://everef.net/dogma-attributes
+        static RANK_ATTRIBUTE_ID: i32 = 275;
+        static PRIMARY_ATTRIBUTE_ID: i32 = 180;
+        static SECONDARY_ATTRIBUTE_ID: i32 = 181;
+
+        // The SDE holds the attributes, ESI is only used when its types aren't installed
+        let (name, attributes) = match self.eve_cache.get_type_info(skill_id).await? {
+            Some(info) => (info.name, info.dogma_attributes),
+            None => {
+                let skill_type = self.eve_cache.get_type(skill_id).await?;
+                let attributes: HashMap<i32, f64> = skill_type
+                    .dogma_attributes
+                    .iter()
+                    .flatten()
+                    .map(|dogma| (dogma.attribute_id, dogma.value))
+                    .collect();
+                (skill_type.name, attributes)
+            }
+        };
+        let rank = attributes.get(&RANK_ATTRIBUTE_ID);
+        let primary_attribute = attributes
+            .get(&PRIMARY_ATTRIBUTE_ID)
+            .and_then(|value| Attribute::from_dogma_id(*value as i32));
+        let secondary_attribute = attributes
+            .get(&SECONDARY_ATTRIBUTE_ID)
+            .and_then(|value| Attribute::from_dogma_id(*value as i32));
+        match (rank, primary_attribute, secondary_attribute) {
+            (Some(rank), Some(primary_attribute), Some(secondary_attribute)) => {
+                Ok(Some(SkillTraining::new(
+                    skill_id,
+                    &name,
+                    *rank,
+                    primary_attribute,
+                    secondary_attribute,
+                )))
+            }
+            _ => Ok(None),
+        }
+    }
+
     pub async fn load_station(&self, station_id: i32) -> Result<Facility, FacilityLoadingError> {
         let station_info = self
             .eve_cache
@@ -310,8 +506,20 @@ impl DataIntegrator {
     }
 
     pub async fn load_item(&self, id: i32) -> Result<Item, DataLoadError> {
+        if let Some(info) = self.eve_cache.get_type_info(id).await? {
+            let tech_level = TechLevel::from_meta_group_id(info.meta_group_id);
+            return Ok(Item::new(id, &info.name, info.packaged_volume, tech_level)
+                .with_classification(ItemClassification {
+                    group: info.group_name,
+                    category: info.category_name,
+                    meta_group: info.meta_group_name,
+                }));
+        }
+
         let loaded = self.eve_cache.get_type(id).await?;
 
+        // Without the SDE type database, relics can't be told apart: invented items are all
+        // considered tech two.
         let mut tech_level = TechLevel::One;
         let blueprint = self
             .eve_cache
@@ -323,11 +531,20 @@ impl DataIntegrator {
                 .get_blueprint(bp_id, &BlueprintActivityType::Invention)
                 .await?;
             if invention_blueprint.is_some() {
-                tech_level = TechLevel::Two
+                tech_level = TechLevel::Two;
             }
         }
 
-        Ok(Item::new(id, &loaded.name, loaded.volume, tech_level))
+        let volume = loaded.packaged_volume.or(loaded.volume);
+        Ok(Item::new(id, &loaded.name, volume, tech_level))
+    }
+
+    /// Ancient relics are the invention sources of tech three blueprints. Only the SDE type
+    /// database tells them apart from tech one blueprints.
+    async fn is_relic(&self, type_id: i32) -> Result<bool, DataLoadError> {
+        static ANCIENT_RELICS_CATEGORY: &str = "Ancient Relics";
+        let info = self.eve_cache.get_type_info(type_id).await?;
+        Ok(info.is_some_and(|info| info.category_name.as_deref() == Some(ANCIENT_RELICS_CATEGORY)))
     }
 
     pub async fn load_system(&self, system_id: i32) -> Result<SolarSystem, SystemLoadingError> {
@@ -446,8 +663,9 @@ impl DataIntegrator {
         let blueprints = self.eve_cache.get_blueprints().await?;
 
         let found_blueprints: Vec<(i32, SDEBlueprint)> = blueprints
-            .into_iter()
+            .iter()
             .filter(|(_, b)| is_searched_blueprint(b, industry_type))
+            .map(|(id, b)| (*id, b.clone()))
             .collect();
 
         let mut futures = vec![];
@@ -457,20 +675,19 @@ impl DataIntegrator {
                 match res {
                     Ok(bp) => Ok(Some(bp)),
                     Err(e) => {
-                        match &e {
-                            DataLoadError::CacheError { source } => {
-                                if let CacheError::Api { source } = &source {
-                                    if source.description.contains("get_type") {
-                                        if let EsiError::InvalidStatusCode(code) = &source.source {
-                                            if &404 == code {
-                                                logging::warning!(
-                                                    "Ignored blueprint (ID:{}): {}",
-                                                    blueprint_id,
-                                                    e
-                                                );
-                                                return Ok(None);
-                                            }
-                                        }
+                        if let DataLoadError::CacheError {
+                            source: CacheError::Api { source },
+                        } = &e
+                        {
+                            if source.description.contains("get_type") {
+                                if let EsiError::InvalidStatusCode(code) = &source.source {
+                                    if &404 == code {
+                                        logging::warning!(
+                                            "Ignored blueprint (ID:{}): {}",
+                                            blueprint_id,
+                                            e
+                                        );
+                                        return Ok(None);
                                     }
                                 }
                             }
@@ -491,12 +708,10 @@ impl DataIntegrator {
         output_item_id: i32,
         industry_type: IndustryType,
     ) -> Result<Vec<Blueprint>, DataLoadError> {
-        let blueprints = self.eve_cache.get_blueprints().await?;
-
-        let found_blueprints: Vec<(i32, SDEBlueprint)> = blueprints
-            .into_iter()
-            .filter(|(_, b)| is_searched_item_blueprint(output_item_id, b, industry_type))
-            .collect();
+        let found_blueprints = self
+            .eve_cache
+            .get_producing_blueprints(output_item_id, &blueprint_activity(industry_type)?)
+            .await?;
 
         let mut futures = vec![];
         for (blueprint_id, found_blueprint) in found_blueprints {
@@ -540,6 +755,68 @@ impl DataIntegrator {
         }
     }
 
+    /// Searches items which can be obtained through `industry_type`. Item names are matched in the
+    /// SDE, tolerating typos, with a fallback on the ESI search when the SDE types aren't installed.
+    pub async fn search_producible_items(
+        &self,
+        name: &str,
+        industry_type: IndustryType,
+        strict: bool,
+    ) -> Result<Vec<Item>, DataLoadError> {
+        let activity = blueprint_activity(industry_type)?;
+        let item_ids = match self.eve_cache.search_types(name, &activity, strict).await? {
+            Some(item_ids) => item_ids,
+            None => {
+                logging::debug!("SDE types not installed, searching items through ESI");
+                let mut item_ids = vec![];
+                for item in self
+                    .search_items(LoadFrom::Name(name.to_string()), strict)
+                    .await?
+                {
+                    if self.is_producible(item.id(), &activity).await? {
+                        item_ids.push(item.id());
+                    }
+                }
+                item_ids
+            }
+        };
+
+        let mut futures = vec![];
+        for item_id in item_ids {
+            futures.push(self.load_item(item_id));
+        }
+        let all_futures = futures.into_iter().collect::<TryJoinAll<_>>();
+        let items = try_join!(all_futures)?.0;
+        Ok(items)
+    }
+
+    async fn is_producible(
+        &self,
+        item_id: i32,
+        activity: &BlueprintActivityType,
+    ) -> Result<bool, DataLoadError> {
+        let manufacturing_blueprints = self
+            .eve_cache
+            .get_producing_blueprints(item_id, &BlueprintActivityType::Manufacturing)
+            .await?;
+        match activity {
+            BlueprintActivityType::Manufacturing => Ok(!manufacturing_blueprints.is_empty()),
+            BlueprintActivityType::Invention => {
+                for (blueprint_id, _) in manufacturing_blueprints {
+                    if self
+                        .eve_cache
+                        .get_blueprint(blueprint_id, activity)
+                        .await?
+                        .is_some()
+                    {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+        }
+    }
+
     pub async fn search_structure(
         &self,
         name: &str,
@@ -626,12 +903,18 @@ impl DataIntegrator {
                         }
                     })?;
                 let end_date = end_date.with_timezone(&Utc);
-                Ok::<Job, IndustryJobsLoadingError>(Job::new(
-                    industry_type,
-                    item,
-                    industry_job.runs,
-                    end_date,
-                ))
+                Ok::<Job, IndustryJobsLoadingError>(
+                    Job::new(
+                        industry_type,
+                        item,
+                        industry_job.runs,
+                        end_date,
+                        industry_job.facility_id,
+                        industry_job.blueprint_type_id,
+                        industry_job.cost,
+                    )
+                    .with_id(industry_job.job_id),
+                )
             })
         }
         let all_operations = futures.into_iter().collect::<TryJoinAll<_>>();
@@ -665,11 +948,14 @@ impl DataIntegrator {
                     OrderType::Sell
                 };
                 Ok::<CharacterOrder, DataLoadError>(CharacterOrder {
+                    id: order.order_id,
                     item,
                     order_type,
                     price: order.price,
                     volume_remain: order.volume_remain,
                     volume_total: order.volume_total,
+                    location_id: order.location_id,
+                    region_id: order.region_id,
                 })
             })
         }
@@ -732,17 +1018,40 @@ impl DataIntegrator {
         blueprint_id: i32,
         blueprint: SDEBlueprint,
     ) -> Result<Blueprint, DataLoadError> {
-        let (manufacturing, invention) = try_join(
+        let (manufacturing, mut invention) = try_join(
             self.load_blueprint_manufacturing(blueprint_id, &blueprint),
             self.load_blueprint_invention(blueprint_id, &blueprint),
         )
         .await?;
 
+        // The job installation cost of a copy is estimated from the materials of the copied
+        // blueprint.
+        if let (Some(invention), Some(manufacturing), Some(copying)) = (
+            &mut invention,
+            &manufacturing,
+            &blueprint.activities.copying,
+        ) {
+            invention.copying = Some(BlueprintCopying {
+                time: copying.time,
+                materials: manufacturing.materials.clone(),
+            });
+        }
+
         Ok(Blueprint {
             id: blueprint_id,
             activities: Activities {
                 manufacturing,
                 invention,
+                research_material: blueprint.activities.research_material.as_ref().map(
+                    |research| BlueprintResearch {
+                        time: research.time,
+                    },
+                ),
+                research_time: blueprint.activities.research_time.as_ref().map(|research| {
+                    BlueprintResearch {
+                        time: research.time,
+                    }
+                }),
             },
         })
     }
@@ -787,10 +1096,11 @@ impl DataIntegrator {
             let invention_blueprint_future =
                 self.load_item_blueprints(blueprint_id, IndustryType::Invention);
 
-            let (materials, products, invention_bps) = try_join3(
+            let (materials, products, invention_bps, skills) = try_join4(
                 material_futures,
                 product_futures,
                 invention_blueprint_future,
+                self.load_required_skills(&blueprint_manufacturing.skills),
             )
             .await?;
 
@@ -805,15 +1115,43 @@ impl DataIntegrator {
                 blueprint_id,
                 materials: Materials::new(materials),
                 products,
+                skills,
                 material_efficiency: 0,
                 time_efficiency: 0,
                 time: blueprint_manufacturing.time,
+                max_production_limit: blueprint.max_production_limit,
+                runs: 1,
                 invention_blueprint: bps,
             }));
         }
         Ok(None)
     }
 
+    async fn load_required_skills(
+        &self,
+        skills: &Option<Vec<SDESkills>>,
+    ) -> Result<Vec<RequiredSkill>, DataLoadError> {
+        let mut skill_futures = vec![];
+        if let Some(skills) = skills {
+            for skill in skills {
+                skill_futures.push(async {
+                    // The SDE holds the names, ESI is only used when its types aren't installed
+                    let name = match self.eve_cache.get_type_info(skill.type_id).await? {
+                        Some(info) => info.name,
+                        None => self.eve_cache.get_type(skill.type_id).await?.name,
+                    };
+                    Ok::<RequiredSkill, DataLoadError>(RequiredSkill::new(
+                        skill.type_id,
+                        &name,
+                        skill.level,
+                    ))
+                });
+            }
+        }
+        let skill_futures = skill_futures.into_iter().collect::<TryJoinAll<_>>();
+        Ok(try_join!(skill_futures)?.0)
+    }
+
     async fn load_blueprint_invention(
         &self,
         blueprint_id: i32,
@@ -854,18 +1192,13 @@ impl DataIntegrator {
 
             let (materials, products) = try_join(material_futures, product_futures).await?;
 
-            let mut skills = vec![];
-            if let Some(blueprint_skills) = &blueprint_invention.skills {
-                for skill in blueprint_skills {
-                    skills.push(self.eve_cache.get_type(skill.type_id));
-                }
-            }
-            let skills = skills.into_iter().collect::<TryJoinAll<_>>();
-            let skill_types = try_join!(skills)?.0;
+            let skills = self
+                .load_required_skills(&blueprint_invention.skills)
+                .await?;
 
-            let mut skills = vec![];
-            for t in skill_types {
-                skills.push(Skill::new(t.type_id, &t.name));
+            let mut relic = None;
+            if self.is_relic(blueprint_id).await? {
+                relic = Some(self.load_item(blueprint_id).await?);
             }
 
             return Ok(Some(BlueprintInvention {
@@ -874,51 +1207,26 @@ impl DataIntegrator {
                 products,
                 skills,
                 time: blueprint_invention.time,
+                relic,
+                // Filled by `load_blueprint` from the copied blueprint
+                copying: None,
             }));
         }
         Ok(None)
     }
 }
 
-fn is_searched_item_blueprint(
-    output_item_id: i32,
-    b: &SDEBlueprint,
-    industry_type: IndustryType,
-) -> bool {
+fn blueprint_activity(industry_type: IndustryType) -> Result<BlueprintActivityType, DataLoadError> {
     match industry_type {
-        IndustryType::Manufacturing => {
-            let blueprint_manufacturing = &b.activities.manufacturing;
-            if let Some(blueprint_manufacturing) = blueprint_manufacturing {
-                if let Some(products) = &blueprint_manufacturing.products {
-                    for product in products {
-                        if product.type_id == output_item_id {
-                            return true;
-                        }
-                    }
-                }
-            }
-        }
-        IndustryType::Copying => {
-            todo!()
-        }
-        IndustryType::Invention => {
-            if let Some(blueprint_invention) = &b.activities.invention {
-                if let Some(products) = &blueprint_invention.products {
-                    for product in products {
-                        if product.type_id == output_item_id {
-                            return true;
-                        }
-                    }
-                }
-            }
+        IndustryType::Manufacturing => Ok(BlueprintActivityType::Manufacturing),
+        IndustryType::Invention => Ok(BlueprintActivityType::Invention),
+        IndustryType::Copying
+        | IndustryType::Reaction
+        | IndustryType::ResearchTimeEfficiency
+        | IndustryType::ResearchMaterialEfficiency => {
+            Err(DataLoadError::UnsupportedBlueprintActivity { industry_type })
         }
-        IndustryType::Reaction => {
-            todo!()
-        }
-        IndustryType::ResearchTimeEfficiency => todo!(),
-        IndustryType::ResearchMaterialEfficiency => todo!(),
     }
-    false
 }
 
 fn is_searched_blueprint(b: &SDEBlueprint, industry_type: IndustryType) -> bool {
@@ -1026,12 +1334,20 @@ pub mod testutils {
 #[cfg(test)]
 mod test {
     use std::collections::HashMap;
+    use std::sync::Arc;
 
     use chrono::{TimeZone, Utc};
 
     use crate::{
-        api::evecache::cache_keys::OrderType,
-        integration::{testutils::create_test_data_integrator, LoadFrom},
+        api::evecache::{
+            cache_keys::OrderType,
+            mocks::{MockRequester, MockRequesterBuilder},
+        },
+        api::sde::{SDETypeInfo, Skills as SDESkills},
+        filesystem::testutils::{create_test_fs_data, prewrite},
+        integration::{
+            testutils::create_test_data_integrator, DataIntegrator, DataLoadError, LoadFrom,
+        },
         model::{
             blueprint::{
                 Activities, Blueprint, BlueprintInvention, BlueprintManufacturing, Materials,
@@ -1040,12 +1356,13 @@ mod test {
             character::{Alliance, Character, CharacterLocation, Corporation, Skills},
             common::Identified,
             facility::{playerstructure::PlayerStructureStats, Facility, FacilityUsage},
+            implants::{Implant, Implants},
             industry::{IndustryType, Job},
-            items::{Item, TechLevel},
+            items::{Item, ItemClassification, TechLevel},
             locations::{Constellation, CostIndexes, Region, SolarSystem},
             markets::CharacterOrder,
             prices::{ItemPrice, Prices},
-            skills::TrainedSkill,
+            skills::{Attribute, RequiredSkill, SkillTraining, TrainedSkill},
         },
     };
 
@@ -1075,6 +1392,9 @@ mod test {
                     CostIndexes {
                         manufacturing: 0.456,
                         invention: 0.789,
+                        copying: 0.0,
+                        research_material_efficiency: 0.0,
+                        research_time_efficiency: 0.0,
                     },
                 ),
                 Some(vec![FacilityUsage::Market, FacilityUsage::Industry]),
@@ -1095,6 +1415,7 @@ mod test {
                     TrainedSkill::new(7, "Test Skill n7", 0),
                 ],
             },
+            implants: Implants::default(),
         };
 
         // Better inequality targeting
@@ -1104,6 +1425,7 @@ mod test {
         assert_eq!(got.location, expected.location);
         assert_eq!(got.corporation, expected.corporation);
         assert_eq!(got.skills, expected.skills);
+        assert_eq!(got.implants, expected.implants);
 
         assert_eq!(got, expected);
     }
@@ -1124,6 +1446,34 @@ mod test {
         assert_eq!(got, expected);
     }
 
+    #[tokio::test]
+    pub async fn test_load_skill_profile() {
+        let (fs_data, data_directory) = create_test_fs_data();
+        let profiles_directory = data_directory.join("skill_profiles");
+        std::fs::create_dir(&profiles_directory).unwrap();
+        prewrite(
+            &profiles_directory.join("alt.json"),
+            r#"{
+                "skills": [
+                  { "id": 5, "level": 3 },
+                  { "id": 4, "level": 4 }
+                ]
+              }"#,
+        );
+        let data_integrator = DataIntegrator::new(Arc::new(MockRequester::default()), fs_data);
+
+        let got = data_integrator.load_skill_profile("alt").await.unwrap();
+        let expected = Skills {
+            skills: vec![
+                TrainedSkill::new(4, "Test Skill n4", 4),
+                TrainedSkill::new(5, "Test Skill n5", 3),
+            ],
+        };
+
+        assert_eq!(got, expected);
+        assert!(data_integrator.load_skill_profile("unknown").await.is_err());
+    }
+
     #[tokio::test]
     pub async fn test_load_station() {
         let (data_integrator, requester) = create_test_data_integrator();
@@ -1146,6 +1496,9 @@ mod test {
                 CostIndexes {
                     manufacturing: 0.456,
                     invention: 0.789,
+                    copying: 0.0,
+                    research_material_efficiency: 0.0,
+                    research_time_efficiency: 0.0,
                 },
             ),
             Some(vec![FacilityUsage::Market, FacilityUsage::Industry]),
@@ -1195,6 +1548,9 @@ mod test {
                 CostIndexes {
                     manufacturing: 0.456,
                     invention: 0.789,
+                    copying: 0.0,
+                    research_material_efficiency: 0.0,
+                    research_time_efficiency: 0.0,
                 },
             ),
             Some(vec![FacilityUsage::Industry]),
@@ -1246,6 +1602,9 @@ mod test {
                     CostIndexes {
                         manufacturing: 0.456,
                         invention: 0.789,
+                        copying: 0.0,
+                        research_material_efficiency: 0.0,
+                        research_time_efficiency: 0.0,
                     },
                 ),
                 Some(vec![FacilityUsage::Market, FacilityUsage::Industry]),
@@ -1268,6 +1627,9 @@ mod test {
                     CostIndexes {
                         manufacturing: 0.456,
                         invention: 0.789,
+                        copying: 0.0,
+                        research_material_efficiency: 0.0,
+                        research_time_efficiency: 0.0,
                     },
                 ),
                 Some(vec![FacilityUsage::Industry]),
@@ -1301,6 +1663,178 @@ mod test {
         assert_eq!(got, expected);
     }
 
+    #[tokio::test]
+    pub async fn test_load_item_from_sde_type_database() {
+        let requester = MockRequesterBuilder::default()
+            .insert_type_info(
+                18,
+                SDETypeInfo {
+                    name: "SDE Item 18".to_string(),
+                    packaged_volume: Some(10.0),
+                    group_name: Some("Test Group".to_string()),
+                    category_name: Some("Test Category".to_string()),
+                    meta_group_id: Some(2),
+                    meta_group_name: Some("Tech II".to_string()),
+                    dogma_attributes: HashMap::new(),
+                },
+            )
+            .build();
+        let (fs_data, _) = create_test_fs_data();
+        let data_integrator = DataIntegrator::new(Arc::new(requester), fs_data);
+
+        let got = data_integrator.load_item(18).await.unwrap();
+
+        let expected = Item::new(18, "SDE Item 18", Some(10.0), TechLevel::Two)
+            .with_classification(ItemClassification {
+                group: Some("Test Group".to_string()),
+                category: Some("Test Category".to_string()),
+                meta_group: Some("Tech II".to_string()),
+            });
+        assert_eq!(got, expected);
+    }
+
+    #[tokio::test]
+    pub async fn test_load_character_implants_from_sde_type_database() {
+        let implant_info = |name: &str, dogma_attributes| SDETypeInfo {
+            name: name.to_string(),
+            packaged_volume: Some(1.0),
+            group_name: Some("Cyberimplant".to_string()),
+            category_name: Some("Implant".to_string()),
+            meta_group_id: None,
+            meta_group_name: None,
+            dogma_attributes,
+        };
+        // Types missing from the mock requester panic when loaded from ESI
+        let requester = MockRequesterBuilder::default()
+            .insert_character_implants(1, vec![27170, 27171])
+            .insert_type_info(
+                27170,
+                implant_info(
+                    "Zainou 'Beancounter' Industry BX-802",
+                    HashMap::from([(440, -2.0)]),
+                ),
+            )
+            .insert_type_info(27171, implant_info("Memory Augmentation", HashMap::new()))
+            .build();
+        let (fs_data, _) = create_test_fs_data();
+        let data_integrator = DataIntegrator::new(Arc::new(requester), fs_data);
+
+        let got = data_integrator.load_character_implants(1).await.unwrap();
+
+        let expected = Implants {
+            implants: vec![Implant::new(
+                27170,
+                "Zainou 'Beancounter' Industry BX-802",
+                HashMap::from([(IndustryType::Manufacturing, 0.02)]),
+            )],
+        };
+        assert_eq!(got, expected);
+    }
+
+    #[tokio::test]
+    pub async fn test_load_skill_training_from_sde_type_database() {
+        // Types missing from the mock requester panic when loaded from ESI
+        let requester = MockRequesterBuilder::default()
+            .insert_type_info(
+                3380,
+                SDETypeInfo {
+                    name: "Industry".to_string(),
+                    packaged_volume: Some(0.01),
+                    group_name: Some("Production".to_string()),
+                    category_name: Some("Skill".to_string()),
+                    meta_group_id: None,
+                    meta_group_name: None,
+                    dogma_attributes: HashMap::from([(180, 165.0), (181, 166.0), (275, 1.0)]),
+                },
+            )
+            .build();
+        let (fs_data, _) = create_test_fs_data();
+        let data_integrator = DataIntegrator::new(Arc::new(requester), fs_data);
+
+        let got = data_integrator.load_skill_training(3380).await.unwrap();
+
+        let expected = SkillTraining::new(
+            3380,
+            "Industry",
+            1.0,
+            Attribute::Intelligence,
+            Attribute::Memory,
+        );
+        assert_eq!(got, Some(expected));
+    }
+
+    #[tokio::test]
+    pub async fn test_load_required_skills_from_sde_type_database() {
+        // Types missing from the mock requester panic when loaded from ESI
+        let requester = MockRequesterBuilder::default()
+            .insert_type_info(
+                3380,
+                SDETypeInfo {
+                    name: "Industry".to_string(),
+                    packaged_volume: Some(0.01),
+                    group_name: Some("Production".to_string()),
+                    category_name: Some("Skill".to_string()),
+                    meta_group_id: None,
+                    meta_group_name: None,
+                    dogma_attributes: HashMap::new(),
+                },
+            )
+            .build();
+        let (fs_data, _) = create_test_fs_data();
+        let data_integrator = DataIntegrator::new(Arc::new(requester), fs_data);
+
+        let got = data_integrator
+            .load_required_skills(&Some(vec![SDESkills {
+                level: 1,
+                type_id: 3380,
+            }]))
+            .await
+            .unwrap();
+
+        assert_eq!(got, vec![RequiredSkill::new(3380, "Industry", 1)]);
+    }
+
+    #[tokio::test]
+    pub async fn test_search_producible_items_from_sde_type_database() {
+        let mut builder = MockRequesterBuilder::default();
+        for id in [18, 19, 20] {
+            builder = builder.insert_type_info(
+                id,
+                SDETypeInfo {
+                    name: format!("Item {}", id),
+                    packaged_volume: None,
+                    group_name: None,
+                    category_name: None,
+                    meta_group_id: None,
+                    meta_group_name: None,
+                    dogma_attributes: HashMap::new(),
+                },
+            );
+        }
+        let (fs_data, _) = create_test_fs_data();
+        let data_integrator = DataIntegrator::new(Arc::new(builder.build()), fs_data);
+
+        let got = data_integrator
+            .search_producible_items("Iten", IndustryType::Manufacturing, false)
+            .await
+            .unwrap();
+        let ids: Vec<i32> = got.iter().map(|i| i.id()).collect();
+        assert_eq!(ids, vec![19, 20]);
+
+        let got = data_integrator
+            .search_producible_items("Iten", IndustryType::Invention, false)
+            .await
+            .unwrap();
+        let ids: Vec<i32> = got.iter().map(|i| i.id()).collect();
+        assert_eq!(ids, vec![20]);
+
+        let got = data_integrator
+            .search_producible_items("Iten 20", IndustryType::Manufacturing, true)
+            .await
+            .unwrap();
+        assert!(got.is_empty());
+    }
+
     #[tokio::test]
     pub async fn test_load_system() {
         let (data_integrator, _) = create_test_data_integrator();
@@ -1320,6 +1854,9 @@ mod test {
             CostIndexes {
                 manufacturing: 0.456,
                 invention: 0.789,
+                copying: 0.0,
+                research_material_efficiency: 0.0,
+                research_time_efficiency: 0.0,
             },
         );
 
@@ -1389,12 +1926,17 @@ mod test {
                             quantity: 10,
                             item: Item::new(19, "Item 19", None, TechLevel::One),
                         }],
+                        skills: vec![],
                         material_efficiency: 0,
                         time_efficiency: 0,
                         time: 1054,
+                        max_production_limit: 1,
+                        runs: 1,
                         invention_blueprint: vec![],
                     }),
                     invention: None,
+                    research_material: None,
+                    research_time: None,
                 },
             },
         );
@@ -1410,9 +1952,12 @@ mod test {
                             quantity: 1,
                             item: Item::new(20, "Item 20", Some(123.0), TechLevel::Two),
                         }],
+                        skills: vec![],
                         material_efficiency: 0,
                         time_efficiency: 0,
                         time: 100,
+                        max_production_limit: 1,
+                        runs: 1,
                         invention_blueprint: vec![BlueprintInvention {
                             blueprint_id: 22,
                             materials: Materials::new(vec![]),
@@ -1428,9 +1973,13 @@ mod test {
                             }],
                             skills: vec![],
                             time: 100,
+                            relic: None,
+                            copying: None,
                         }],
                     }),
                     invention: None,
+                    research_material: None,
+                    research_time: None,
                 },
             },
         );
@@ -1477,18 +2026,38 @@ mod test {
                         quantity: 10,
                         item: Item::new(19, "Item 19", None, TechLevel::One),
                     }],
+                    skills: vec![],
                     material_efficiency: 0,
                     time_efficiency: 0,
                     time: 1054,
+                    max_production_limit: 1,
+                    runs: 1,
                     invention_blueprint: vec![],
                 }),
                 invention: None,
+                research_material: None,
+                research_time: None,
             },
         }];
 
         assert_eq!(got, expected);
     }
 
+    #[tokio::test]
+    pub async fn test_load_item_blueprints_unsupported_activity() {
+        let (data_integrator, _) = create_test_data_integrator();
+        let got = data_integrator
+            .load_item_blueprints(19, IndustryType::Copying)
+            .await;
+
+        assert!(matches!(
+            got,
+            Err(DataLoadError::UnsupportedBlueprintActivity {
+                industry_type: IndustryType::Copying
+            })
+        ));
+    }
+
     #[tokio::test]
     pub async fn test_search_items() {
         let (data_integrator, _) = create_test_data_integrator();
@@ -1562,6 +2131,9 @@ mod test {
                     CostIndexes {
                         manufacturing: 0.456,
                         invention: 0.789,
+                        copying: 0.0,
+                        research_material_efficiency: 0.0,
+                        research_time_efficiency: 0.0,
                     },
                 ),
                 Some(vec![FacilityUsage::Industry]),
@@ -1631,6 +2203,9 @@ mod test {
                 Some(Item::new(19, "Item 19", None, TechLevel::One)),
                 6,
                 Utc.with_ymd_and_hms(2014, 7, 8, 9, 10, 11).unwrap(),
+                0,
+                0,
+                None,
             ),
         );
         expected.insert(
@@ -1645,6 +2220,9 @@ mod test {
                 )),
                 2,
                 Utc.with_ymd_and_hms(2014, 10, 2, 10, 11, 12).unwrap(),
+                0,
+                0,
+                None,
             ),
         );
 
@@ -1672,21 +2250,27 @@ mod test {
         expected.insert(
             19,
             CharacterOrder {
+                id: 0,
                 item: Item::new(19, "Item 19", None, TechLevel::One),
                 order_type: OrderType::Sell,
                 price: 123456.78,
                 volume_remain: 456,
                 volume_total: 789,
+                location_id: 0,
+                region_id: 0,
             },
         );
         expected.insert(
             20,
             CharacterOrder {
+                id: 0,
                 item: Item::new(20, "Item 20", Some(123.0), TechLevel::Two),
                 order_type: OrderType::Buy,
                 price: 456789.12,
                 volume_remain: 789,
                 volume_total: 1230,
+                location_id: 0,
+                region_id: 0,
             },
         );
 
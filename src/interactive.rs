@@ -1,14 +1,10 @@
-use futures_util::future::TryJoinAll;
 use inquire::{InquireError, Select};
-use tokio::try_join;
 
 use crate::{
     errors::{EnvironmentError, EveError, ModelError},
-    integration::{DataIntegrator, LoadFrom},
+    integration::DataIntegrator,
     logging,
     model::{
-        blueprint::Blueprint,
-        common::Identified,
         industry::IndustryType,
         items::{Item, TechLevel},
     },
@@ -33,39 +29,15 @@ pub async fn load_item(
     eve: &DataIntegrator,
     item_name: String,
     strict: bool,
+    industry_type: IndustryType,
     tech_level: Option<TechLevel>,
 ) -> Result<Option<Item>, EveError> {
     logging::debug!("Item name: {:?}", item_name);
-    let items = eve
-        .search_items(LoadFrom::Name(item_name.clone()), strict)
+    let mut filtered_items = eve
+        .search_producible_items(&item_name, industry_type, strict)
         .await?;
     logging::debug!("Search item: {:?}", item_name);
 
-    let mut futures = vec![];
-    for item in items {
-        futures.push(async {
-            let bps = eve
-                .load_item_blueprints(item.id(), IndustryType::Manufacturing)
-                .await?;
-            Ok::<(Item, Vec<Blueprint>), EveError>((item, bps))
-        })
-    }
-    let all_futures = futures.into_iter().collect::<TryJoinAll<_>>();
-    let item_bps_associations = try_join!(all_futures)?.0;
-    logging::debug!("Blueprints loaded: {:?}", item_name);
-
-    let mut filtered_items: Vec<Item> = item_bps_associations
-        .into_iter()
-        .filter(|(_, bps)| {
-            for bp in bps {
-                if bp.activities.manufacturing.is_some() {
-                    return true;
-                }
-            }
-            false
-        })
-        .map(|(i, _)| i)
-        .collect();
     if let Some(tech_level) = tech_level {
         filtered_items.retain(|i| i.tech_level == tech_level);
     }
@@ -9,7 +9,7 @@ use std::path::PathBuf;
 use std::process;
 use std::sync::Arc;
 
-use api::evecache::{CacheLevel, EveCache};
+use api::evecache::{CacheLevel, EveCache, PeriodicPersistence};
 use api::sde::Sde;
 use authentication::tokens::TokenHelper;
 use cache::FSCache;
@@ -19,20 +19,30 @@ use configuration::ConfigurationDirectoryType;
 use errors::{Advice, EnvironmentError, EveApiError, EveAuthenticationError, EveError};
 
 use crate::authentication::Authenticator;
+use crate::commands::calibrate::calibrate;
 use crate::commands::facility::facility;
+use crate::commands::history::history;
 use crate::commands::init::init;
 use crate::commands::item::item;
+use crate::commands::jobs::jobs;
 use crate::commands::login::login;
 use crate::commands::logout::logout;
 use crate::commands::manufacturing::manufacturing;
+use crate::commands::optimize::optimize;
+use crate::commands::orders::orders;
+use crate::commands::research::research;
+use crate::commands::schedule::schedule;
+use crate::commands::skills::skills;
 use crate::commands::state::state;
 use crate::commands::update::update;
+use crate::commands::watch::watch;
 use crate::configuration::cli;
 use crate::configuration::cli::{Args, Commands};
 use crate::configuration::Configuration;
 use crate::filesystem::FSData;
 use crate::integration::DataIntegrator;
 use crate::logging::Verbosity;
+use crate::notification::Notifier;
 
 mod api;
 mod authentication;
@@ -49,10 +59,14 @@ mod display;
 mod filesystem;
 mod interactive;
 mod model;
+mod notification;
 mod retry;
 mod round;
+mod search;
 mod vector;
 
+const CACHE_PERSISTENCE_PERIOD: std::time::Duration = std::time::Duration::from_secs(60);
+
 #[tokio::main]
 async fn main() -> Result<(), EveError> {
     let args = cli::Args::parse();
@@ -118,6 +132,10 @@ async fn run(args: Args, verbosity: Verbosity) -> Result<(), EveError> {
     }
     logging::init(false, verbosity);
 
+    if !matches!(args.command, Commands::Update(_)) {
+        commands::update::warn_if_outdated(&cache_dir).await;
+    }
+
     let fs_data = FSData::new(data_dir);
 
     let authenticator = Authenticator::new(&fs_data, &cfg);
@@ -146,10 +164,10 @@ async fn run(args: Args, verbosity: Verbosity) -> Result<(), EveError> {
             if opts.json {
                 logging::init(true, verbosity);
             }
-            let cache = Arc::new(get_eve_cache(&args, cache_dir, cfg, &fs_data).await?);
+            let (cache, persistence) = get_eve_cache(&args, cache_dir, cfg, &fs_data).await?;
             let data_integrator = DataIntegrator::new(cache.clone(), fs_data);
-            state(&data_integrator).await?;
-            cache.persist().await?;
+            let result = state(&data_integrator).await;
+            stop_and_persist(&cache, persistence, result).await?;
         }
         Commands::Update(opts) => {
             update(cache_dir.clone(), opts).await?;
@@ -158,43 +176,152 @@ async fn run(args: Args, verbosity: Verbosity) -> Result<(), EveError> {
             if opts.json {
                 logging::init(true, verbosity);
             }
-            let cache = Arc::new(get_eve_cache(&args, cache_dir, cfg, &fs_data).await?);
+            let (cache, persistence) = get_eve_cache(&args, cache_dir, cfg, &fs_data).await?;
             let data_integrator = DataIntegrator::new(cache.clone(), fs_data);
-            manufacturing(&data_integrator, opts).await?;
-            cache.persist().await?;
+            let result = manufacturing(&data_integrator, opts).await;
+            stop_and_persist(&cache, persistence, result).await?;
         }
         Commands::Facility(opts) => {
-            let cache = Arc::new(get_eve_cache(&args, cache_dir, cfg, &fs_data).await?);
+            let (cache, persistence) = get_eve_cache(&args, cache_dir, cfg, &fs_data).await?;
             let data_integrator = DataIntegrator::new(cache.clone(), fs_data);
-            facility(&data_integrator, opts).await?;
-            cache.persist().await?;
+            let result = facility(&data_integrator, opts).await;
+            stop_and_persist(&cache, persistence, result).await?;
         }
         Commands::Item(opts) => {
-            let cache = Arc::new(get_eve_cache(&args, cache_dir, cfg, &fs_data).await?);
+            let (cache, persistence) = get_eve_cache(&args, cache_dir, cfg, &fs_data).await?;
             let data_integrator = DataIntegrator::new(cache.clone(), fs_data);
-            item(&data_integrator, opts).await?;
-            cache.persist().await?;
+            let result = item(&data_integrator, opts).await;
+            stop_and_persist(&cache, persistence, result).await?;
         }
         Commands::Invent(opts) => {
             if opts.json {
                 logging::init(true, verbosity);
             }
-            let cache = Arc::new(get_eve_cache(&args, cache_dir, cfg, &fs_data).await?);
+            let (cache, persistence) = get_eve_cache(&args, cache_dir, cfg, &fs_data).await?;
+            let data_integrator = DataIntegrator::new(cache.clone(), fs_data);
+            let result = invention(&data_integrator, opts).await;
+            stop_and_persist(&cache, persistence, result).await?;
+        }
+        Commands::Research(opts) => {
+            if opts.json {
+                logging::init(true, verbosity);
+            }
+            let (cache, persistence) = get_eve_cache(&args, cache_dir, cfg, &fs_data).await?;
+            let data_integrator = DataIntegrator::new(cache.clone(), fs_data);
+            let result = research(&data_integrator, opts).await;
+            stop_and_persist(&cache, persistence, result).await?;
+        }
+        Commands::Schedule(opts) => {
+            if opts.json {
+                logging::init(true, verbosity);
+            }
+            let (cache, persistence) = get_eve_cache(&args, cache_dir, cfg, &fs_data).await?;
+            let data_integrator = DataIntegrator::new(cache.clone(), fs_data);
+            let result = schedule(&data_integrator, opts).await;
+            stop_and_persist(&cache, persistence, result).await?;
+        }
+        Commands::Optimize(opts) => {
+            if opts.json {
+                logging::init(true, verbosity);
+            }
+            let (cache, persistence) = get_eve_cache(&args, cache_dir, cfg, &fs_data).await?;
+            let data_integrator = DataIntegrator::new(cache.clone(), fs_data);
+            let result = optimize(&data_integrator, opts).await;
+            stop_and_persist(&cache, persistence, result).await?;
+        }
+        Commands::Calibrate(opts) => {
+            if opts.json {
+                logging::init(true, verbosity);
+            }
+            let (cache, persistence) = get_eve_cache(&args, cache_dir, cfg, &fs_data).await?;
+            let data_integrator = DataIntegrator::new(cache.clone(), fs_data);
+            let result = calibrate(&data_integrator, opts).await;
+            stop_and_persist(&cache, persistence, result).await?;
+        }
+        Commands::History(opts) => {
+            if opts.json {
+                logging::init(true, verbosity);
+            }
+            history(&fs_data, opts).await?;
+        }
+        Commands::Watch(opts) => {
+            if opts.json {
+                logging::init(true, verbosity);
+            }
+            let notifiers = Notifier::from_configuration(&cfg).map_err(|source| {
+                EnvironmentError::ConfigurationOptionLoading {
+                    option_name: "notifications".to_string(),
+                    source,
+                }
+            })?;
+            let (cache, persistence) = get_eve_cache(&args, cache_dir, cfg, &fs_data).await?;
             let data_integrator = DataIntegrator::new(cache.clone(), fs_data);
-            invention(&data_integrator, opts).await?;
-            cache.persist().await?;
+            let result = watch(&data_integrator, &notifiers, opts).await;
+            stop_and_persist(&cache, persistence, result).await?;
+        }
+        Commands::Jobs(opts) => {
+            if opts.json {
+                logging::init(true, verbosity);
+            }
+            let notifiers = Notifier::from_configuration(&cfg).map_err(|source| {
+                EnvironmentError::ConfigurationOptionLoading {
+                    option_name: "notifications".to_string(),
+                    source,
+                }
+            })?;
+            let (cache, persistence) = get_eve_cache(&args, cache_dir, cfg, &fs_data).await?;
+            let data_integrator = DataIntegrator::new(cache.clone(), fs_data);
+            let result = jobs(&data_integrator, &notifiers, opts).await;
+            stop_and_persist(&cache, persistence, result).await?;
+        }
+        Commands::Orders(opts) => {
+            if opts.json {
+                logging::init(true, verbosity);
+            }
+            let (cache, persistence) = get_eve_cache(&args, cache_dir, cfg, &fs_data).await?;
+            let data_integrator = DataIntegrator::new(cache.clone(), fs_data);
+            let result = orders(&data_integrator, opts).await;
+            stop_and_persist(&cache, persistence, result).await?;
+        }
+        Commands::Skills(opts) => {
+            if opts.json {
+                logging::init(true, verbosity);
+            }
+            let (cache, persistence) = get_eve_cache(&args, cache_dir, cfg, &fs_data).await?;
+            let data_integrator = DataIntegrator::new(cache.clone(), fs_data);
+            let result = skills(&data_integrator, opts).await;
+            stop_and_persist(&cache, persistence, result).await?;
         }
     }
 
     Ok(())
 }
 
+/// Stops the periodic persistence and saves the cache whether the command succeeded or not, so
+/// the ESI responses fetched before a failure are not lost.
+async fn stop_and_persist<E: Into<EveError>>(
+    cache: &EveCache,
+    persistence: PeriodicPersistence,
+    result: Result<(), E>,
+) -> Result<(), EveError> {
+    persistence.stop().await;
+    let persisted = cache.persist().await;
+    if let Err(err) = result {
+        if let Err(persist_err) = persisted {
+            logging::warning!("{}", persist_err);
+        }
+        return Err(err.into());
+    }
+    persisted?;
+    Ok(())
+}
+
 async fn get_eve_cache(
     args: &Args,
     cache_dir: PathBuf,
     cfg: impl Configuration,
     fs_data: &FSData,
-) -> Result<EveCache, EveError> {
+) -> Result<(Arc<EveCache>, PeriodicPersistence), EveError> {
     let mut esi = Authenticator::new(fs_data, &cfg)
         .authenticate()
         .await
@@ -221,5 +348,7 @@ async fn get_eve_cache(
     )
     .await
     .map_err(|source| EveApiError::ESICacheInitFailed { source })?;
-    Ok(cache)
+    let cache = Arc::new(cache);
+    let persistence = cache.clone().persist_periodically(CACHE_PERSISTENCE_PERIOD);
+    Ok((cache, persistence))
 }
@@ -1,7 +1,7 @@
 use crate::model::common::{DetailedCalculation, Identified, Named};
 use crate::model::prices::Prices;
 
-use super::skills::Skill;
+use super::skills::RequiredSkill;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Blueprint {
@@ -19,11 +19,15 @@ impl Identified<i32> for Blueprint {
 pub struct Activities {
     pub manufacturing: Option<BlueprintManufacturing>,
     pub invention: Option<BlueprintInvention>,
+    pub research_material: Option<BlueprintResearch>,
+    pub research_time: Option<BlueprintResearch>,
 }
 
 pub struct ManufacturingEnvironment {
     pub material_efficiency: u8,
     pub material_consumption_modifier: Option<f64>,
+    /// Runs of the job, materials being rounded for the whole job.
+    pub runs: i32,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -31,11 +35,49 @@ pub struct BlueprintInvention {
     pub blueprint_id: i32,
     pub materials: Materials,
     pub products: Vec<ProbableMultipleItems>,
-    pub skills: Vec<Skill>,
+    pub skills: Vec<RequiredSkill>,
     pub time: i32,
+    /// Ancient relic consumed by the job, when reverse engineering tech three blueprints.
+    pub relic: Option<crate::model::items::Item>,
+    /// Copy job producing the blueprint copy consumed by the job. Relics are consumed as is.
+    pub copying: Option<BlueprintCopying>,
+}
+
+/// Material or time efficiency research of an original blueprint.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BlueprintResearch {
+    /// Time to research the first level, the following ones take longer.
+    pub time: i32,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BlueprintCopying {
+    /// Time to copy a single run.
+    pub time: i32,
+    /// Manufacturing materials of the copied blueprint, used to estimate the job cost.
+    pub materials: Materials,
+}
+
+impl BlueprintCopying {
+    pub fn estimated_item_value(&self, prices: &Prices) -> f64 {
+        self.materials.estimated_item_value(prices)
+    }
 }
 
 impl BlueprintInvention {
+    /// Materials consumed by one job, including the relic which has to be bought like any other
+    /// material.
+    pub fn input_materials(&self) -> Materials {
+        let mut materials = self.materials.0.clone();
+        if let Some(relic) = &self.relic {
+            materials.push(MultipleItems {
+                quantity: 1,
+                item: relic.clone(),
+            });
+        }
+        Materials::new(materials)
+    }
+
     pub fn get_product(&self, product_id: i32) -> Option<&ProbableMultipleItems> {
         self.products
             .iter()
@@ -55,9 +97,14 @@ pub struct BlueprintManufacturing {
     pub blueprint_id: i32,
     pub materials: Materials,
     pub products: Vec<MultipleItems>,
+    pub skills: Vec<RequiredSkill>,
     pub material_efficiency: u8,
     pub time_efficiency: u8,
     pub time: i32,
+    /// Maximum runs of a single job, or of a copy of this blueprint.
+    pub max_production_limit: i32,
+    /// Runs of a single manufacturing job, bounded by `max_production_limit`.
+    pub runs: i32,
     pub invention_blueprint: Vec<BlueprintInvention>,
 }
 
@@ -75,14 +122,12 @@ impl BlueprintManufacturing {
     }
 
     pub fn estimated_item_value(&self, prices: &Prices) -> f64 {
-        let mut val = 0.0;
-        for material in &self.materials.0 {
-            let item_prices = prices.get(material.item.id());
-            if let Some(item_prices) = item_prices {
-                val += (material.quantity as f64) * item_prices.get_adjusted().unwrap_or(0.0);
-            }
-        }
-        val
+        self.materials.estimated_item_value(prices)
+    }
+
+    /// Sets the runs of a single job, bounded between one run and the blueprint production limit.
+    pub fn set_runs(&mut self, runs: i32) {
+        self.runs = runs.clamp(1, self.max_production_limit.max(1));
     }
 }
 
@@ -93,17 +138,19 @@ pub struct MultipleItems {
 }
 
 impl MultipleItems {
+    /// Quantity consumed by a whole job, rounded like in game:
+    /// `max(runs, ceil(round(quantity * runs * ME * structure modifier, 2)))`.
     pub fn effective_quantity(&self, env: &ManufacturingEnvironment) -> i32 {
-        let qt = self.quantity;
-
-        let material_efficiency_normalized = (env.material_efficiency as f64) / 100.0;
-        let material_efficiency_reduction = (qt as f64) * material_efficiency_normalized;
-
-        let material_consumption_modifier_reduction = match env.material_consumption_modifier {
-            None => 0,
-            Some(modifier) => (qt as f64 * modifier).round() as i32,
-        };
-        qt - (material_efficiency_reduction as i32) - material_consumption_modifier_reduction
+        let material_efficiency_normalized = 1.0 - (env.material_efficiency as f64) / 100.0;
+        let material_consumption_normalized =
+            1.0 - env.material_consumption_modifier.unwrap_or(0.0);
+
+        let quantity = (self.quantity as f64)
+            * (env.runs as f64)
+            * material_efficiency_normalized
+            * material_consumption_normalized;
+        let quantity = ((quantity * 100.0).round() / 100.0).ceil() as i32;
+        quantity.max(env.runs)
     }
 
     pub fn cost(
@@ -161,11 +208,13 @@ impl Materials {
         &self,
         material_consumption_modifier: Option<f64>,
         material_efficiency: Option<u8>,
+        runs: i32,
         prices: &Prices,
     ) -> DetailedCalculation<f64, InputMaterialsCostsDetails> {
         let manufacturing_environment = ManufacturingEnvironment {
             material_efficiency: material_efficiency.unwrap_or(0),
             material_consumption_modifier,
+            runs,
         };
 
         let mut total = 0.0;
@@ -184,6 +233,18 @@ impl Materials {
             details: InputMaterialsCostsDetails { costs: orders },
         }
     }
+
+    /// Sum of the materials adjusted prices, which is the base of job installation costs.
+    pub fn estimated_item_value(&self, prices: &Prices) -> f64 {
+        let mut val = 0.0;
+        for material in &self.0 {
+            let item_prices = prices.get(material.item.id());
+            if let Some(item_prices) = item_prices {
+                val += (material.quantity as f64) * item_prices.get_adjusted().unwrap_or(0.0);
+            }
+        }
+        val
+    }
 }
 
 #[cfg(test)]
@@ -202,6 +263,7 @@ mod tests {
         let env = ManufacturingEnvironment {
             material_efficiency: 50,
             material_consumption_modifier: None,
+            runs: 1,
         };
 
         let item = MultipleItems {
@@ -217,6 +279,7 @@ mod tests {
         let env = ManufacturingEnvironment {
             material_efficiency: 25,
             material_consumption_modifier: Some(0.2),
+            runs: 1,
         };
 
         let item = MultipleItems {
@@ -224,7 +287,36 @@ mod tests {
             item: Item::new(1, "Test", None, TechLevel::One),
         };
 
-        assert_eq!(item.effective_quantity(&env), 4); // 8 - (8 * 0.25) - (8 * 0.2).round()
+        assert_eq!(item.effective_quantity(&env), 5); // ceil(8 * 0.75 * 0.8)
+    }
+
+    #[test]
+    fn test_effective_quantity_per_job() {
+        let quantity = |quantity, runs, material_efficiency, modifier| {
+            let env = ManufacturingEnvironment {
+                material_efficiency,
+                material_consumption_modifier: modifier,
+                runs,
+            };
+            MultipleItems {
+                quantity,
+                item: Item::new(1, "Test", None, TechLevel::One),
+            }
+            .effective_quantity(&env)
+        };
+
+        assert_eq!(quantity(1, 10, 10, None), 10); // At least one unit per run
+        assert_eq!(quantity(5, 10, 10, Some(0.01)), 45); // ceil(44.55)
+        assert_eq!(quantity(29, 1, 10, Some(0.042)), 25); // 25.0038 rounded to 25.00 first
+        assert_eq!(quantity(100, 10, 10, None), 900);
+
+        // Drone Link Augmentor I blueprint (23528), ME 10, 10 runs in a Raitaru (1% material bonus)
+        assert_eq!(quantity(5556, 10, 10, Some(0.01)), 49504); // Tritanium, 49503.96
+        assert_eq!(quantity(2222, 10, 10, Some(0.01)), 19799); // Pyerite, 19798.02
+        assert_eq!(quantity(61, 10, 10, Some(0.01)), 544); // Mexallon, 543.51
+        assert_eq!(quantity(2, 10, 10, Some(0.01)), 18); // Isogen, 17.82
+        assert_eq!(quantity(112, 10, 10, Some(0.01)), 998); // Zydrine, 997.92
+        assert_eq!(quantity(22, 10, 10, Some(0.01)), 197); // Megacyte, 196.02
     }
 
     #[test]
@@ -232,6 +324,7 @@ mod tests {
         let env = ManufacturingEnvironment {
             material_efficiency: 50,
             material_consumption_modifier: None,
+            runs: 1,
         };
 
         let item = MultipleItems {
@@ -257,6 +350,7 @@ mod tests {
         let env = ManufacturingEnvironment {
             material_efficiency: 25,
             material_consumption_modifier: Some(0.2),
+            runs: 1,
         };
 
         let item = MultipleItems {
@@ -270,11 +364,11 @@ mod tests {
         assert_eq!(result.details.id, 1);
         assert_eq!(result.details.name, "Test");
         assert_eq!(result.details.orders.len(), 1);
-        assert_eq!(result.details.orders[0].effective_quantity, 4); // 8 - (8 * 0.25) - (8 * 0.2).round()
+        assert_eq!(result.details.orders[0].effective_quantity, 5); // ceil(8 * 0.75 * 0.8)
         assert_eq!(result.details.orders[0].price_per_unit, average_price);
-        assert_eq!(result.details.orders[0].total, 12.0);
-        assert_eq!(result.details.total, 12.0);
-        assert_eq!(result.value, 12.0);
+        assert_eq!(result.details.orders[0].total, 15.0);
+        assert_eq!(result.details.total, 15.0);
+        assert_eq!(result.value, 15.0);
     }
 
     #[test]
@@ -297,7 +391,7 @@ mod tests {
 
         let materials = Materials::new(items);
 
-        let result = materials.input_materials_cost(None, Some(50), &prices);
+        let result = materials.input_materials_cost(None, Some(50), 1, &prices);
 
         assert_eq!(result.value, 25.0);
         assert_eq!(result.details.costs.len(), 2);
@@ -325,7 +419,7 @@ mod tests {
 
         let materials = Materials::new(items);
 
-        let result = materials.input_materials_cost(Some(0.2), Some(25), &prices);
+        let result = materials.input_materials_cost(Some(0.2), Some(25), 1, &prices);
 
         assert_eq!(result.value, 28.5);
         assert_eq!(result.details.costs.len(), 2);
@@ -352,9 +446,12 @@ mod tests {
             blueprint_id: 1,
             materials,
             products: vec![],
+            skills: vec![],
             material_efficiency: 25,
             time_efficiency: 10,
             time: 120,
+            max_production_limit: 1,
+            runs: 1,
             invention_blueprint: Vec::new(),
         };
 
@@ -367,4 +464,33 @@ mod tests {
 
         assert_eq!(result, 45.0);
     }
+
+    #[test]
+    fn test_invention_input_materials_include_relic() {
+        let blueprint = BlueprintInvention {
+            blueprint_id: 30,
+            materials: Materials::new(vec![MultipleItems {
+                quantity: 3,
+                item: Item::new(1, "Datacore", None, TechLevel::One),
+            }]),
+            products: vec![],
+            skills: vec![],
+            time: 100,
+            relic: Some(Item::new(30, "Intact Hull Section", None, TechLevel::One)),
+            copying: None,
+        };
+
+        let mut prices = HashMap::new();
+        prices.insert(1, ItemPrice::new(None, Some(2.0)));
+        prices.insert(30, ItemPrice::new(None, Some(100.0)));
+        let prices = Prices { prices };
+
+        let result = blueprint
+            .input_materials()
+            .input_materials_cost(None, None, 1, &prices);
+
+        assert_eq!(result.value, 106.0);
+        assert_eq!(result.details.costs.len(), 2);
+        assert_eq!(result.details.costs[1].name, "Intact Hull Section");
+    }
 }
@@ -0,0 +1,92 @@
+/// Installation cost of a job, as estimated from the facility current data and as charged in game.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobCalibration {
+    pub facility_id: i64,
+    pub item_name: String,
+    pub runs: i32,
+    pub estimated_cost: f64,
+    pub actual_cost: f64,
+}
+
+impl JobCalibration {
+    /// Relative difference between the estimated and the actual cost. Positive when the estimate
+    /// is too high.
+    pub fn deviation(&self) -> f64 {
+        (self.estimated_cost - self.actual_cost) / self.actual_cost
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FacilityCalibration {
+    pub facility_id: i64,
+    pub jobs: Vec<JobCalibration>,
+}
+
+impl FacilityCalibration {
+    /// Relative difference between the estimated and the actual costs of all the facility jobs.
+    pub fn deviation(&self) -> f64 {
+        let estimated: f64 = self.jobs.iter().map(|job| job.estimated_cost).sum();
+        let actual: f64 = self.jobs.iter().map(|job| job.actual_cost).sum();
+        (estimated - actual) / actual
+    }
+
+    /// Cost indexes move daily, so small deviations are expected. Larger ones come from a tax or
+    /// a job cost modifier which doesn't match the facility.
+    pub fn exceeds_tolerance(&self, tolerance: f64) -> bool {
+        self.deviation().abs() > tolerance
+    }
+}
+
+/// Groups the jobs per facility, ignoring jobs installed for free.
+pub fn calibrate(jobs: Vec<JobCalibration>) -> Vec<FacilityCalibration> {
+    let mut facilities: Vec<FacilityCalibration> = vec![];
+    for job in jobs.into_iter().filter(|job| job.actual_cost > 0.0) {
+        match facilities
+            .iter_mut()
+            .find(|facility| facility.facility_id == job.facility_id)
+        {
+            Some(facility) => facility.jobs.push(job),
+            None => facilities.push(FacilityCalibration {
+                facility_id: job.facility_id,
+                jobs: vec![job],
+            }),
+        }
+    }
+    facilities
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::round::Round;
+
+    use super::*;
+
+    fn job(facility_id: i64, estimated_cost: f64, actual_cost: f64) -> JobCalibration {
+        JobCalibration {
+            facility_id,
+            item_name: "Item".to_string(),
+            runs: 1,
+            estimated_cost,
+            actual_cost,
+        }
+    }
+
+    #[test]
+    fn test_calibrate() {
+        let facilities = calibrate(vec![
+            job(1, 1010.0, 1000.0),
+            job(2, 1500.0, 1000.0),
+            job(1, 990.0, 1000.0),
+            job(2, 100.0, 0.0),
+        ]);
+
+        assert_eq!(facilities.len(), 2);
+        assert_eq!(facilities[0].jobs.len(), 2);
+        assert_eq!(facilities[0].jobs[0].deviation().specific_round(2), 0.01);
+        assert_eq!(facilities[0].deviation(), 0.0);
+        assert!(!facilities[0].exceeds_tolerance(0.05));
+        assert_eq!(facilities[1].jobs.len(), 1);
+        assert_eq!(facilities[1].deviation(), 0.5);
+        assert!(facilities[1].exceeds_tolerance(0.05));
+    }
+}
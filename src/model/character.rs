@@ -1,8 +1,12 @@
+use strum::IntoEnumIterator;
+
 use crate::model::common::{Identified, Named};
 use crate::model::facility::Facility;
 use crate::model::locations::SolarSystem;
 
-use super::skills::TrainedSkill;
+use super::implants::Implants;
+use super::industry::{Job, JobSlots, SlotType};
+use super::skills::{MissingSkill, RequiredSkill, TrainedSkill};
 
 #[derive(Debug, PartialEq)]
 pub struct Character {
@@ -12,6 +16,7 @@ pub struct Character {
     pub location: CharacterLocation,
     pub corporation: Corporation,
     pub skills: Skills,
+    pub implants: Implants,
 }
 
 impl Character {
@@ -22,6 +27,7 @@ impl Character {
         location: CharacterLocation,
         corporation: Corporation,
         skills: Skills,
+        implants: Implants,
     ) -> Self {
         Self {
             id,
@@ -29,6 +35,7 @@ impl Character {
             location,
             corporation,
             skills,
+            implants,
             isk,
         }
     }
@@ -101,7 +108,7 @@ impl Named for Alliance {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Skills {
     pub skills: Vec<TrainedSkill>,
 }
@@ -128,6 +135,62 @@ impl Skills {
     pub fn get_skill(&self, id: i32) -> Option<&TrainedSkill> {
         self.skills.iter().find(|&skill| skill.id() == id)
     }
+
+    /// Trained level of the skill with the given name, 0 when the skill isn't trained.
+    pub fn trained_level(&self, name: &str) -> i32 {
+        self.skills
+            .iter()
+            .find(|&skill| skill.name() == name)
+            .map(|skill| skill.trained_level)
+            .unwrap_or(0)
+    }
+
+    /// Copy of the skills, with the given skill trained one more level.
+    pub fn with_raised_skill(&self, id: i32, name: &str) -> Skills {
+        let mut skills = self.clone();
+        match skills.skills.iter_mut().find(|skill| skill.id() == id) {
+            Some(skill) => skill.trained_level += 1,
+            None => skills.skills.push(TrainedSkill::new(id, name, 1)),
+        }
+        skills
+    }
+
+    /// Maximum number of parallel jobs of the slot type: one, plus one per level of the slot
+    /// skills.
+    pub fn max_job_slots(&self, slot_type: SlotType) -> i32 {
+        let (skill, advanced_skill) = match slot_type {
+            SlotType::Manufacturing => ("Mass Production", "Advanced Mass Production"),
+            SlotType::Science => ("Laboratory Operation", "Advanced Laboratory Operation"),
+            SlotType::Reaction => ("Mass Reactions", "Advanced Mass Reactions"),
+        };
+        1 + self.trained_level(skill) + self.trained_level(advanced_skill)
+    }
+
+    /// Job slots of each kind, occupied by the given running jobs.
+    pub fn job_slots(&self, jobs: &[Job]) -> Vec<JobSlots> {
+        SlotType::iter()
+            .map(|slot_type| JobSlots::new(slot_type, self.max_job_slots(slot_type), jobs))
+            .collect()
+    }
+
+    /// Required skills the character hasn't trained to the required level.
+    pub fn missing_skills(&self, required_skills: &[RequiredSkill]) -> Vec<MissingSkill> {
+        let mut missing_skills = vec![];
+        for required_skill in required_skills {
+            let trained_level = self
+                .get_skill(required_skill.id())
+                .map(|skill| skill.trained_level)
+                .unwrap_or(0);
+            if trained_level < required_skill.level {
+                missing_skills.push(MissingSkill {
+                    name: required_skill.name(),
+                    required_level: required_skill.level,
+                    trained_level,
+                });
+            }
+        }
+        missing_skills
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -138,6 +201,10 @@ pub struct ManufacturingSkills {
 
 #[cfg(test)]
 mod test {
+    use chrono::{TimeZone, Utc};
+
+    use crate::model::industry::IndustryType;
+
     use super::*;
 
     #[test]
@@ -229,4 +296,98 @@ mod test {
 
         assert_eq!(skill, Some(&TrainedSkill::new(2, "Advanced Industry", 5)));
     }
+
+    #[test]
+    fn test_with_raised_skill() {
+        let skills = Skills {
+            skills: vec![TrainedSkill::new(1, "Industry", 3)],
+        };
+
+        let raised = skills
+            .with_raised_skill(1, "Industry")
+            .with_raised_skill(2, "Advanced Industry");
+
+        assert_eq!(
+            raised.skills,
+            vec![
+                TrainedSkill::new(1, "Industry", 4),
+                TrainedSkill::new(2, "Advanced Industry", 1),
+            ]
+        );
+        assert_eq!(skills.trained_level("Industry"), 3);
+    }
+
+    #[test]
+    fn test_missing_skills() {
+        let trained_skills = vec![
+            TrainedSkill::new(1, "Industry", 3),
+            TrainedSkill::new(2, "Advanced Industry", 5),
+        ];
+        let skills = Skills {
+            skills: trained_skills,
+        };
+        let missing_skills = skills.missing_skills(&[
+            RequiredSkill::new(1, "Industry", 4),
+            RequiredSkill::new(2, "Advanced Industry", 5),
+            RequiredSkill::new(3, "Science", 1),
+        ]);
+
+        assert_eq!(
+            missing_skills,
+            vec![
+                MissingSkill {
+                    name: "Industry".to_string(),
+                    required_level: 4,
+                    trained_level: 3,
+                },
+                MissingSkill {
+                    name: "Science".to_string(),
+                    required_level: 1,
+                    trained_level: 0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_job_slots() {
+        let skills = Skills {
+            skills: vec![
+                TrainedSkill::new(3387, "Mass Production", 5),
+                TrainedSkill::new(24625, "Advanced Mass Production", 2),
+                TrainedSkill::new(3406, "Laboratory Operation", 3),
+            ],
+        };
+        let end_date = |hour| Utc.with_ymd_and_hms(2024, 2, 3, hour, 0, 0).unwrap();
+        let jobs = vec![
+            Job::new(
+                IndustryType::Manufacturing,
+                None,
+                1,
+                end_date(5),
+                1,
+                10,
+                None,
+            ),
+            Job::new(IndustryType::Copying, None, 1, end_date(4), 1, 10, None),
+            Job::new(IndustryType::Invention, None, 1, end_date(2), 1, 10, None),
+        ];
+
+        let slots = skills.job_slots(&jobs);
+
+        assert_eq!(slots[0].slot_type, SlotType::Manufacturing);
+        assert_eq!(slots[0].max, 8);
+        assert_eq!(slots[0].free(), 7);
+        assert_eq!(slots[1].slot_type, SlotType::Science);
+        assert_eq!(slots[1].max, 4);
+        assert_eq!(slots[1].used(), 2);
+        assert_eq!(slots[1].end_dates, vec![end_date(2), end_date(4)]);
+        assert_eq!(
+            slots[1].available_from(end_date(3)),
+            vec![end_date(3), end_date(3), end_date(3), end_date(4)]
+        );
+        assert_eq!(slots[2].slot_type, SlotType::Reaction);
+        assert_eq!(slots[2].max, 1);
+        assert_eq!(slots[2].free(), 1);
+    }
 }
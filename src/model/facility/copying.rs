@@ -0,0 +1,309 @@
+use thiserror::Error;
+
+use crate::model::{
+    blueprint::BlueprintCopying, character::Skills, common::DetailedCalculation, common::Named,
+    implants::Implants, industry::IndustryType, prices::Prices,
+};
+
+use super::{
+    science::{self, JobCostDetails, ScienceFacilityProperties},
+    Facility,
+};
+
+#[derive(Debug, Error)]
+#[error("No registered facility named '{name}' supports copying")]
+pub struct CopyingFacilityNotFound {
+    name: String,
+}
+
+pub struct CopyingFacility<'a>(&'a Facility);
+
+impl<'a> CopyingFacility<'a> {
+    pub fn new(facility: &'a Facility) -> Self {
+        Self(facility)
+    }
+
+    pub fn facility(&self) -> &Facility {
+        self.0
+    }
+
+    /// Selects the facility copying the blueprints consumed by invention: the registered facility
+    /// whose name contains `name`, ignoring case, when specified. Otherwise the first facility that
+    /// can both invent and copy, then the first one that can copy. `None` when no registered
+    /// facility can copy.
+    pub fn select(
+        facilities: &'a [Facility],
+        name: Option<&str>,
+    ) -> Result<Option<Self>, CopyingFacilityNotFound> {
+        match name {
+            Some(name) => {
+                let lowercase_name = name.to_lowercase();
+                facilities
+                    .iter()
+                    .filter(|facility| facility.name().to_lowercase().contains(&lowercase_name))
+                    .find_map(|facility| facility.copying())
+                    .map(Some)
+                    .ok_or_else(|| CopyingFacilityNotFound {
+                        name: name.to_string(),
+                    })
+            }
+            None => Ok(facilities
+                .iter()
+                .filter(|facility| facility.support_industry_type(&IndustryType::Invention))
+                .find_map(|facility| facility.copying())
+                .or_else(|| facilities.iter().find_map(|facility| facility.copying()))),
+        }
+    }
+
+    pub fn job_installation_cost(
+        &self,
+        estimated_item_value: f64,
+    ) -> DetailedCalculation<f64, JobCostDetails> {
+        science::job_installation_cost(self.0, &IndustryType::Copying, estimated_item_value, 1.0)
+    }
+
+    /// Science reduces copy time by 5% per level and Advanced Industry by 3% per level, on top of
+    /// the copying implants.
+    pub fn time_per_run(
+        &self,
+        blueprint: &BlueprintCopying,
+        skills: &Skills,
+        implants: &Implants,
+    ) -> i32 {
+        let mut run_time = blueprint.time as f64;
+        let job_duration_modifier = self
+            .0
+            .type_specific_data
+            .job_duration_modifier(&IndustryType::Copying);
+        if let Some(modifier) = job_duration_modifier {
+            run_time *= 1.0 - modifier;
+        }
+
+        run_time *= 1.0 - 0.05 * (skills.trained_level("Science") as f64);
+        run_time *= 1.0 - 0.03 * (skills.trained_level("Advanced Industry") as f64);
+        run_time *= implants.time_modifier(&IndustryType::Copying);
+        run_time as i32
+    }
+
+    /// Copies a single run of the blueprint, which is what an invention job consumes.
+    pub fn copy_blueprint(
+        &self,
+        blueprint: &BlueprintCopying,
+        skills: &Skills,
+        implants: &Implants,
+        prices: &Prices,
+    ) -> DetailedCalculation<CopyingResult, JobCostDetails> {
+        let job_installation_cost =
+            self.job_installation_cost(blueprint.estimated_item_value(prices));
+        DetailedCalculation {
+            value: CopyingResult {
+                facility: self.name(),
+                cost: job_installation_cost.value,
+                time: self.time_per_run(blueprint, skills, implants),
+                implants: implants
+                    .contributing(&IndustryType::Copying)
+                    .iter()
+                    .map(|implant| implant.name())
+                    .collect(),
+            },
+            details: job_installation_cost.details,
+        }
+    }
+}
+
+impl<'a> Named for CopyingFacility<'a> {
+    fn name(&self) -> String {
+        self.0.name()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CopyingResult {
+    pub facility: String,
+    pub cost: f64,
+    /// Science slot time, in seconds.
+    pub time: i32,
+    /// Names of the implants reducing the copy time.
+    pub implants: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Arc};
+
+    use crate::{
+        api::evecache::mocks::MockRequester,
+        model::{
+            blueprint::{Materials, MultipleItems},
+            facility::{playerstructure::PlayerStructureStats, FacilityUsage},
+            implants::Implant,
+            items::{Item, TechLevel},
+            locations::{Constellation, CostIndexes, Region, SolarSystem},
+            prices::ItemPrice,
+            skills::TrainedSkill,
+        },
+        round::Round,
+    };
+
+    use super::*;
+
+    fn create_test_facility() -> Facility {
+        let mut activites = HashMap::new();
+        activites.insert(
+            IndustryType::Copying,
+            PlayerStructureStats {
+                tax_rate: 0.1,
+                job_duration_modifier: Some(0.2),
+                job_cost_modifier: Some(0.5),
+                material_consumption_modifier: None,
+            },
+        );
+        create_test_structure("Test Structure", activites)
+    }
+
+    fn create_test_structure(
+        name: &str,
+        activites: HashMap<IndustryType, PlayerStructureStats>,
+    ) -> Facility {
+        Facility::new_structure(
+            Arc::new(MockRequester::builder().build()),
+            15,
+            name.to_string(),
+            SolarSystem::new(
+                9,
+                "Test Solar System".to_string(),
+                0.1234,
+                vec![8],
+                Constellation::new(
+                    10,
+                    "Test Constellation".to_string(),
+                    vec![9],
+                    Region::new(11, "Test Region", vec![10]),
+                ),
+                CostIndexes {
+                    manufacturing: 0.456,
+                    invention: 0.789,
+                    copying: 0.25,
+                    research_material_efficiency: 0.0,
+                    research_time_efficiency: 0.0,
+                },
+            ),
+            Some(vec![FacilityUsage::Industry]),
+            activites,
+        )
+    }
+
+    #[test]
+    fn test_select() {
+        let stats = PlayerStructureStats {
+            tax_rate: 0.1,
+            job_duration_modifier: None,
+            job_cost_modifier: None,
+            material_consumption_modifier: None,
+        };
+        let facilities = vec![
+            create_test_structure(
+                "Manufacturing Structure",
+                HashMap::from([(IndustryType::Manufacturing, stats.clone())]),
+            ),
+            create_test_structure(
+                "Copying Structure",
+                HashMap::from([(IndustryType::Copying, stats.clone())]),
+            ),
+            create_test_structure(
+                "Laboratory",
+                HashMap::from([
+                    (IndustryType::Invention, stats.clone()),
+                    (IndustryType::Copying, stats.clone()),
+                ]),
+            ),
+        ];
+
+        let default = CopyingFacility::select(&facilities, None).unwrap();
+        assert_eq!(default.unwrap().name(), "Laboratory");
+        let default = CopyingFacility::select(&facilities[..2], None).unwrap();
+        assert_eq!(default.unwrap().name(), "Copying Structure");
+        assert!(CopyingFacility::select(&facilities[..1], None)
+            .unwrap()
+            .is_none());
+
+        let named = CopyingFacility::select(&facilities, Some("copying")).unwrap();
+        assert_eq!(named.unwrap().name(), "Copying Structure");
+        assert!(CopyingFacility::select(&facilities, Some("manufacturing")).is_err());
+    }
+
+    #[test]
+    fn test_job_installation_cost() {
+        let facility = create_test_facility();
+
+        let result = facility.copying().unwrap().job_installation_cost(1000.0);
+
+        assert_eq!(result.details.base_job_cost, 20.0);
+        assert_eq!(result.details.job_cost_modifier, Some(0.5));
+        assert_eq!(result.details.facility_tax, 0.1);
+        // 20 * 0.25 * 0.5 + 20 * 0.1 + 20 * 0.015
+        assert_eq!(result.value.specific_round(2), 4.8);
+    }
+
+    #[test]
+    fn test_copy_blueprint() {
+        let facility = create_test_facility();
+
+        let blueprint = BlueprintCopying {
+            time: 1000,
+            materials: Materials::new(vec![MultipleItems {
+                quantity: 10,
+                item: Item::new(50, "Item 50", None, TechLevel::One),
+            }]),
+        };
+        let skills = Skills {
+            skills: vec![
+                TrainedSkill::new(1, "Science", 4),
+                TrainedSkill::new(2, "Advanced Industry", 5),
+            ],
+        };
+        let mut prices = HashMap::new();
+        prices.insert(50, ItemPrice::new(Some(100.0), None));
+        let prices = Prices { prices };
+
+        let result = facility.copying().unwrap().copy_blueprint(
+            &blueprint,
+            &skills,
+            &Implants::default(),
+            &prices,
+        );
+
+        assert_eq!(result.details.estimated_item_value, 1000.0);
+        assert_eq!(result.value.cost.specific_round(2), 4.8);
+        // 1000 * 0.8 * 0.8 * 0.85
+        assert_eq!(result.value.time, 544);
+        assert_eq!(result.value.facility, "Test Structure");
+        assert!(result.value.implants.is_empty());
+    }
+
+    #[test]
+    fn test_time_per_run_with_implants() {
+        let facility = create_test_facility();
+
+        let blueprint = BlueprintCopying {
+            time: 1000,
+            materials: Materials::new(vec![]),
+        };
+        let implants = Implants {
+            implants: vec![Implant::new(
+                27170,
+                "Zainou 'Beancounter' Science SC-805",
+                HashMap::from([(IndustryType::Copying, 0.05)]),
+            )],
+        };
+
+        let result = facility.copying().unwrap().time_per_run(
+            &blueprint,
+            &Skills { skills: vec![] },
+            &implants,
+        );
+
+        // 1000 * 0.8 * 0.95
+        assert_eq!(result, 760);
+    }
+}
@@ -1,23 +1,21 @@
-use crate::{
-    logging,
-    model::{
-        blueprint::{BlueprintInvention, BlueprintManufacturing, InputMaterialsCostsDetails},
-        character::ManufacturingSkills,
-        character::Skills,
-        common::DetailedCalculation,
-        common::{Identified, Named},
-        prices::Prices,
-        skills::TrainedSkill,
-    },
+use crate::logging;
+use crate::model::{
+    blueprint::{BlueprintInvention, BlueprintManufacturing, InputMaterialsCostsDetails},
+    character::ManufacturingSkills,
+    character::Skills,
+    common::DetailedCalculation,
+    common::{Identified, Named},
+    implants::Implants,
+    industry::IndustryType,
+    prices::Prices,
+    skills::TrainedSkill,
 };
 
-use super::{Facility, FacilityType};
-
-pub trait InventionFacilityProperties {
-    fn invention_tax(&self) -> f64;
-    fn job_cost_modifier(&self) -> Option<f64>;
-    fn job_duration_modifier(&self) -> Option<f64>;
-}
+use super::{
+    copying::{CopyingFacility, CopyingResult},
+    science::{self, JobCostDetails, ScienceFacilityProperties},
+    Facility,
+};
 
 pub struct InventionFacility<'a>(&'a Facility);
 
@@ -30,40 +28,29 @@ impl<'a> InventionFacility<'a> {
         self.0
     }
 
-    pub fn job_installation_cost(
-        &self,
-        estimated_item_value: f64,
-    ) -> DetailedCalculation<f64, JobInventionCostDetails> {
-        let base_job_cost = 0.02 * estimated_item_value;
-        logging::trace!("Base job cost: {}", base_job_cost);
-        let mut gross_cost = base_job_cost * self.0.location.indexes.invention;
-        logging::trace!("Gross Cost: {}", gross_cost);
-        if let Some(modifier) = self.job_cost_modifier() {
-            gross_cost -= gross_cost * modifier;
-            logging::trace!("Gross Cost (Modifier): {}", gross_cost);
-        }
-
-        let mut tax = base_job_cost * self.invention_tax();
-        logging::trace!("Tax: {}", tax);
+    pub fn invention_tax(&self) -> f64 {
+        self.0
+            .type_specific_data
+            .science_tax(&IndustryType::Invention)
+    }
 
-        // Fixed amount
-        // https://wiki.eveuniversity.org/Manufacturing
-        // https://www.eveonline.com/news/view/patch-notes-version-21-05-2
-        static SCC_SURCHARGE: f64 = 0.015;
+    pub fn job_cost_modifier(&self) -> Option<f64> {
+        self.0
+            .type_specific_data
+            .job_cost_modifier(&IndustryType::Invention)
+    }
 
-        tax += base_job_cost * SCC_SURCHARGE;
-        logging::trace!("Tax (SCC): {}", tax);
+    pub fn job_duration_modifier(&self) -> Option<f64> {
+        self.0
+            .type_specific_data
+            .job_duration_modifier(&IndustryType::Invention)
+    }
 
-        DetailedCalculation {
-            value: gross_cost + tax,
-            details: JobInventionCostDetails {
-                estimated_item_value,
-                base_job_cost,
-                job_cost_modifier: self.job_cost_modifier(),
-                facility_invention_tax: self.invention_tax(),
-                scc_surcharge: SCC_SURCHARGE,
-            },
-        }
+    pub fn job_installation_cost(
+        &self,
+        estimated_item_value: f64,
+    ) -> DetailedCalculation<f64, JobCostDetails> {
+        science::job_installation_cost(self.0, &IndustryType::Invention, estimated_item_value, 1.0)
     }
 
     pub fn time_per_run(
@@ -95,6 +82,8 @@ impl<'a> InventionFacility<'a> {
         }
     }
 
+    /// Science skills add 1/30 of the base chance per level and the encryption skill (including the
+    /// Sleeper one used to reverse engineer relics) 1/40 per level.
     pub async fn success_probability(
         &self,
         invention_blueprint: &BlueprintInvention,
@@ -138,22 +127,86 @@ impl<'a> InventionFacility<'a> {
         }
     }
 
+    /// Invents the manufacturing blueprint through each of its invention sources, or only through
+    /// `source_id` when specified, and keeps the cheapest per invented run. Relics the character
+    /// lacks the skills to reverse engineer are skipped.
+    pub async fn cheapest_invention(
+        &self,
+        manufacturing_blueprint: &BlueprintManufacturing,
+        source_id: Option<i32>,
+        copying_facility: Option<&CopyingFacility<'_>>,
+        skills: &Skills,
+        implants: &Implants,
+        prices: &Prices,
+    ) -> Option<DetailedCalculation<InventionResult, InventionDetails>> {
+        let mut cheapest: Option<DetailedCalculation<InventionResult, InventionDetails>> = None;
+        for invention_blueprint in &manufacturing_blueprint.invention_blueprint {
+            if let Some(source_id) = source_id {
+                if source_id != invention_blueprint.blueprint_id {
+                    continue;
+                }
+            }
+            if let Some(relic) = &invention_blueprint.relic {
+                let missing_skills = skills.missing_skills(&invention_blueprint.skills);
+                if !missing_skills.is_empty() {
+                    logging::debug!(
+                        "Skipped relic {}, missing skills: {:?}",
+                        relic.name(),
+                        missing_skills
+                    );
+                    continue;
+                }
+            }
+            let result = self
+                .invent_item(
+                    manufacturing_blueprint,
+                    invention_blueprint,
+                    copying_facility,
+                    skills,
+                    implants,
+                    prices,
+                )
+                .await;
+            cheapest = match cheapest {
+                Some(c) if c.value.cost_per_run() <= result.value.cost_per_run() => Some(c),
+                _ => Some(result),
+            };
+        }
+        cheapest
+    }
+
+    /// When a copying facility is given, the copy job producing the consumed blueprint copy is
+    /// added to the cost and the time of each attempt.
     pub async fn invent_item(
         &self,
         manufacturing_blueprint: &BlueprintManufacturing,
+        invention_blueprint: &BlueprintInvention,
+        copying_facility: Option<&CopyingFacility<'_>>,
         skills: &Skills,
+        implants: &Implants,
         prices: &Prices,
     ) -> DetailedCalculation<InventionResult, InventionDetails> {
-        let invention_blueprint: &BlueprintInvention =
-            &manufacturing_blueprint.invention_blueprint[0];
         let input_materials_cost = invention_blueprint
-            .materials
-            .input_materials_cost(None, None, prices);
+            .input_materials()
+            .input_materials_cost(None, None, 1, prices);
         let estimated_item_value = manufacturing_blueprint.estimated_item_value(prices);
         let job_installation_cost = self.job_installation_cost(estimated_item_value);
-        let cost_per_run = job_installation_cost.value + input_materials_cost.value;
         let time_per_run =
             self.time_per_run(invention_blueprint, &skills.get_manufacturing_skill());
+        let copying = match (copying_facility, &invention_blueprint.copying) {
+            (Some(facility), Some(blueprint)) => Some(
+                facility
+                    .copy_blueprint(blueprint, skills, implants, prices)
+                    .value,
+            ),
+            _ => None,
+        };
+        let mut cost_per_run = job_installation_cost.value + input_materials_cost.value;
+        let mut time = time_per_run.value;
+        if let Some(copying) = &copying {
+            cost_per_run += copying.cost;
+            time += copying.time;
+        }
         let success_probability = self
             .success_probability(
                 invention_blueprint,
@@ -172,9 +225,10 @@ impl<'a> InventionFacility<'a> {
         DetailedCalculation {
             value: InventionResult {
                 cost_normalized: cost_per_run * normalization_factor,
-                time_normalized: (time_per_run.value as f64) * normalization_factor,
+                time_normalized: (time as f64) * normalization_factor,
                 blueprint: InventionResultBlueprint {
                     id: manufacturing_blueprint.blueprint_id,
+                    source_id: invention_blueprint.blueprint_id,
                     runs: nb_runs,
                     material_efficiency: 2,
                     time_efficiency: 4,
@@ -187,50 +241,19 @@ impl<'a> InventionFacility<'a> {
                     total: cost_per_run,
                 },
                 time: time_per_run.details,
+                copying,
                 success_probability: success_probability.details,
             },
         }
     }
 }
 
-impl<'a> InventionFacilityProperties for InventionFacility<'a> {
-    fn invention_tax(&self) -> f64 {
-        match &self.0.type_specific_data {
-            FacilityType::Station(s) => s.invention_tax(),
-            FacilityType::Structure(s) => s.invention_tax(),
-        }
-    }
-
-    fn job_cost_modifier(&self) -> Option<f64> {
-        match &self.0.type_specific_data {
-            FacilityType::Station(s) => InventionFacilityProperties::job_cost_modifier(s),
-            FacilityType::Structure(s) => InventionFacilityProperties::job_cost_modifier(s),
-        }
-    }
-
-    fn job_duration_modifier(&self) -> Option<f64> {
-        match &self.0.type_specific_data {
-            FacilityType::Station(s) => InventionFacilityProperties::job_duration_modifier(s),
-            FacilityType::Structure(s) => InventionFacilityProperties::job_duration_modifier(s),
-        }
-    }
-}
-
 impl<'a> Named for InventionFacility<'a> {
     fn name(&self) -> String {
         self.0.name()
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct JobInventionCostDetails {
-    pub estimated_item_value: f64,
-    pub base_job_cost: f64,
-    pub job_cost_modifier: Option<f64>,
-    pub facility_invention_tax: f64,
-    pub scc_surcharge: f64,
-}
-
 #[derive(Debug)]
 pub struct InventionResult {
     pub blueprint: InventionResultBlueprint,
@@ -238,9 +261,22 @@ pub struct InventionResult {
     pub time_normalized: f64,
 }
 
+impl InventionResult {
+    pub fn cost_per_run(&self) -> f64 {
+        self.cost_normalized / (self.blueprint.runs as f64)
+    }
+
+    /// Science slot time spent inventing a single run of the manufacturing blueprint, in seconds.
+    pub fn time_per_run(&self) -> f64 {
+        self.time_normalized / (self.blueprint.runs as f64)
+    }
+}
+
 #[derive(Debug)]
 pub struct InventionResultBlueprint {
     pub id: i32,
+    /// Blueprint the invention job is started from.
+    pub source_id: i32,
     pub runs: i32,
     pub material_efficiency: u8,
     pub time_efficiency: u8,
@@ -250,13 +286,15 @@ pub struct InventionResultBlueprint {
 pub struct InventionDetails {
     pub cost: CostsDetails,
     pub time: TimeDetails,
+    /// Copy job of the consumed blueprint copy, already included in the cost and time.
+    pub copying: Option<CopyingResult>,
     pub success_probability: SuccessProbabilityDetails,
 }
 
 #[derive(Debug, Clone)]
 pub struct CostsDetails {
     pub inputs: InputMaterialsCostsDetails,
-    pub job_cost: JobInventionCostDetails,
+    pub job_cost: JobCostDetails,
     pub total: f64,
 }
 
@@ -281,13 +319,13 @@ mod tests {
     use crate::{
         api::evecache::mocks::MockRequester,
         model::{
-            blueprint::{Materials, MultipleItems, ProbableMultipleItems},
+            blueprint::{BlueprintCopying, Materials, MultipleItems, ProbableMultipleItems},
             facility::{playerstructure::PlayerStructureStats, FacilityUsage},
             industry::IndustryType,
             items::{Item, TechLevel},
             locations::{Constellation, CostIndexes, Region, SolarSystem},
             prices::ItemPrice,
-            skills::Skill,
+            skills::RequiredSkill,
         },
         round::Round,
     };
@@ -323,6 +361,9 @@ mod tests {
                 CostIndexes {
                     manufacturing: 0.456,
                     invention: 0.789,
+                    copying: 0.0,
+                    research_material_efficiency: 0.0,
+                    research_time_efficiency: 0.0,
                 },
             ),
             Some(vec![FacilityUsage::Industry]),
@@ -346,7 +387,7 @@ mod tests {
         assert_eq!(result.details.estimated_item_value, estimated_item_value);
         assert_eq!(result.details.base_job_cost, 2.468);
         assert_eq!(result.details.job_cost_modifier, Some(3.0));
-        assert_eq!(result.details.facility_invention_tax, 0.32);
+        assert_eq!(result.details.facility_tax, 0.32);
         assert_eq!(result.details.scc_surcharge, 0.015);
     }
 
@@ -363,11 +404,13 @@ mod tests {
                 item: Item::new(10, "test", None, TechLevel::One),
             }],
             skills: vec![
-                Skill::new(10, "Skill1"),
-                Skill::new(11, "Skill2"),
-                Skill::new(12, "Skill3"),
+                RequiredSkill::new(10, "Skill1", 1),
+                RequiredSkill::new(11, "Skill2", 1),
+                RequiredSkill::new(12, "Skill3", 1),
             ],
             time: 100,
+            relic: None,
+            copying: None,
         };
 
         let skills = ManufacturingSkills {
@@ -396,11 +439,13 @@ mod tests {
                 item: Item::new(10, "test", None, TechLevel::One),
             }],
             skills: vec![
-                Skill::new(10, "Skill1"),
-                Skill::new(11, "Skill2"),
-                Skill::new(12, "Skill3 Encryption Methods"),
+                RequiredSkill::new(10, "Skill1", 1),
+                RequiredSkill::new(11, "Skill2", 1),
+                RequiredSkill::new(12, "Skill3 Encryption Methods", 1),
             ],
             time: 100,
+            relic: None,
+            copying: None,
         };
 
         let skills = Skills {
@@ -430,9 +475,12 @@ mod tests {
             blueprint_id: 10,
             materials: Materials::new(vec![]),
             products: vec![],
+            skills: vec![],
             material_efficiency: 0,
             time_efficiency: 0,
             time: 0,
+            max_production_limit: 1,
+            runs: 1,
             invention_blueprint: vec![BlueprintInvention {
                 blueprint_id: 1,
                 materials: Materials::new(vec![
@@ -451,11 +499,13 @@ mod tests {
                     item: Item::new(10, "test", None, TechLevel::One),
                 }],
                 skills: vec![
-                    Skill::new(10, "Skill1"),
-                    Skill::new(11, "Skill2"),
-                    Skill::new(12, "Skill3 Encryption Methods"),
+                    RequiredSkill::new(10, "Skill1", 1),
+                    RequiredSkill::new(11, "Skill2", 1),
+                    RequiredSkill::new(12, "Skill3 Encryption Methods", 1),
                 ],
                 time: 100,
+                relic: None,
+                copying: None,
             }],
         };
 
@@ -477,7 +527,14 @@ mod tests {
         let result = facility
             .invention()
             .unwrap()
-            .invent_item(&blueprint, &skills, &prices)
+            .invent_item(
+                &blueprint,
+                &blueprint.invention_blueprint[0],
+                None,
+                &skills,
+                &Implants::default(),
+                &prices,
+            )
             .await;
 
         assert_eq!(result.details.cost.total, 32.0);
@@ -491,6 +548,247 @@ mod tests {
         assert_eq!(result.value.blueprint.material_efficiency, 2);
         assert_eq!(result.value.blueprint.time_efficiency, 4);
         assert_eq!(result.value.blueprint.id, 10);
+        assert_eq!(result.value.blueprint.source_id, 1);
         assert_eq!(result.value.blueprint.runs, 2);
     }
+
+    #[tokio::test]
+    async fn test_invent_item_with_copying() {
+        let facility = create_test_facility();
+        let copying_facility = Facility::new_station(
+            Arc::new(MockRequester::builder().build()),
+            8,
+            "Test Station".to_string(),
+            SolarSystem::new(
+                1,
+                "Test Solar System".to_string(),
+                0.9,
+                vec![8],
+                Constellation::new(
+                    2,
+                    "Test Constellation".to_string(),
+                    vec![1],
+                    Region::new(3, "Test Region", vec![2]),
+                ),
+                CostIndexes {
+                    manufacturing: 0.0,
+                    invention: 0.0,
+                    copying: 0.1,
+                    research_material_efficiency: 0.0,
+                    research_time_efficiency: 0.0,
+                },
+            ),
+            Some(vec![FacilityUsage::Industry]),
+        );
+
+        let blueprint = BlueprintManufacturing {
+            blueprint_id: 10,
+            materials: Materials::new(vec![]),
+            products: vec![],
+            skills: vec![],
+            material_efficiency: 0,
+            time_efficiency: 0,
+            time: 0,
+            max_production_limit: 1,
+            runs: 1,
+            invention_blueprint: vec![BlueprintInvention {
+                blueprint_id: 1,
+                materials: Materials::new(vec![
+                    MultipleItems {
+                        quantity: 10,
+                        item: Item::new(50, "Item 50", None, TechLevel::One),
+                    },
+                    MultipleItems {
+                        quantity: 2,
+                        item: Item::new(51, "Item 51", None, TechLevel::One),
+                    },
+                ]),
+                products: vec![ProbableMultipleItems {
+                    quantity: 2,
+                    base_probability: Some(0.3),
+                    item: Item::new(10, "test", None, TechLevel::One),
+                }],
+                skills: vec![
+                    RequiredSkill::new(10, "Skill1", 1),
+                    RequiredSkill::new(11, "Skill2", 1),
+                    RequiredSkill::new(12, "Skill3 Encryption Methods", 1),
+                ],
+                time: 100,
+                relic: None,
+                copying: Some(BlueprintCopying {
+                    time: 200,
+                    materials: Materials::new(vec![MultipleItems {
+                        quantity: 1,
+                        item: Item::new(60, "Item 60", None, TechLevel::One),
+                    }]),
+                }),
+            }],
+        };
+
+        let skills = Skills {
+            skills: vec![
+                TrainedSkill::new(10, "Skill1", 3),
+                TrainedSkill::new(11, "Skill2", 1),
+                TrainedSkill::new(12, "Skill3 Encryption Methods", 5),
+                TrainedSkill::new(13, "Industry", 4),
+                TrainedSkill::new(14, "Advanced Industry", 3),
+            ],
+        };
+
+        let mut prices = HashMap::new();
+        prices.insert(50, ItemPrice::new(None, Some(2.5)));
+        prices.insert(51, ItemPrice::new(None, Some(3.5)));
+        prices.insert(60, ItemPrice::new(Some(1000.0), None));
+        let prices = Prices { prices };
+
+        let result = facility
+            .invention()
+            .unwrap()
+            .invent_item(
+                &blueprint,
+                &blueprint.invention_blueprint[0],
+                copying_facility.copying().as_ref(),
+                &skills,
+                &Implants::default(),
+                &prices,
+            )
+            .await;
+
+        let copying = result.details.copying.unwrap();
+        assert_eq!(copying.facility, "Test Station");
+        assert_eq!(copying.cost.specific_round(2), 2.35);
+        assert_eq!(copying.time, 182);
+        assert_eq!(result.details.cost.total.specific_round(2), 34.35);
+        assert_eq!(result.value.cost_normalized.specific_round(2), 90.99);
+    }
+
+    #[tokio::test]
+    async fn test_cheapest_invention() {
+        let facility = create_test_facility();
+
+        let invention_blueprint = |blueprint_id: i32, material_quantity: i32| BlueprintInvention {
+            blueprint_id,
+            materials: Materials::new(vec![MultipleItems {
+                quantity: material_quantity,
+                item: Item::new(50, "Item 50", None, TechLevel::One),
+            }]),
+            products: vec![ProbableMultipleItems {
+                quantity: 1,
+                base_probability: Some(0.3),
+                item: Item::new(10, "test", None, TechLevel::One),
+            }],
+            skills: vec![],
+            time: 100,
+            relic: None,
+            copying: None,
+        };
+        let blueprint = BlueprintManufacturing {
+            blueprint_id: 10,
+            materials: Materials::new(vec![]),
+            products: vec![],
+            skills: vec![],
+            material_efficiency: 0,
+            time_efficiency: 0,
+            time: 0,
+            max_production_limit: 1,
+            runs: 1,
+            invention_blueprint: vec![
+                invention_blueprint(1, 10),
+                invention_blueprint(2, 5),
+                invention_blueprint(3, 20),
+            ],
+        };
+
+        let skills = Skills { skills: vec![] };
+        let implants = Implants::default();
+
+        let mut prices = HashMap::new();
+        prices.insert(50, ItemPrice::new(None, Some(2.5)));
+        let prices = Prices { prices };
+
+        let invention = facility.invention().unwrap();
+        let cheapest = invention
+            .cheapest_invention(&blueprint, None, None, &skills, &implants, &prices)
+            .await
+            .unwrap();
+        assert_eq!(cheapest.value.blueprint.source_id, 2);
+
+        let selected = invention
+            .cheapest_invention(&blueprint, Some(3), None, &skills, &implants, &prices)
+            .await
+            .unwrap();
+        assert_eq!(selected.value.blueprint.source_id, 3);
+
+        let unknown = invention
+            .cheapest_invention(&blueprint, Some(4), None, &skills, &implants, &prices)
+            .await;
+        assert!(unknown.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cheapest_invention_relic_requires_skills() {
+        let facility = create_test_facility();
+
+        let blueprint = BlueprintManufacturing {
+            blueprint_id: 10,
+            materials: Materials::new(vec![]),
+            products: vec![],
+            skills: vec![],
+            material_efficiency: 0,
+            time_efficiency: 0,
+            time: 0,
+            max_production_limit: 1,
+            runs: 1,
+            invention_blueprint: vec![BlueprintInvention {
+                blueprint_id: 30,
+                materials: Materials::new(vec![]),
+                products: vec![ProbableMultipleItems {
+                    quantity: 20,
+                    base_probability: Some(0.26),
+                    item: Item::new(10, "test", None, TechLevel::Three),
+                }],
+                skills: vec![
+                    RequiredSkill::new(3409, "Mechanical Engineering", 1),
+                    RequiredSkill::new(21790, "Sleeper Encryption Methods", 1),
+                ],
+                time: 3600,
+                relic: Some(Item::new(30, "Intact Hull Section", None, TechLevel::One)),
+                copying: None,
+            }],
+        };
+
+        let implants = Implants::default();
+        let mut prices = HashMap::new();
+        prices.insert(30, ItemPrice::new(None, Some(1000.0)));
+        let prices = Prices { prices };
+
+        let invention = facility.invention().unwrap();
+        let untrained = Skills {
+            skills: vec![TrainedSkill::new(3409, "Mechanical Engineering", 4)],
+        };
+        let skipped = invention
+            .cheapest_invention(&blueprint, None, None, &untrained, &implants, &prices)
+            .await;
+        assert!(skipped.is_none());
+
+        let trained = Skills {
+            skills: vec![
+                TrainedSkill::new(3409, "Mechanical Engineering", 4),
+                TrainedSkill::new(21790, "Sleeper Encryption Methods", 2),
+            ],
+        };
+        let invented = invention
+            .cheapest_invention(&blueprint, None, None, &trained, &implants, &prices)
+            .await
+            .unwrap();
+        assert_eq!(invented.value.blueprint.source_id, 30);
+        assert_eq!(
+            invented
+                .details
+                .success_probability
+                .final_success_chance
+                .specific_round(4),
+            0.3077
+        );
+    }
 }
@@ -7,14 +7,16 @@ use crate::{
     model::{
         blueprint::BlueprintManufacturing,
         character::{ManufacturingSkills, Skills},
-        common::Named,
-        items::TechLevel,
+        common::{DetailedCalculation, Named},
+        implants::Implants,
+        industry::IndustryType,
+        markets::sell_order_fees,
         prices::Prices,
     },
     vector::{UnicityError, UniqueElement},
 };
 
-use super::{invention::InventionFacility, Facility, FacilityType};
+use super::{copying::CopyingFacility, invention::InventionFacility, Facility, FacilityType};
 
 #[derive(Debug, Error)]
 pub enum ManufactureError {
@@ -25,6 +27,16 @@ pub enum ManufactureError {
     },
 }
 
+#[derive(Debug, Clone)]
+pub struct ManufactureCostDetails {
+    /// Science slot time spent inventing a single run, in seconds, for invented blueprints.
+    pub invention_time_per_run: Option<f64>,
+    pub materials_per_run: f64,
+    pub installation_per_run: f64,
+    /// Invention cost spread over the runs of the invented copy, for invented blueprints.
+    pub invention_per_run: f64,
+}
+
 pub trait ManufacturingFacility {
     fn manufacturing_tax(&self) -> f64;
     fn job_cost_modifier(&self) -> Option<f64>;
@@ -43,12 +55,15 @@ impl<'a> Manufacture<'a> {
         &self,
         blueprint: &BlueprintManufacturing,
         skills: &Skills,
+        implants: &Implants,
         inventions_facilities: &Vec<InventionFacility<'_>>,
+        copying_facility: Option<&CopyingFacility<'_>>,
         prices: &Prices,
-    ) -> Result<f64, ManufactureError> {
+    ) -> Result<DetailedCalculation<f64, ManufactureCostDetails>, ManufactureError> {
         let mut blueprint = blueprint.clone();
 
         let mut blueprint_run_price = 0.0;
+        let mut invention_time_per_run = None;
         let item = &blueprint
             .products
             .unique_ref("Only unique products supported")
@@ -57,37 +72,58 @@ impl<'a> Manufacture<'a> {
                 source,
             })?
             .item;
-        if TechLevel::Two == item.tech_level {
+        if item.tech_level.is_invented() {
             let mut futures = vec![];
             for invention in inventions_facilities {
-                futures.push(invention.invent_item(&blueprint, skills, prices));
+                futures.push(invention.cheapest_invention(
+                    &blueprint,
+                    None,
+                    copying_facility,
+                    skills,
+                    implants,
+                    prices,
+                ));
             }
             let all_futures = futures.into_iter().collect::<JoinAll<_>>();
             let invention_result = join!(all_futures).0;
-            let cheapest_invention_result = invention_result.iter().reduce(|acc, res| {
-                if acc.value.cost_normalized > res.value.cost_normalized {
+            let cheapest_invention_result = invention_result.iter().flatten().reduce(|acc, res| {
+                if acc.value.cost_per_run() > res.value.cost_per_run() {
                     res
                 } else {
                     acc
                 }
             });
             if let Some(res) = cheapest_invention_result {
-                let total_per_run = res.value.cost_normalized / (res.value.blueprint.runs as f64);
+                let total_per_run = res.value.cost_per_run();
 
                 blueprint.material_efficiency = res.value.blueprint.material_efficiency;
                 blueprint.time_efficiency = res.value.blueprint.time_efficiency;
+                // Jobs can't run more times than the invented copy allows
+                blueprint.runs = blueprint.runs.min(res.value.blueprint.runs);
 
                 blueprint_run_price = total_per_run;
+                invention_time_per_run = Some(res.value.time_per_run());
             }
         }
 
+        let runs = blueprint.runs as f64;
         let material_costs = blueprint.materials.input_materials_cost(
             self.material_consumption_modifier(),
             Some(blueprint.material_efficiency),
+            blueprint.runs,
             prices,
         );
-        let installation_cost = self.job_installation_cost(blueprint.estimated_item_value(prices));
-        Ok(material_costs.value + installation_cost + blueprint_run_price)
+        let installation_cost =
+            self.job_installation_cost(blueprint.estimated_item_value(prices) * runs);
+        Ok(DetailedCalculation {
+            value: (material_costs.value + installation_cost) / runs + blueprint_run_price,
+            details: ManufactureCostDetails {
+                invention_time_per_run,
+                materials_per_run: material_costs.value / runs,
+                installation_per_run: installation_cost / runs,
+                invention_per_run: blueprint_run_price,
+            },
+        })
     }
 
     pub fn job_installation_cost(&self, estimated_item_value: f64) -> f64 {
@@ -112,10 +148,46 @@ impl<'a> Manufacture<'a> {
         gross_cost + tax
     }
 
+    /// Profits per hour of manufacturing the product of the blueprint in jobs of `blueprint.runs`
+    /// runs, given the `cost_per_run` computed by `manufacture_cost_per_run`, and selling each unit
+    /// at `sell_price` through a sell order. `None` when the job takes no time.
+    pub fn profits_per_hour(
+        &self,
+        blueprint: &BlueprintManufacturing,
+        product_id: i32,
+        cost_per_run: f64,
+        sell_price: f64,
+        skills: &Skills,
+        implants: &Implants,
+    ) -> Option<f64> {
+        let output_quantity = blueprint
+            .get_product(product_id)
+            .map_or(1, |product| product.quantity) as f64;
+        let time_per_job =
+            self.time_per_job(blueprint, &skills.get_manufacturing_skill(), implants);
+        if time_per_job <= 0 {
+            return None;
+        }
+        let time_per_unit = time_per_job as f64 / (blueprint.runs as f64) / output_quantity;
+        let margin = sell_price * (1.0 - sell_order_fees(skills)) - cost_per_run / output_quantity;
+        Some(margin * 3600.0 / time_per_unit)
+    }
+
     pub fn time_per_run(
         &self,
         blueprint_manufacturing: &BlueprintManufacturing,
         skills: &ManufacturingSkills,
+        implants: &Implants,
+    ) -> i32 {
+        self.time_per_job(blueprint_manufacturing, skills, implants) / blueprint_manufacturing.runs
+    }
+
+    /// Duration of a job of `blueprint.runs` runs, rounded for the whole job like in game.
+    pub fn time_per_job(
+        &self,
+        blueprint_manufacturing: &BlueprintManufacturing,
+        skills: &ManufacturingSkills,
+        implants: &Implants,
     ) -> i32 {
         let time_efficiency_normalized =
             1.0 - (blueprint_manufacturing.time_efficiency as f64) / 100.0;
@@ -139,7 +211,8 @@ impl<'a> Manufacture<'a> {
         let skill_industry_modifier = 0.04 * (industry_level as f64);
         let skill_advanced_industry_modifier = 0.03 * (advanced_industry_level as f64);
         run_time *= (1.0 - skill_industry_modifier) * (1.0 - skill_advanced_industry_modifier);
-        run_time as i32
+        run_time *= implants.time_modifier(&IndustryType::Manufacturing);
+        (run_time * (blueprint_manufacturing.runs as f64)) as i32
     }
 
     pub fn facility(&self) -> &Facility {
@@ -191,12 +264,15 @@ mod tests {
         api::evecache::mocks::MockRequester,
         model::{
             blueprint::{BlueprintInvention, Materials, MultipleItems, ProbableMultipleItems},
-            facility::{playerstructure::PlayerStructureStats, FacilityUsage},
+            facility::{
+                playerstructure::{PlayerStructure, PlayerStructureStats},
+                FacilityUsage,
+            },
             industry::IndustryType,
-            items::Item,
+            items::{Item, TechLevel},
             locations::{Constellation, CostIndexes, Region, SolarSystem},
             prices::ItemPrice,
-            skills::{Skill, TrainedSkill},
+            skills::{RequiredSkill, TrainedSkill},
         },
         round::Round,
     };
@@ -241,6 +317,9 @@ mod tests {
                 CostIndexes {
                     manufacturing: 0.456,
                     invention: 0.789,
+                    copying: 0.0,
+                    research_material_efficiency: 0.0,
+                    research_time_efficiency: 0.0,
                 },
             ),
             Some(vec![FacilityUsage::Industry]),
@@ -266,9 +345,12 @@ mod tests {
                 quantity: 1,
                 item: Item::new(200, "Item 101", None, TechLevel::Two),
             }],
+            skills: vec![],
             material_efficiency: 5,
             time_efficiency: 8,
             time: 154,
+            max_production_limit: 1,
+            runs: 1,
             invention_blueprint: vec![BlueprintInvention {
                 blueprint_id: 1,
                 materials: Materials::new(vec![
@@ -287,11 +369,13 @@ mod tests {
                     item: Item::new(10, "test", None, TechLevel::One),
                 }],
                 skills: vec![
-                    Skill::new(10, "Skill1"),
-                    Skill::new(11, "Skill2"),
-                    Skill::new(12, "Skill3 Encryption Methods"),
+                    RequiredSkill::new(10, "Skill1", 1),
+                    RequiredSkill::new(11, "Skill2", 1),
+                    RequiredSkill::new(12, "Skill3 Encryption Methods", 1),
                 ],
                 time: 100,
+                relic: None,
+                copying: None,
             }],
         }
     }
@@ -321,11 +405,47 @@ mod tests {
                 industry: Some(TrainedSkill::new(10, "Industry", 5)),
                 advanced_industry_level: Some(TrainedSkill::new(11, "Advanced Industry", 3)),
             },
+            &Implants::default(),
         );
 
         assert_eq!(result, -412);
     }
 
+    #[test]
+    fn test_profits_per_hour() {
+        let mut activites = HashMap::new();
+        activites.insert(
+            IndustryType::Manufacturing,
+            PlayerStructureStats {
+                tax_rate: 0.0,
+                job_duration_modifier: None,
+                job_cost_modifier: None,
+                material_consumption_modifier: None,
+            },
+        );
+        let mut facility = create_test_facility();
+        facility.type_specific_data = FacilityType::Structure(PlayerStructure::new(1, activites));
+        let mut blueprint = create_blueprint();
+        blueprint.time = 3600;
+        blueprint.time_efficiency = 0;
+        blueprint.max_production_limit = 10;
+        blueprint.set_runs(10);
+        blueprint.products[0].quantity = 2;
+        let skills = Skills { skills: vec![] };
+
+        let result = facility.manufacture().unwrap().profits_per_hour(
+            &blueprint,
+            200,
+            100.0,
+            1000.0,
+            &skills,
+            &Implants::default(),
+        );
+
+        // Two units per hour, sold for 1000 minus 10.5% of fees, each costing 50
+        assert_eq!(result.unwrap().specific_round(2), 1690.0);
+    }
+
     #[tokio::test]
     async fn test_manufacture_cost_per_run() {
         let facility = create_test_facility();
@@ -354,12 +474,15 @@ mod tests {
             .manufacture_cost_per_run(
                 &blueprint,
                 &skills,
+                &Implants::default(),
                 &vec![facility.invention().unwrap()],
+                None,
                 &prices,
             )
             .await
             .unwrap();
 
-        assert_eq!(result.specific_round(2), -3795.62);
+        // Modifiers above 100% would consume negative quantities, a run consumes at least one unit
+        assert_eq!(result.value.specific_round(2), 79.38);
     }
 }
@@ -5,7 +5,7 @@ use rfesi::{groups::MarketOrder, prelude::EsiError};
 use thiserror::Error;
 
 use crate::{
-    api::evecache::cache::CacheError,
+    api::evecache::{cache::CacheError, cache_keys::OrderType},
     dates::NaivePeriod,
     model::{
         common::{Identified, Named},
@@ -314,6 +314,33 @@ impl RegionOrders {
             .unwrap_or(0.0)
     }
 
+    pub fn station_sell_orders_count(&self, type_id: i32, facility_id: i64) -> usize {
+        self.orders_for(type_id, OrdersRange::Station, Some(facility_id))
+            .iter()
+            .filter(|s| !s.is_buy_order)
+            .count()
+    }
+
+    /// Orders of the item at the station, best price first: the lowest sell or the highest buy
+    /// prices.
+    pub fn station_orders(
+        &self,
+        type_id: i32,
+        facility_id: i64,
+        order_type: OrderType,
+    ) -> Vec<&MarketOrder> {
+        let mut orders: Vec<&MarketOrder> = self
+            .orders_for(type_id, OrdersRange::Station, Some(facility_id))
+            .into_iter()
+            .filter(|o| o.is_buy_order == (order_type == OrderType::Buy))
+            .collect();
+        match order_type {
+            OrderType::Sell => orders.sort_by(|a, b| a.price.total_cmp(&b.price)),
+            OrderType::Buy => orders.sort_by(|a, b| b.price.total_cmp(&a.price)),
+        }
+        orders
+    }
+
     fn orders_for(
         &self,
         type_id: i32,
@@ -366,6 +393,9 @@ mod test {
                 CostIndexes {
                     manufacturing: 0.456,
                     invention: 0.789,
+                    copying: 0.0,
+                    research_material_efficiency: 0.0,
+                    research_time_efficiency: 0.0,
                 },
             ),
             Some(vec![FacilityUsage::Market, FacilityUsage::Industry]),
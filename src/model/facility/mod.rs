@@ -11,19 +11,24 @@ use crate::api::evecache::MarketTraits;
 use crate::model::common::{Identified, Named};
 use crate::model::locations::SolarSystem;
 
+use self::copying::CopyingFacility;
 use self::invention::InventionFacility;
 use self::manufacture::Manufacture;
 use self::markets::Market;
 use self::npcstation::NPCStation;
 use self::playerstructure::{PlayerStructure, PlayerStructureStats};
+use self::research::ResearchFacility;
 
 use super::industry::IndustryType;
 
+pub mod copying;
 pub mod invention;
 pub mod manufacture;
 pub mod markets;
 pub mod npcstation;
 pub mod playerstructure;
+pub mod research;
+pub mod science;
 
 #[derive(Clone)]
 pub struct Facility {
@@ -137,6 +142,23 @@ impl Facility {
             None
         }
     }
+
+    pub fn copying(&self) -> Option<CopyingFacility> {
+        if self.support_industry_type(&IndustryType::Copying) {
+            Some(CopyingFacility::new(self))
+        } else {
+            None
+        }
+    }
+
+    /// `research` is either the material or the time efficiency research.
+    pub fn research(&self, research: IndustryType) -> Option<ResearchFacility> {
+        if self.support_industry_type(&research) {
+            Some(ResearchFacility::new(self, research))
+        } else {
+            None
+        }
+    }
 }
 
 #[derive(Debug, Error)]
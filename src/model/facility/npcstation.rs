@@ -1,6 +1,6 @@
-use crate::model::common::Identified;
+use crate::model::{common::Identified, industry::IndustryType};
 
-use super::{invention::InventionFacilityProperties, manufacture::ManufacturingFacility};
+use super::{manufacture::ManufacturingFacility, science::ScienceFacilityProperties};
 
 #[derive(Debug, PartialEq, Clone, Default)]
 pub struct NPCStation {
@@ -37,16 +37,16 @@ impl ManufacturingFacility for NPCStation {
     }
 }
 
-impl InventionFacilityProperties for NPCStation {
-    fn invention_tax(&self) -> f64 {
+impl ScienceFacilityProperties for NPCStation {
+    fn science_tax(&self, _: &IndustryType) -> f64 {
         0.0025
     }
 
-    fn job_cost_modifier(&self) -> Option<f64> {
+    fn job_cost_modifier(&self, _: &IndustryType) -> Option<f64> {
         None
     }
 
-    fn job_duration_modifier(&self) -> Option<f64> {
+    fn job_duration_modifier(&self, _: &IndustryType) -> Option<f64> {
         None
     }
 }
@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::model::{common::Identified, industry::IndustryType};
 
-use super::{invention::InventionFacilityProperties, manufacture::ManufacturingFacility};
+use super::{manufacture::ManufacturingFacility, science::ScienceFacilityProperties};
 
 #[derive(Debug, PartialEq, Clone, Default)]
 pub struct PlayerStructure {
@@ -54,23 +54,23 @@ impl ManufacturingFacility for PlayerStructure {
     }
 }
 
-impl InventionFacilityProperties for PlayerStructure {
-    fn invention_tax(&self) -> f64 {
-        match self.activities.get(&IndustryType::Invention) {
+impl ScienceFacilityProperties for PlayerStructure {
+    fn science_tax(&self, activity: &IndustryType) -> f64 {
+        match self.activities.get(activity) {
             None => 0.0,
             Some(stat) => stat.tax_rate,
         }
     }
 
-    fn job_cost_modifier(&self) -> Option<f64> {
-        match self.activities.get(&IndustryType::Invention) {
+    fn job_cost_modifier(&self, activity: &IndustryType) -> Option<f64> {
+        match self.activities.get(activity) {
             None => None,
             Some(stat) => stat.job_cost_modifier,
         }
     }
 
-    fn job_duration_modifier(&self) -> Option<f64> {
-        match self.activities.get(&IndustryType::Invention) {
+    fn job_duration_modifier(&self, activity: &IndustryType) -> Option<f64> {
+        match self.activities.get(activity) {
             None => None,
             Some(stat) => stat.job_duration_modifier,
         }
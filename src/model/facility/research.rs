@@ -0,0 +1,364 @@
+use crate::model::{
+    blueprint::{BlueprintManufacturing, BlueprintResearch},
+    character::Skills,
+    common::{DetailedCalculation, Named},
+    implants::Implants,
+    industry::IndustryType,
+    prices::Prices,
+};
+
+use super::{
+    science::{self, JobCostDetails, ScienceFacilityProperties},
+    Facility,
+};
+
+/// Relative duration of each research level, the first one lasting the blueprint research time.
+/// https://wiki.eveuniversity.org/Research
+static RESEARCH_LEVEL_MODIFIERS: [f64; 10] = [
+    105.0, 250.0, 595.0, 1414.0, 3360.0, 8000.0, 19000.0, 45255.0, 107700.0, 256000.0,
+];
+
+pub const MAX_RESEARCH_LEVEL: u8 = 10;
+
+/// Facility researching either the material or the time efficiency of blueprints.
+pub struct ResearchFacility<'a>(&'a Facility, IndustryType);
+
+impl<'a> ResearchFacility<'a> {
+    pub fn new(facility: &'a Facility, research: IndustryType) -> Self {
+        Self(facility, research)
+    }
+
+    pub fn system_cost_index(&self) -> f64 {
+        self.0.location.indexes.get(&self.1)
+    }
+
+    pub fn job_duration_modifier(&self) -> Option<f64> {
+        self.0.type_specific_data.job_duration_modifier(&self.1)
+    }
+
+    pub fn job_installation_cost(
+        &self,
+        estimated_item_value: f64,
+        from_level: u8,
+        to_level: u8,
+    ) -> DetailedCalculation<f64, JobCostDetails> {
+        science::job_installation_cost(
+            self.0,
+            &self.1,
+            estimated_item_value,
+            level_multiplier(from_level, to_level),
+        )
+    }
+
+    /// Metallurgy (material efficiency) or Research (time efficiency) reduce research time by 5%
+    /// per level and Advanced Industry by 3% per level, on top of the research implants.
+    pub fn time(
+        &self,
+        blueprint: &BlueprintResearch,
+        from_level: u8,
+        to_level: u8,
+        skills: &Skills,
+        implants: &Implants,
+    ) -> DetailedCalculation<i64, ResearchTimeDetails> {
+        let base_time = (blueprint.time as f64) * level_multiplier(from_level, to_level);
+        let mut time = base_time;
+        if let Some(modifier) = self.job_duration_modifier() {
+            time *= 1.0 - modifier;
+        }
+
+        let skill_name = match self.1 {
+            IndustryType::ResearchTimeEfficiency => "Research",
+            _ => "Metallurgy",
+        };
+        let research_skill_level = skills.trained_level(skill_name);
+        let advanced_industry_skill_level = skills.trained_level("Advanced Industry");
+        time *= 1.0 - 0.05 * (research_skill_level as f64);
+        time *= 1.0 - 0.03 * (advanced_industry_skill_level as f64);
+        time *= implants.time_modifier(&self.1);
+
+        DetailedCalculation {
+            value: time as i64,
+            details: ResearchTimeDetails {
+                base_time: base_time as i64,
+                job_duration_modifier: self.job_duration_modifier(),
+                research_skill_level,
+                advanced_industry_skill_level,
+                implants: implants
+                    .contributing(&self.1)
+                    .iter()
+                    .map(|implant| implant.name())
+                    .collect(),
+            },
+        }
+    }
+
+    pub fn research(
+        &self,
+        blueprint: &BlueprintManufacturing,
+        research: &BlueprintResearch,
+        (from_level, to_level): (u8, u8),
+        skills: &Skills,
+        implants: &Implants,
+        prices: &Prices,
+    ) -> DetailedCalculation<ResearchResult, ResearchDetails> {
+        let job_cost = self.job_installation_cost(
+            blueprint.estimated_item_value(prices),
+            from_level,
+            to_level,
+        );
+        let time = self.time(research, from_level, to_level, skills, implants);
+        DetailedCalculation {
+            value: ResearchResult {
+                cost: job_cost.value,
+                time: time.value,
+            },
+            details: ResearchDetails {
+                job_cost: job_cost.details,
+                time: time.details,
+            },
+        }
+    }
+}
+
+impl<'a> Named for ResearchFacility<'a> {
+    fn name(&self) -> String {
+        self.0.name()
+    }
+}
+
+/// Research time of the levels from `from_level` (excluded) to `to_level`, relative to the first
+/// level.
+fn level_multiplier(from_level: u8, to_level: u8) -> f64 {
+    let from_level = from_level.min(MAX_RESEARCH_LEVEL) as usize;
+    let to_level = to_level.min(MAX_RESEARCH_LEVEL) as usize;
+    if to_level <= from_level {
+        return 0.0;
+    }
+    RESEARCH_LEVEL_MODIFIERS[from_level..to_level]
+        .iter()
+        .sum::<f64>()
+        / RESEARCH_LEVEL_MODIFIERS[0]
+}
+
+/// ISK saved on the materials of each manufactured run by improving the material efficiency of
+/// the blueprint. Materials are rounded per job, so savings are computed over jobs of `runs` runs,
+/// bounded by the blueprint production limit.
+pub fn material_savings_per_run(
+    blueprint: &BlueprintManufacturing,
+    runs: i32,
+    from_material_efficiency: u8,
+    to_material_efficiency: u8,
+    prices: &Prices,
+) -> f64 {
+    let runs = runs.clamp(1, blueprint.max_production_limit.max(1));
+    let before = blueprint.materials.input_materials_cost(
+        None,
+        Some(from_material_efficiency),
+        runs,
+        prices,
+    );
+    let after =
+        blueprint
+            .materials
+            .input_materials_cost(None, Some(to_material_efficiency), runs, prices);
+    (before.value - after.value) / runs as f64
+}
+
+/// Manufactured runs needed for the material savings to cover the material efficiency research
+/// cost. `None` when the research doesn't save anything.
+pub fn payback_runs(research_cost: f64, savings_per_run: f64) -> Option<i64> {
+    if savings_per_run <= 0.0 {
+        return None;
+    }
+    Some((research_cost / savings_per_run).ceil() as i64)
+}
+
+#[derive(Debug, Clone)]
+pub struct ResearchTimeDetails {
+    pub base_time: i64,
+    pub job_duration_modifier: Option<f64>,
+    pub research_skill_level: i32,
+    pub advanced_industry_skill_level: i32,
+    /// Names of the implants reducing the research time.
+    pub implants: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ResearchDetails {
+    pub job_cost: JobCostDetails,
+    pub time: ResearchTimeDetails,
+}
+
+#[derive(Debug, Clone)]
+pub struct ResearchResult {
+    pub cost: f64,
+    /// Science slot time, in seconds.
+    pub time: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Arc};
+
+    use crate::{
+        api::evecache::mocks::MockRequester,
+        model::{
+            blueprint::{Materials, MultipleItems},
+            facility::{playerstructure::PlayerStructureStats, FacilityUsage},
+            items::{Item, TechLevel},
+            locations::{Constellation, CostIndexes, Region, SolarSystem},
+            prices::ItemPrice,
+            skills::TrainedSkill,
+        },
+        round::Round,
+    };
+
+    use super::*;
+
+    fn create_test_facility() -> Facility {
+        let mut activites = HashMap::new();
+        activites.insert(
+            IndustryType::ResearchMaterialEfficiency,
+            PlayerStructureStats {
+                tax_rate: 0.1,
+                job_duration_modifier: Some(0.2),
+                job_cost_modifier: Some(0.5),
+                material_consumption_modifier: None,
+            },
+        );
+        Facility::new_structure(
+            Arc::new(MockRequester::builder().build()),
+            15,
+            "Test Structure".to_string(),
+            SolarSystem::new(
+                9,
+                "Test Solar System".to_string(),
+                0.1234,
+                vec![8],
+                Constellation::new(
+                    10,
+                    "Test Constellation".to_string(),
+                    vec![9],
+                    Region::new(11, "Test Region", vec![10]),
+                ),
+                CostIndexes {
+                    manufacturing: 0.456,
+                    invention: 0.789,
+                    copying: 0.0,
+                    research_material_efficiency: 0.25,
+                    research_time_efficiency: 0.0,
+                },
+            ),
+            Some(vec![FacilityUsage::Industry]),
+            activites,
+        )
+    }
+
+    fn create_blueprint() -> BlueprintManufacturing {
+        BlueprintManufacturing {
+            blueprint_id: 10,
+            materials: Materials::new(vec![MultipleItems {
+                quantity: 100,
+                item: Item::new(50, "Item 50", None, TechLevel::One),
+            }]),
+            products: vec![],
+            skills: vec![],
+            material_efficiency: 0,
+            time_efficiency: 0,
+            time: 0,
+            max_production_limit: 1,
+            runs: 1,
+            invention_blueprint: vec![],
+        }
+    }
+
+    #[test]
+    fn test_level_multiplier() {
+        assert_eq!(level_multiplier(0, 1), 1.0);
+        assert_eq!(level_multiplier(1, 1), 0.0);
+        assert_eq!(level_multiplier(0, 2).specific_round(2), 3.38);
+        assert_eq!(level_multiplier(9, 12).specific_round(2), 2438.1);
+    }
+
+    #[test]
+    fn test_research_time_efficiency_unsupported() {
+        let facility = create_test_facility();
+
+        assert!(facility
+            .research(IndustryType::ResearchTimeEfficiency)
+            .is_none());
+    }
+
+    #[test]
+    fn test_research() {
+        let facility = create_test_facility();
+        let blueprint = create_blueprint();
+        let skills = Skills {
+            skills: vec![
+                TrainedSkill::new(1, "Metallurgy", 4),
+                TrainedSkill::new(2, "Advanced Industry", 5),
+            ],
+        };
+        let mut prices = HashMap::new();
+        prices.insert(50, ItemPrice::new(Some(10.0), Some(10.0)));
+        let prices = Prices { prices };
+
+        let result = facility
+            .research(IndustryType::ResearchMaterialEfficiency)
+            .unwrap()
+            .research(
+                &blueprint,
+                &BlueprintResearch { time: 1000 },
+                (0, 1),
+                &skills,
+                &Implants::default(),
+                &prices,
+            );
+
+        assert_eq!(result.details.job_cost.base_job_cost, 20.0);
+        // 20 * 0.25 * 0.5 + 20 * 0.1 + 20 * 0.015
+        assert_eq!(result.value.cost.specific_round(2), 4.8);
+        // 1000 * 0.8 * 0.8 * 0.85
+        assert_eq!(result.value.time, 544);
+        assert_eq!(result.details.time.research_skill_level, 4);
+    }
+
+    #[test]
+    fn test_payback() {
+        let blueprint = create_blueprint();
+        let mut prices = HashMap::new();
+        prices.insert(50, ItemPrice::new(Some(10.0), Some(10.0)));
+        let prices = Prices { prices };
+
+        let savings = material_savings_per_run(&blueprint, 1, 0, 10, &prices);
+        assert_eq!(savings, 100.0);
+        assert_eq!(payback_runs(250.0, savings), Some(3));
+        assert_eq!(payback_runs(250.0, 0.0), None);
+    }
+
+    #[test]
+    fn test_material_savings_rounded_per_job() {
+        let mut blueprint = create_blueprint();
+        blueprint.materials = Materials::new(vec![MultipleItems {
+            quantity: 2,
+            item: Item::new(50, "Item 50", None, TechLevel::One),
+        }]);
+        blueprint.max_production_limit = 10;
+        let mut prices = HashMap::new();
+        prices.insert(50, ItemPrice::new(Some(10.0), Some(10.0)));
+        let prices = Prices { prices };
+
+        // A single run still consumes ceil(1.8) = 2 units
+        assert_eq!(material_savings_per_run(&blueprint, 1, 0, 10, &prices), 0.0);
+        // 10 runs consume 18 units instead of 20
+        assert_eq!(
+            material_savings_per_run(&blueprint, 10, 0, 10, &prices),
+            2.0
+        );
+        // Bounded by the production limit
+        assert_eq!(
+            material_savings_per_run(&blueprint, 50, 0, 10, &prices),
+            2.0
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{
+    logging,
+    model::{common::DetailedCalculation, industry::IndustryType},
+};
+
+use super::{Facility, FacilityType};
+
+/// Taxes and bonuses of a facility for the science activities: invention, copying and research.
+pub trait ScienceFacilityProperties {
+    fn science_tax(&self, activity: &IndustryType) -> f64;
+    fn job_cost_modifier(&self, activity: &IndustryType) -> Option<f64>;
+    fn job_duration_modifier(&self, activity: &IndustryType) -> Option<f64>;
+}
+
+impl ScienceFacilityProperties for FacilityType {
+    fn science_tax(&self, activity: &IndustryType) -> f64 {
+        match self {
+            FacilityType::Station(s) => s.science_tax(activity),
+            FacilityType::Structure(s) => s.science_tax(activity),
+        }
+    }
+
+    fn job_cost_modifier(&self, activity: &IndustryType) -> Option<f64> {
+        match self {
+            FacilityType::Station(s) => ScienceFacilityProperties::job_cost_modifier(s, activity),
+            FacilityType::Structure(s) => ScienceFacilityProperties::job_cost_modifier(s, activity),
+        }
+    }
+
+    fn job_duration_modifier(&self, activity: &IndustryType) -> Option<f64> {
+        match self {
+            FacilityType::Station(s) => {
+                ScienceFacilityProperties::job_duration_modifier(s, activity)
+            }
+            FacilityType::Structure(s) => {
+                ScienceFacilityProperties::job_duration_modifier(s, activity)
+            }
+        }
+    }
+}
+
+/// Installation cost of a science job. Its base is 2% of the estimated item value, times
+/// `base_multiplier` for jobs spanning several research levels. The base is scaled by the system
+/// cost index and the facility bonus, then the facility tax and the SCC surcharge are added.
+pub fn job_installation_cost(
+    facility: &Facility,
+    activity: &IndustryType,
+    estimated_item_value: f64,
+    base_multiplier: f64,
+) -> DetailedCalculation<f64, JobCostDetails> {
+    let properties = &facility.type_specific_data;
+
+    let base_job_cost = 0.02 * estimated_item_value * base_multiplier;
+    logging::trace!("Base job cost: {}", base_job_cost);
+    let mut gross_cost = base_job_cost * facility.location.indexes.get(activity);
+    logging::trace!("Gross Cost: {}", gross_cost);
+    let job_cost_modifier = properties.job_cost_modifier(activity);
+    if let Some(modifier) = job_cost_modifier {
+        gross_cost -= gross_cost * modifier;
+        logging::trace!("Gross Cost (Modifier): {}", gross_cost);
+    }
+
+    let facility_tax = properties.science_tax(activity);
+    let mut tax = base_job_cost * facility_tax;
+    logging::trace!("Tax: {}", tax);
+
+    // Fixed amount
+    // https://wiki.eveuniversity.org/Manufacturing
+    // https://www.eveonline.com/news/view/patch-notes-version-21-05-2
+    static SCC_SURCHARGE: f64 = 0.015;
+
+    tax += base_job_cost * SCC_SURCHARGE;
+    logging::trace!("Tax (SCC): {}", tax);
+
+    DetailedCalculation {
+        value: gross_cost + tax,
+        details: JobCostDetails {
+            estimated_item_value,
+            base_job_cost,
+            job_cost_modifier,
+            facility_tax,
+            scc_surcharge: SCC_SURCHARGE,
+        },
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct JobCostDetails {
+    pub estimated_item_value: f64,
+    pub base_job_cost: f64,
+    pub job_cost_modifier: Option<f64>,
+    pub facility_tax: f64,
+    pub scc_surcharge: f64,
+}
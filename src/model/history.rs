@@ -0,0 +1,218 @@
+use serde::{Deserialize, Serialize};
+
+/// Results of a `manufacture all` run, with the prices and cost indexes they were computed from.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct HistorySnapshot {
+    /// Unix timestamp of the run, in seconds.
+    pub timestamp: i64,
+    pub items: Vec<HistoryItem>,
+}
+
+impl HistorySnapshot {
+    /// Items sorted from the most to the least profitable per hour.
+    pub fn ranking(&self) -> Vec<&HistoryItem> {
+        let mut items: Vec<&HistoryItem> = self.items.iter().collect();
+        items.sort_by(|a, b| b.profits_per_hour.total_cmp(&a.profits_per_hour));
+        items
+    }
+}
+
+/// Manufacturing of an item in its most profitable facility. Costs are per unit.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct HistoryItem {
+    pub id: i32,
+    pub name: String,
+    pub facility_name: String,
+    pub profits_per_hour: f64,
+    pub sell_price: f64,
+    pub material_cost: f64,
+    pub installation_cost: f64,
+    pub invention_cost: f64,
+    /// Manufacturing cost index of the facility system.
+    pub cost_index: f64,
+    /// Manufacturing time of a unit, in seconds.
+    pub time_per_unit: f64,
+}
+
+impl HistoryItem {
+    pub fn margin(&self) -> f64 {
+        self.sell_price - self.material_cost - self.installation_cost - self.invention_cost
+    }
+
+    pub fn units_per_hour(&self) -> f64 {
+        if self.time_per_unit > 0.0 {
+            3600.0 / self.time_per_unit
+        } else {
+            0.0
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Factor {
+    SellPrice,
+    MaterialCost,
+    InstallationCost,
+    InventionCost,
+    ManufacturingTime,
+}
+
+/// Part of the profits per hour change caused by a single factor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FactorImpact {
+    pub factor: Factor,
+    pub profits_per_hour: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemDiff<'a> {
+    pub previous: Option<(usize, &'a HistoryItem)>,
+    pub current: Option<(usize, &'a HistoryItem)>,
+    pub impacts: Vec<FactorImpact>,
+}
+
+impl<'a> ItemDiff<'a> {
+    /// Factor which moved the profits per hour the most.
+    pub fn main_factor(&self) -> Option<&FactorImpact> {
+        self.impacts
+            .iter()
+            .filter(|impact| impact.profits_per_hour != 0.0)
+            .max_by(|a, b| {
+                a.profits_per_hour
+                    .abs()
+                    .total_cmp(&b.profits_per_hour.abs())
+            })
+    }
+}
+
+/// Compares the ranking of two runs. Items are listed by their current rank (starting at 1), then
+/// items missing from the current run.
+///
+/// The profits per hour change of an item present in both runs is split between its factors: the
+/// margin changes are weighted by the current units per hour, and the manufacturing time change by
+/// the previous margin, so impacts add up to the whole change.
+pub fn diff<'a>(previous: &'a HistorySnapshot, current: &'a HistorySnapshot) -> Vec<ItemDiff<'a>> {
+    let previous_ranking = previous.ranking();
+    let current_ranking = current.ranking();
+    let find_previous = |id: i32| {
+        previous_ranking
+            .iter()
+            .enumerate()
+            .find(|(_, item)| item.id == id)
+            .map(|(index, item)| (index + 1, *item))
+    };
+
+    let mut diffs = vec![];
+    for (index, item) in current_ranking.iter().enumerate() {
+        let previous_item = find_previous(item.id);
+        let impacts = match previous_item {
+            Some((_, previous_item)) => impacts(previous_item, item),
+            None => vec![],
+        };
+        diffs.push(ItemDiff {
+            previous: previous_item,
+            current: Some((index + 1, *item)),
+            impacts,
+        });
+    }
+    for (index, item) in previous_ranking.iter().enumerate() {
+        if !current_ranking.iter().any(|current| current.id == item.id) {
+            diffs.push(ItemDiff {
+                previous: Some((index + 1, *item)),
+                current: None,
+                impacts: vec![],
+            });
+        }
+    }
+    diffs
+}
+
+fn impacts(previous: &HistoryItem, current: &HistoryItem) -> Vec<FactorImpact> {
+    let units_per_hour = current.units_per_hour();
+    vec![
+        FactorImpact {
+            factor: Factor::SellPrice,
+            profits_per_hour: (current.sell_price - previous.sell_price) * units_per_hour,
+        },
+        FactorImpact {
+            factor: Factor::MaterialCost,
+            profits_per_hour: (previous.material_cost - current.material_cost) * units_per_hour,
+        },
+        FactorImpact {
+            factor: Factor::InstallationCost,
+            profits_per_hour: (previous.installation_cost - current.installation_cost)
+                * units_per_hour,
+        },
+        FactorImpact {
+            factor: Factor::InventionCost,
+            profits_per_hour: (previous.invention_cost - current.invention_cost) * units_per_hour,
+        },
+        FactorImpact {
+            factor: Factor::ManufacturingTime,
+            profits_per_hour: previous.margin() * (units_per_hour - previous.units_per_hour()),
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: i32, sell_price: f64, material_cost: f64, installation_cost: f64) -> HistoryItem {
+        let mut item = HistoryItem {
+            id,
+            name: format!("Item {id}"),
+            facility_name: "Facility".to_string(),
+            profits_per_hour: 0.0,
+            sell_price,
+            material_cost,
+            installation_cost,
+            invention_cost: 0.0,
+            cost_index: 0.05,
+            time_per_unit: 3600.0,
+        };
+        item.profits_per_hour = item.margin() * item.units_per_hour();
+        item
+    }
+
+    #[test]
+    fn test_diff() {
+        let previous = HistorySnapshot {
+            timestamp: 0,
+            items: vec![item(1, 1000.0, 500.0, 10.0), item(2, 1000.0, 700.0, 10.0)],
+        };
+        let current = HistorySnapshot {
+            timestamp: 86400,
+            items: vec![
+                item(1, 1000.0, 500.0, 260.0),
+                item(2, 1100.0, 700.0, 10.0),
+                item(3, 1000.0, 900.0, 10.0),
+            ],
+        };
+
+        let diffs = diff(&previous, &current);
+
+        assert_eq!(diffs.len(), 3);
+        assert_eq!(diffs[0].current.unwrap().1.id, 2);
+        assert_eq!(diffs[0].previous.unwrap().0, 2);
+        assert_eq!(
+            diffs[0].main_factor().unwrap(),
+            &FactorImpact {
+                factor: Factor::SellPrice,
+                profits_per_hour: 100.0,
+            }
+        );
+        assert_eq!(diffs[1].current.unwrap().0, 2);
+        assert_eq!(diffs[1].previous.unwrap().0, 1);
+        assert_eq!(
+            diffs[1].main_factor().unwrap(),
+            &FactorImpact {
+                factor: Factor::InstallationCost,
+                profits_per_hour: -250.0,
+            }
+        );
+        assert_eq!(diffs[2].previous, None);
+        assert!(diffs[2].main_factor().is_none());
+    }
+}
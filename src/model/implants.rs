@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+
+use super::common::{Identified, Named};
+use super::industry::IndustryType;
+
+/// Implant plugged in the character head, such as the Zainou 'Beancounter' ones reducing the
+/// duration of industry jobs.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Implant {
+    id: i32,
+    name: String,
+    /// Duration reduction of the jobs of each industry activity, e.g. 0.04 for 4%.
+    pub time_bonuses: HashMap<IndustryType, f64>,
+}
+
+impl Implant {
+    pub fn new(id: i32, name: &str, time_bonuses: HashMap<IndustryType, f64>) -> Self {
+        Self {
+            id,
+            name: name.to_string(),
+            time_bonuses,
+        }
+    }
+
+    pub fn time_bonus(&self, industry_type: &IndustryType) -> Option<f64> {
+        self.time_bonuses.get(industry_type).copied()
+    }
+}
+
+impl Identified<i32> for Implant {
+    fn id(&self) -> i32 {
+        self.id
+    }
+}
+
+impl Named for Implant {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Implants {
+    pub implants: Vec<Implant>,
+}
+
+impl Implants {
+    /// Multiplier applied to the duration of the jobs of the industry activity.
+    pub fn time_modifier(&self, industry_type: &IndustryType) -> f64 {
+        self.implants
+            .iter()
+            .filter_map(|implant| implant.time_bonus(industry_type))
+            .fold(1.0, |modifier, bonus| modifier * (1.0 - bonus))
+    }
+
+    /// Implants reducing the duration of the jobs of the industry activity.
+    pub fn contributing(&self, industry_type: &IndustryType) -> Vec<&Implant> {
+        self.implants
+            .iter()
+            .filter(|implant| implant.time_bonus(industry_type).is_some())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::round::Round;
+
+    use super::*;
+
+    #[test]
+    fn test_time_modifier() {
+        let implants = Implants {
+            implants: vec![
+                Implant::new(
+                    27167,
+                    "Zainou 'Beancounter' Industry BX-804",
+                    HashMap::from([(IndustryType::Manufacturing, 0.04)]),
+                ),
+                Implant::new(
+                    27170,
+                    "Zainou 'Beancounter' Science SC-803",
+                    HashMap::from([(IndustryType::Copying, 0.03)]),
+                ),
+            ],
+        };
+
+        assert_eq!(
+            implants
+                .time_modifier(&IndustryType::Manufacturing)
+                .specific_round(2),
+            0.96
+        );
+        assert_eq!(implants.time_modifier(&IndustryType::Invention), 1.0);
+        assert_eq!(
+            implants.contributing(&IndustryType::Copying)[0].name(),
+            "Zainou 'Beancounter' Science SC-803"
+        );
+    }
+}
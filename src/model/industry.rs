@@ -1,6 +1,6 @@
 use std::fmt::{Display, Formatter};
 
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use strum_macros::{EnumIter, EnumString};
 use thiserror::Error;
@@ -9,10 +9,17 @@ use super::items::Item;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Job {
+    /// Eve API ID of the job, 0 when unknown.
+    pub id: i32,
     pub industry_type: IndustryType,
     pub item_produced: Option<Item>,
     pub end_date: chrono::DateTime<Utc>,
     pub runs: i32,
+    /// Station or structure the job has been installed in.
+    pub facility_id: i64,
+    pub blueprint_type_id: i32,
+    /// Installation cost actually charged for the job.
+    pub cost: Option<f64>,
 }
 
 impl Job {
@@ -21,15 +28,31 @@ impl Job {
         item_produced: Option<Item>,
         runs: i32,
         end_date: chrono::DateTime<Utc>,
+        facility_id: i64,
+        blueprint_type_id: i32,
+        cost: Option<f64>,
     ) -> Self {
         Self {
+            id: 0,
             industry_type,
             item_produced,
             runs,
             end_date,
+            facility_id,
+            blueprint_type_id,
+            cost,
         }
     }
 
+    pub fn with_id(mut self, id: i32) -> Self {
+        self.id = id;
+        self
+    }
+
+    pub fn is_ready(&self, now: DateTime<Utc>) -> bool {
+        self.end_date <= now
+    }
+
     pub fn duration_left(&self) -> Duration {
         let now = Utc::now();
         if Utc::now() > self.end_date {
@@ -85,6 +108,86 @@ impl IndustryType {
     }
 }
 
+/// Kind of job slot consumed by the industry activities.
+#[derive(Copy, EnumIter, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum SlotType {
+    Manufacturing,
+    Science,
+    Reaction,
+}
+
+impl From<IndustryType> for SlotType {
+    fn from(industry_type: IndustryType) -> Self {
+        match industry_type {
+            IndustryType::Manufacturing => SlotType::Manufacturing,
+            IndustryType::Reaction => SlotType::Reaction,
+            IndustryType::ResearchTimeEfficiency
+            | IndustryType::ResearchMaterialEfficiency
+            | IndustryType::Copying
+            | IndustryType::Invention => SlotType::Science,
+        }
+    }
+}
+
+impl Display for SlotType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let res = match self {
+            SlotType::Manufacturing => "Manufacturing",
+            SlotType::Science => "Science",
+            SlotType::Reaction => "Reaction",
+        };
+        write!(f, "{}", res)
+    }
+}
+
+/// Job slots of a kind available to the character, and the end dates of the running jobs
+/// occupying them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobSlots {
+    pub slot_type: SlotType,
+    pub max: i32,
+    pub end_dates: Vec<DateTime<Utc>>,
+}
+
+impl JobSlots {
+    pub fn new(slot_type: SlotType, max: i32, jobs: &[Job]) -> Self {
+        let mut end_dates: Vec<DateTime<Utc>> = jobs
+            .iter()
+            .filter(|job| SlotType::from(job.industry_type) == slot_type)
+            .map(|job| job.end_date)
+            .collect();
+        end_dates.sort();
+        Self {
+            slot_type,
+            max,
+            end_dates,
+        }
+    }
+
+    pub fn used(&self) -> i32 {
+        self.end_dates.len() as i32
+    }
+
+    pub fn free(&self) -> i32 {
+        (self.max - self.used()).max(0)
+    }
+
+    /// Date at which each slot can take a new job, `now` for the free ones. When more jobs run
+    /// than the skills allow, the slots are freed by the last ending jobs.
+    pub fn available_from(&self, now: DateTime<Utc>) -> Vec<DateTime<Utc>> {
+        let occupied = self.used().min(self.max).max(0) as usize;
+        let mut dates: Vec<DateTime<Utc>> = self
+            .end_dates
+            .iter()
+            .skip(self.end_dates.len() - occupied)
+            .map(|end_date| (*end_date).max(now))
+            .collect();
+        dates.resize(dates.len() + self.free() as usize, now);
+        dates.sort();
+        dates
+    }
+}
+
 impl Display for IndustryType {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let res = match self {
@@ -98,3 +201,104 @@ impl Display for IndustryType {
         write!(f, "{}", res)
     }
 }
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum JobTransition {
+    Started,
+    /// The job ended and its output waits for delivery.
+    Ready,
+    Delivered,
+}
+
+impl Display for JobTransition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let res = match self {
+            JobTransition::Started => "Started",
+            JobTransition::Ready => "Ready",
+            JobTransition::Delivered => "Delivered",
+        };
+        write!(f, "{}", res)
+    }
+}
+
+/// Remembers the jobs seen at the last poll, to detect the jobs started, ready and delivered since.
+#[derive(Debug, Default)]
+pub struct JobTracker {
+    last_poll: Option<(DateTime<Utc>, Vec<Job>)>,
+}
+
+impl JobTracker {
+    /// Records the running jobs, and returns the transitions since the last poll. The first poll
+    /// only records the jobs. Jobs missing from the running jobs have been delivered.
+    pub fn update(&mut self, jobs: Vec<Job>, now: DateTime<Utc>) -> Vec<(Job, JobTransition)> {
+        let mut transitions = vec![];
+        if let Some((last_poll, previous_jobs)) = &self.last_poll {
+            for job in &jobs {
+                match previous_jobs.iter().find(|previous| previous.id == job.id) {
+                    None => {
+                        transitions.push((job.clone(), JobTransition::Started));
+                        if job.is_ready(now) {
+                            transitions.push((job.clone(), JobTransition::Ready));
+                        }
+                    }
+                    Some(previous) => {
+                        if !previous.is_ready(*last_poll) && job.is_ready(now) {
+                            transitions.push((job.clone(), JobTransition::Ready));
+                        }
+                    }
+                }
+            }
+            for previous in previous_jobs {
+                if !jobs.iter().any(|job| job.id == previous.id) {
+                    transitions.push((previous.clone(), JobTransition::Delivered));
+                }
+            }
+        }
+        self.last_poll = Some((now, jobs));
+        transitions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn test_job_tracker_transitions() {
+        let date = |hour| Utc.with_ymd_and_hms(2024, 2, 3, hour, 0, 0).unwrap();
+        let job = |id, end_hour| {
+            Job::new(
+                IndustryType::Manufacturing,
+                None,
+                1,
+                date(end_hour),
+                1,
+                10,
+                None,
+            )
+            .with_id(id)
+        };
+        let mut tracker = JobTracker::default();
+
+        assert_eq!(tracker.update(vec![job(1, 2), job(2, 5)], date(1)), vec![]);
+        assert_eq!(
+            tracker.update(vec![job(1, 2), job(2, 5), job(3, 8)], date(3)),
+            vec![
+                (job(1, 2), JobTransition::Ready),
+                (job(3, 8), JobTransition::Started),
+            ]
+        );
+        assert_eq!(
+            tracker.update(vec![job(2, 5), job(3, 8)], date(4)),
+            vec![(job(1, 2), JobTransition::Delivered)]
+        );
+        assert_eq!(
+            tracker.update(vec![job(2, 5), job(3, 8)], date(6)),
+            vec![(job(2, 5), JobTransition::Ready)]
+        );
+        assert_eq!(tracker.update(vec![job(2, 5), job(3, 8)], date(7)), vec![]);
+    }
+}
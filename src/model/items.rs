@@ -7,6 +7,7 @@ pub struct Item {
     name: String,
     pub volume: Option<f64>,
     pub tech_level: TechLevel,
+    pub classification: Option<ItemClassification>,
 }
 
 impl Item {
@@ -16,8 +17,21 @@ impl Item {
             name: name.to_string(),
             volume,
             tech_level,
+            classification: None,
         }
     }
+
+    pub fn with_classification(mut self, classification: ItemClassification) -> Self {
+        self.classification = Some(classification);
+        self
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ItemClassification {
+    pub group: Option<String>,
+    pub category: Option<String>,
+    pub meta_group: Option<String>,
 }
 
 impl Identified<i32> for Item {
@@ -42,4 +56,21 @@ impl Display for Item {
 pub enum TechLevel {
     One,
     Two,
+    Three,
+}
+
+impl TechLevel {
+    /// Tech level from the SDE meta group ("Tech II" and "Structure Tech II" are the only tech two groups).
+    pub fn from_meta_group_id(meta_group_id: Option<i32>) -> Self {
+        match meta_group_id {
+            Some(2) | Some(53) => TechLevel::Two,
+            Some(14) => TechLevel::Three,
+            _ => TechLevel::One,
+        }
+    }
+
+    /// Tech two blueprints are invented from tech one copies, tech three ones from ancient relics.
+    pub fn is_invented(&self) -> bool {
+        matches!(self, TechLevel::Two | TechLevel::Three)
+    }
 }
@@ -1,4 +1,5 @@
 use crate::model::common::{Identified, Named};
+use crate::model::industry::IndustryType;
 use rfesi::groups::CostIndex;
 
 #[derive(Debug, PartialEq, Clone)]
@@ -107,24 +108,53 @@ impl Named for Region {
 pub struct CostIndexes {
     pub manufacturing: f64,
     pub invention: f64,
+    pub copying: f64,
+    pub research_material_efficiency: f64,
+    pub research_time_efficiency: f64,
+}
+
+impl CostIndexes {
+    /// Cost index of the activity in the system. Reactions aren't tracked and cost nothing.
+    pub fn get(&self, activity: &IndustryType) -> f64 {
+        match activity {
+            IndustryType::Manufacturing => self.manufacturing,
+            IndustryType::Invention => self.invention,
+            IndustryType::Copying => self.copying,
+            IndustryType::ResearchMaterialEfficiency => self.research_material_efficiency,
+            IndustryType::ResearchTimeEfficiency => self.research_time_efficiency,
+            IndustryType::Reaction => 0.0,
+        }
+    }
 }
 
 impl From<&Vec<CostIndex>> for CostIndexes {
     fn from(indexes: &Vec<CostIndex>) -> Self {
         let mut manufacturing_index = 0.0;
         let mut invention_index = 0.0;
+        let mut copying_index = 0.0;
+        let mut research_material_efficiency_index = 0.0;
+        let mut research_time_efficiency_index = 0.0;
 
         for index in indexes {
             if "manufacturing" == index.activity {
                 manufacturing_index = index.cost_index;
             } else if "invention" == index.activity {
                 invention_index = index.cost_index;
+            } else if "copying" == index.activity {
+                copying_index = index.cost_index;
+            } else if "researching_material_efficiency" == index.activity {
+                research_material_efficiency_index = index.cost_index;
+            } else if "researching_time_efficiency" == index.activity {
+                research_time_efficiency_index = index.cost_index;
             }
         }
 
         Self {
             manufacturing: manufacturing_index,
             invention: invention_index,
+            copying: copying_index,
+            research_material_efficiency: research_material_efficiency_index,
+            research_time_efficiency: research_time_efficiency_index,
         }
     }
 }
@@ -142,7 +172,10 @@ mod test {
             cost_indexes,
             CostIndexes {
                 manufacturing: 0.0,
-                invention: 0.0
+                invention: 0.0,
+                copying: 0.0,
+                research_material_efficiency: 0.0,
+                research_time_efficiency: 0.0
             }
         );
     }
@@ -159,7 +192,10 @@ mod test {
             cost_indexes,
             CostIndexes {
                 manufacturing: 10.0,
-                invention: 0.0
+                invention: 0.0,
+                copying: 0.0,
+                research_material_efficiency: 0.0,
+                research_time_efficiency: 0.0
             }
         );
     }
@@ -176,7 +212,56 @@ mod test {
             cost_indexes,
             CostIndexes {
                 manufacturing: 0.0,
-                invention: 15.0
+                invention: 15.0,
+                copying: 0.0,
+                research_material_efficiency: 0.0,
+                research_time_efficiency: 0.0
+            }
+        );
+    }
+
+    #[test]
+    fn test_from_single_copying_index() {
+        let indexes = vec![CostIndex {
+            activity: String::from("copying"),
+            cost_index: 5.0,
+        }];
+        let cost_indexes = CostIndexes::from(&indexes);
+
+        assert_eq!(
+            cost_indexes,
+            CostIndexes {
+                manufacturing: 0.0,
+                invention: 0.0,
+                copying: 5.0,
+                research_material_efficiency: 0.0,
+                research_time_efficiency: 0.0
+            }
+        );
+    }
+
+    #[test]
+    fn test_from_research_indexes() {
+        let indexes = vec![
+            CostIndex {
+                activity: String::from("researching_material_efficiency"),
+                cost_index: 3.0,
+            },
+            CostIndex {
+                activity: String::from("researching_time_efficiency"),
+                cost_index: 4.0,
+            },
+        ];
+        let cost_indexes = CostIndexes::from(&indexes);
+
+        assert_eq!(
+            cost_indexes,
+            CostIndexes {
+                manufacturing: 0.0,
+                invention: 0.0,
+                copying: 0.0,
+                research_material_efficiency: 3.0,
+                research_time_efficiency: 4.0
             }
         );
     }
@@ -199,7 +284,10 @@ mod test {
             cost_indexes,
             CostIndexes {
                 manufacturing: 10.0,
-                invention: 15.0
+                invention: 15.0,
+                copying: 0.0,
+                research_material_efficiency: 0.0,
+                research_time_efficiency: 0.0
             }
         );
     }
@@ -226,7 +314,10 @@ mod test {
             cost_indexes,
             CostIndexes {
                 manufacturing: 20.0,
-                invention: 15.0
+                invention: 15.0,
+                copying: 0.0,
+                research_material_efficiency: 0.0,
+                research_time_efficiency: 0.0
             }
         );
     }
@@ -1,12 +1,155 @@
 use crate::api::evecache::cache_keys::OrderType;
+use crate::round::Round;
 
+use super::character::Skills;
 use super::items::Item;
 
 #[derive(Debug, PartialEq)]
 pub struct CharacterOrder {
+    pub id: i64,
     pub item: Item,
     pub order_type: OrderType,
     pub price: f64,
     pub volume_remain: i32,
     pub volume_total: i32,
+    /// Station or structure the order has been placed in.
+    pub location_id: i64,
+    pub region_id: i32,
+}
+
+impl CharacterOrder {
+    /// Whether an order at this price is served before this order: a lower sell price or a higher
+    /// buy price.
+    pub fn is_beaten_by(&self, price: f64) -> bool {
+        match self.order_type {
+            OrderType::Sell => price < self.price,
+            OrderType::Buy => price > self.price,
+        }
+    }
+}
+
+/// Share of the sell price lost to the sales tax and the broker fee when selling through a sell
+/// order in an NPC station. Accounting reduces the sales tax by 11% per level and Broker Relations
+/// the broker fee by 0.3% per level. Standings are ignored.
+/// https://wiki.eveuniversity.org/Trading
+pub fn sell_order_fees(skills: &Skills) -> f64 {
+    let sales_tax = 0.075 * (1.0 - 0.11 * (skills.trained_level("Accounting") as f64));
+    sales_tax + broker_fee(skills)
+}
+
+/// Share of the order value charged when placing an order in an NPC station.
+pub fn broker_fee(skills: &Skills) -> f64 {
+    0.03 - 0.003 * (skills.trained_level("Broker Relations") as f64)
+}
+
+/// Fee charged for changing the price of the order. The broker fee of the new order value is
+/// discounted by 50%, plus 6% per level of Advanced Broker Relations, and a price increase pays the
+/// full broker fee on the difference.
+/// https://wiki.eveuniversity.org/Trading
+pub fn relist_fee(order: &CharacterOrder, new_price: f64, skills: &Skills) -> f64 {
+    let discount = 0.5 + 0.06 * (skills.trained_level("Advanced Broker Relations") as f64);
+    let increase = (new_price - order.price).max(0.0);
+    broker_fee(skills) * (order.volume_remain as f64) * (new_price * (1.0 - discount) + increase)
+}
+
+/// Smallest price change allowed at this price: prices are limited to 4 significant digits, and
+/// to the ISK cent.
+pub fn tick_size(price: f64) -> f64 {
+    if price <= 0.0 {
+        return 0.01;
+    }
+    10.0f64.powi(price.log10().floor() as i32 - 3).max(0.01)
+}
+
+/// Closest valid price served before an order at this price: the highest valid price below it for
+/// sell orders, the lowest valid price above it for buy orders. A valid price is a multiple of the
+/// tick size at that price.
+pub fn outbid_price(price: f64, order_type: OrderType) -> f64 {
+    // Tolerance for the float errors of the division by the tick
+    let epsilon = 1e-6;
+    match order_type {
+        OrderType::Sell => {
+            let below = |tick: f64| ((price / tick - epsilon).ceil() - 1.0) * tick;
+            let mut outbid = below(tick_size(price));
+            // Prices under a power of ten have a finer tick
+            if tick_size(outbid) < tick_size(price) {
+                outbid = below(tick_size(outbid));
+            }
+            outbid.specific_round(2)
+        }
+        OrderType::Buy => {
+            // Reaching a power of ten gives a valid price for its coarser tick too
+            let tick = tick_size(price);
+            (((price / tick + epsilon).floor() + 1.0) * tick).specific_round(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::{items::TechLevel, skills::TrainedSkill};
+
+    use super::*;
+
+    #[test]
+    fn test_sell_order_fees() {
+        assert_eq!(
+            sell_order_fees(&Skills { skills: vec![] }).specific_round(4),
+            0.105
+        );
+
+        let skills = Skills {
+            skills: vec![
+                TrainedSkill::new(16622, "Accounting", 5),
+                TrainedSkill::new(3446, "Broker Relations", 5),
+            ],
+        };
+        // 0.075 * 0.45 + 0.015
+        assert_eq!(sell_order_fees(&skills).specific_round(5), 0.04875);
+    }
+
+    #[test]
+    fn test_tick_size() {
+        assert_eq!(tick_size(1234567.0), 1000.0);
+        assert_eq!(tick_size(1000.0), 1.0);
+        assert_eq!(tick_size(999.99), 0.1);
+        assert_eq!(tick_size(5.0), 0.01);
+    }
+
+    #[test]
+    fn test_outbid_price() {
+        assert_eq!(outbid_price(1234000.0, OrderType::Sell), 1233000.0);
+        assert_eq!(outbid_price(1000.0, OrderType::Sell), 999.9);
+        // Prices of old orders may not respect the current tick size
+        assert_eq!(outbid_price(1234.56, OrderType::Sell), 1234.0);
+        assert_eq!(outbid_price(12.34, OrderType::Sell), 12.33);
+        assert_eq!(outbid_price(9999.0, OrderType::Buy), 10000.0);
+        assert_eq!(outbid_price(1234.56, OrderType::Buy), 1235.0);
+        assert_eq!(outbid_price(12.34, OrderType::Buy), 12.35);
+    }
+
+    #[test]
+    fn test_relist_fee() {
+        let order = CharacterOrder {
+            id: 1,
+            item: Item::new(19, "Item 19", None, TechLevel::One),
+            order_type: OrderType::Sell,
+            price: 1000.0,
+            volume_remain: 10,
+            volume_total: 20,
+            location_id: 60003760,
+            region_id: 10000002,
+        };
+        let skills = Skills {
+            skills: vec![
+                TrainedSkill::new(3446, "Broker Relations", 5),
+                TrainedSkill::new(12181, "Advanced Broker Relations", 5),
+            ],
+        };
+
+        // 0.015 * 10 * 990 * 0.2
+        assert_eq!(relist_fee(&order, 990.0, &skills).specific_round(2), 29.7);
+        // 0.015 * 10 * (1100 * 0.2 + 100)
+        assert_eq!(relist_fee(&order, 1100.0, &skills).specific_round(2), 48.0);
+    }
 }
@@ -1,10 +1,16 @@
 pub mod blueprint;
+pub mod calibration;
 pub mod character;
 pub mod common;
 pub mod facility;
+pub mod history;
+pub mod implants;
 pub mod industry;
 pub mod items;
 pub mod locations;
 pub mod markets;
+pub mod portfolio;
 pub mod prices;
+pub mod schedule;
 pub mod skills;
+pub mod watch;
@@ -0,0 +1,126 @@
+/// Item which can be manufactured with the available capital.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PortfolioCandidate {
+    pub name: String,
+    /// Capital tied up by a single run, materials and job installation included.
+    pub cost_per_run: f64,
+    /// Profit of a single run, once the products are sold.
+    pub profit_per_run: f64,
+    /// Duration of a single run, in seconds.
+    pub time_per_run: i64,
+    /// Maximum number of runs the markets can absorb over the planning horizon.
+    pub max_runs: i64,
+}
+
+impl PortfolioCandidate {
+    pub fn return_on_investment(&self) -> f64 {
+        self.profit_per_run / self.cost_per_run
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PortfolioEntry {
+    pub name: String,
+    pub runs: i64,
+    pub capital: f64,
+    pub profit: f64,
+    /// Slot time used by the runs, in seconds.
+    pub time: i64,
+}
+
+impl PortfolioEntry {
+    pub fn return_on_investment(&self) -> f64 {
+        self.profit / self.capital
+    }
+}
+
+/// Invests the budget in the candidates with the highest return on investment first, as long as
+/// their markets absorb the runs and manufacturing slot time (in seconds) remains.
+pub fn optimize(
+    candidates: &[PortfolioCandidate],
+    budget: f64,
+    slot_time: i64,
+) -> Vec<PortfolioEntry> {
+    let mut candidates: Vec<&PortfolioCandidate> = candidates
+        .iter()
+        .filter(|candidate| {
+            candidate.profit_per_run > 0.0
+                && candidate.cost_per_run > 0.0
+                && candidate.time_per_run > 0
+        })
+        .collect();
+    candidates.sort_by(|a, b| {
+        b.return_on_investment()
+            .total_cmp(&a.return_on_investment())
+    });
+
+    let mut remaining_budget = budget;
+    let mut remaining_time = slot_time;
+    let mut entries = vec![];
+    for candidate in candidates {
+        let affordable_runs = (remaining_budget / candidate.cost_per_run).floor() as i64;
+        let runs = candidate
+            .max_runs
+            .min(affordable_runs)
+            .min(remaining_time / candidate.time_per_run);
+        if runs <= 0 {
+            continue;
+        }
+        let entry = PortfolioEntry {
+            name: candidate.name.clone(),
+            runs,
+            capital: candidate.cost_per_run * (runs as f64),
+            profit: candidate.profit_per_run * (runs as f64),
+            time: candidate.time_per_run * runs,
+        };
+        remaining_budget -= entry.capital;
+        remaining_time -= entry.time;
+        entries.push(entry);
+    }
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_optimize() {
+        let candidates = vec![
+            PortfolioCandidate {
+                name: "Cheap".to_string(),
+                cost_per_run: 100.0,
+                profit_per_run: 50.0,
+                time_per_run: 3600,
+                max_runs: 3,
+            },
+            PortfolioCandidate {
+                name: "Expensive".to_string(),
+                cost_per_run: 1000.0,
+                profit_per_run: 200.0,
+                time_per_run: 3600,
+                max_runs: 10,
+            },
+            PortfolioCandidate {
+                name: "Loss".to_string(),
+                cost_per_run: 10.0,
+                profit_per_run: -5.0,
+                time_per_run: 60,
+                max_runs: 10,
+            },
+        ];
+
+        let entries = optimize(&candidates, 2500.0, 10 * 3600);
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].name, "Cheap");
+        assert_eq!(entries[0].runs, 3);
+        assert_eq!(entries[0].return_on_investment(), 0.5);
+        assert_eq!(entries[1].name, "Expensive");
+        assert_eq!(entries[1].runs, 2);
+        assert_eq!(entries[1].capital, 2000.0);
+
+        let entries = optimize(&candidates, 100_000.0, 5 * 3600);
+        assert_eq!(entries[1].runs, 2);
+    }
+}
@@ -0,0 +1,140 @@
+use chrono::{DateTime, Duration, Utc};
+
+/// Item which can be queued in a manufacturing slot.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduleCandidate {
+    pub name: String,
+    /// Profit of a single run, once the products are sold.
+    pub profit_per_run: f64,
+    /// Duration of a single run, in seconds.
+    pub time_per_run: i64,
+    /// Maximum number of runs the markets can absorb over the planning horizon.
+    pub max_runs: i64,
+}
+
+impl ScheduleCandidate {
+    pub fn profit_per_second(&self) -> f64 {
+        self.profit_per_run / (self.time_per_run as f64)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduledJob {
+    pub name: String,
+    pub runs: i64,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub profit: f64,
+}
+
+/// Queue of jobs of a single manufacturing slot.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotSchedule {
+    /// Date at which the slot is freed from the already running job.
+    pub free_from: DateTime<Utc>,
+    pub jobs: Vec<ScheduledJob>,
+}
+
+impl SlotSchedule {
+    fn available_from(&self) -> DateTime<Utc> {
+        self.jobs
+            .last()
+            .map(|job| job.end)
+            .unwrap_or(self.free_from)
+    }
+}
+
+/// Queues the most profitable candidates per hour first, each in the slot freed first, as long as
+/// runs can end before `horizon_end` and their markets can absorb them.
+pub fn schedule(
+    candidates: &[ScheduleCandidate],
+    slots_free_from: &[DateTime<Utc>],
+    horizon_end: DateTime<Utc>,
+) -> Vec<SlotSchedule> {
+    let mut slots: Vec<SlotSchedule> = slots_free_from
+        .iter()
+        .map(|free_from| SlotSchedule {
+            free_from: *free_from,
+            jobs: vec![],
+        })
+        .collect();
+
+    let mut candidates: Vec<&ScheduleCandidate> = candidates
+        .iter()
+        .filter(|candidate| candidate.profit_per_run > 0.0 && candidate.time_per_run > 0)
+        .collect();
+    candidates.sort_by(|a, b| b.profit_per_second().total_cmp(&a.profit_per_second()));
+
+    for candidate in candidates {
+        let mut remaining_runs = candidate.max_runs;
+        while remaining_runs > 0 {
+            let slot = match slots.iter_mut().min_by_key(|slot| slot.available_from()) {
+                Some(slot) => slot,
+                None => break,
+            };
+            let start = slot.available_from();
+            let fitting_runs = (horizon_end - start).num_seconds() / candidate.time_per_run;
+            // No slot is freed earlier, the item doesn't fit in the horizon anymore.
+            if fitting_runs <= 0 {
+                break;
+            }
+            let runs = remaining_runs.min(fitting_runs);
+            slot.jobs.push(ScheduledJob {
+                name: candidate.name.clone(),
+                runs,
+                start,
+                end: start + Duration::seconds(runs * candidate.time_per_run),
+                profit: candidate.profit_per_run * (runs as f64),
+            });
+            remaining_runs -= runs;
+        }
+    }
+    slots
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn test_schedule() {
+        let now = Utc.with_ymd_and_hms(2024, 2, 3, 0, 0, 0).unwrap();
+        let hours = |hours| now + Duration::hours(hours);
+        let candidates = vec![
+            ScheduleCandidate {
+                name: "Slow".to_string(),
+                profit_per_run: 1000.0,
+                time_per_run: 3600,
+                max_runs: 100,
+            },
+            ScheduleCandidate {
+                name: "Fast".to_string(),
+                profit_per_run: 2000.0,
+                time_per_run: 3600,
+                max_runs: 6,
+            },
+            ScheduleCandidate {
+                name: "Loss".to_string(),
+                profit_per_run: -10.0,
+                time_per_run: 60,
+                max_runs: 100,
+            },
+        ];
+
+        let slots = schedule(&candidates, &[now, hours(4)], hours(10));
+
+        assert_eq!(slots[0].jobs.len(), 2);
+        assert_eq!(slots[0].jobs[0].name, "Fast");
+        assert_eq!(slots[0].jobs[0].runs, 6);
+        assert_eq!(slots[0].jobs[0].end, hours(6));
+        assert_eq!(slots[0].jobs[1].name, "Slow");
+        assert_eq!(slots[0].jobs[1].start, hours(6));
+        assert_eq!(slots[0].jobs[1].runs, 4);
+        assert_eq!(slots[1].jobs.len(), 1);
+        assert_eq!(slots[1].jobs[0].start, hours(4));
+        assert_eq!(slots[1].jobs[0].runs, 6);
+        assert_eq!(slots[1].jobs[0].profit, 6000.0);
+    }
+}
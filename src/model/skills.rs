@@ -1,5 +1,7 @@
 use super::common::{Identified, Named};
 
+pub const MAX_SKILL_LEVEL: i32 = 5;
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct Skill {
     id: i32,
@@ -56,3 +58,180 @@ impl Named for TrainedSkill {
         self.skill.name()
     }
 }
+
+/// Skill level required to start an industry job.
+#[derive(Debug, PartialEq, Clone)]
+pub struct RequiredSkill {
+    skill: Skill,
+    pub level: i32,
+}
+
+impl RequiredSkill {
+    pub fn new(id: i32, name: &str, level: i32) -> Self {
+        Self {
+            skill: Skill::new(id, name),
+            level,
+        }
+    }
+}
+
+impl Identified<i32> for RequiredSkill {
+    fn id(&self) -> i32 {
+        self.skill.id()
+    }
+}
+
+impl Named for RequiredSkill {
+    fn name(&self) -> String {
+        self.skill.name()
+    }
+}
+
+/// Required skill the character hasn't trained to the required level.
+#[derive(Debug, PartialEq, Clone)]
+pub struct MissingSkill {
+    pub name: String,
+    pub required_level: i32,
+    pub trained_level: i32,
+}
+
+/// Character attribute, the primary and secondary attributes of a skill speed up its training.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Attribute {
+    Charisma,
+    Intelligence,
+    Memory,
+    Perception,
+    Willpower,
+}
+
+impl Attribute {
+    /// Attributes are referenced by their dogma attribute ID in the skills dogma.
+    pub fn from_dogma_id(id: i32) -> Option<Self> {
+        match id {
+            164 => Some(Attribute::Charisma),
+            165 => Some(Attribute::Intelligence),
+            166 => Some(Attribute::Memory),
+            167 => Some(Attribute::Perception),
+            168 => Some(Attribute::Willpower),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Attributes {
+    pub charisma: i32,
+    pub intelligence: i32,
+    pub memory: i32,
+    pub perception: i32,
+    pub willpower: i32,
+}
+
+impl Attributes {
+    pub fn get(&self, attribute: Attribute) -> i32 {
+        match attribute {
+            Attribute::Charisma => self.charisma,
+            Attribute::Intelligence => self.intelligence,
+            Attribute::Memory => self.memory,
+            Attribute::Perception => self.perception,
+            Attribute::Willpower => self.willpower,
+        }
+    }
+}
+
+/// Training properties of a skill.
+/// https://wiki.eveuniversity.org/Skills_and_learning
+#[derive(Debug, PartialEq, Clone)]
+pub struct SkillTraining {
+    skill: Skill,
+    pub rank: f64,
+    pub primary_attribute: Attribute,
+    pub secondary_attribute: Attribute,
+}
+
+impl SkillTraining {
+    pub fn new(
+        id: i32,
+        name: &str,
+        rank: f64,
+        primary_attribute: Attribute,
+        secondary_attribute: Attribute,
+    ) -> Self {
+        Self {
+            skill: Skill::new(id, name),
+            rank,
+            primary_attribute,
+            secondary_attribute,
+        }
+    }
+
+    /// Skill points needed to train the skill up to `level`.
+    pub fn skill_points(&self, level: i32) -> f64 {
+        if level <= 0 {
+            return 0.0;
+        }
+        250.0 * self.rank * 32.0_f64.sqrt().powi(level - 1)
+    }
+
+    /// Time to train the skill from `level - 1` to `level`, in seconds.
+    pub fn training_time(&self, level: i32, attributes: &Attributes) -> i64 {
+        let skill_points = self.skill_points(level) - self.skill_points(level - 1);
+        let skill_points_per_minute = attributes.get(self.primary_attribute) as f64
+            + (attributes.get(self.secondary_attribute) as f64) / 2.0;
+        (skill_points / skill_points_per_minute * 60.0) as i64
+    }
+}
+
+impl Identified<i32> for SkillTraining {
+    fn id(&self) -> i32 {
+        self.skill.id()
+    }
+}
+
+impl Named for SkillTraining {
+    fn name(&self) -> String {
+        self.skill.name()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skill_points() {
+        let training = SkillTraining::new(
+            3388,
+            "Advanced Industry",
+            3.0,
+            Attribute::Memory,
+            Attribute::Intelligence,
+        );
+
+        assert_eq!(training.skill_points(0), 0.0);
+        assert_eq!(training.skill_points(1), 750.0);
+        assert_eq!(training.skill_points(5).round(), 768000.0);
+    }
+
+    #[test]
+    fn test_training_time() {
+        let training = SkillTraining::new(
+            3388,
+            "Advanced Industry",
+            3.0,
+            Attribute::Memory,
+            Attribute::Intelligence,
+        );
+        let attributes = Attributes {
+            charisma: 17,
+            intelligence: 20,
+            memory: 20,
+            perception: 20,
+            willpower: 20,
+        };
+
+        // 750 SP at 30 SP per minute
+        assert_eq!(training.training_time(1, &attributes), 1500);
+    }
+}
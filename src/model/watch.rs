@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+
+/// Condition on an item checked by `watch run`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct WatchRule {
+    pub item_id: i32,
+    pub metric: WatchMetric,
+    pub comparison: Comparison,
+    pub threshold: f64,
+}
+
+impl WatchRule {
+    pub fn is_triggered(&self, value: f64) -> bool {
+        match self.comparison {
+            Comparison::Above => value > self.threshold,
+            Comparison::Below => value < self.threshold,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WatchMetric {
+    /// Highest profits per hour over the registered manufacturing facilities and markets.
+    ProfitPerHour,
+    /// Lowest sell price at the market.
+    SellPrice { market_id: i64 },
+    /// Number of sell orders at the market.
+    Competitors { market_id: i64 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Comparison {
+    Above,
+    Below,
+}
+
+/// Remembers which rules were triggered at the last check, so an alert is only raised when a rule
+/// starts being triggered.
+#[derive(Debug, Default)]
+pub struct WatchTracker {
+    triggered: Vec<(WatchRule, bool)>,
+}
+
+impl WatchTracker {
+    /// Records the state of the rule, and returns whether it just started being triggered. Rules
+    /// without value keep their previous state.
+    pub fn update(&mut self, rule: &WatchRule, triggered: Option<bool>) -> bool {
+        let triggered = match triggered {
+            Some(triggered) => triggered,
+            None => return false,
+        };
+        match self
+            .triggered
+            .iter_mut()
+            .find(|(tracked, _)| tracked == rule)
+        {
+            Some((_, was_triggered)) => {
+                let started = triggered && !*was_triggered;
+                *was_triggered = triggered;
+                started
+            }
+            None => {
+                self.triggered.push((rule.clone(), triggered));
+                triggered
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_triggered() {
+        let rule = WatchRule {
+            item_id: 2048,
+            metric: WatchMetric::SellPrice { market_id: 1 },
+            comparison: Comparison::Below,
+            threshold: 1000.0,
+        };
+
+        assert!(rule.is_triggered(999.0));
+        assert!(!rule.is_triggered(1000.0));
+        assert!(!rule.is_triggered(1001.0));
+    }
+
+    #[test]
+    fn test_tracker_alerts_once() {
+        let rule = |item_id| WatchRule {
+            item_id,
+            metric: WatchMetric::ProfitPerHour,
+            comparison: Comparison::Above,
+            threshold: 1000000.0,
+        };
+        let mut tracker = WatchTracker::default();
+
+        assert!(!tracker.update(&rule(1), Some(false)));
+        assert!(tracker.update(&rule(1), Some(true)));
+        assert!(!tracker.update(&rule(1), Some(true)));
+        assert!(!tracker.update(&rule(1), None));
+        assert!(!tracker.update(&rule(1), Some(false)));
+        assert!(tracker.update(&rule(1), Some(true)));
+        assert!(tracker.update(&rule(2), Some(true)));
+    }
+}
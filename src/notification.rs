@@ -0,0 +1,209 @@
+use std::path::PathBuf;
+use std::process::ExitStatus;
+
+use chrono::Local;
+use serde::Serialize;
+use thiserror::Error;
+use tokio::io::AsyncWriteExt;
+
+use crate::configuration::{Configuration, ConfigurationError};
+use crate::display::Display;
+use crate::logging;
+
+#[derive(Debug, Error)]
+pub enum NotificationError {
+    #[error("Notification command is empty")]
+    EmptyCommand,
+    #[error("Could not run notification command '{command}': {source}")]
+    CommandFailed {
+        command: String,
+        source: std::io::Error,
+    },
+    #[error("Notification command '{command}' exited with {status}")]
+    CommandUnsuccessful { command: String, status: ExitStatus },
+    #[error("Could not serialize notification: {source}")]
+    Serialization { source: serde_json::Error },
+    #[error("Could not send notification to webhook '{url}': {source}")]
+    WebhookFailed { url: String, source: reqwest::Error },
+    #[error("Could not write notification to log file '{path}': {source}")]
+    LogFileFailed {
+        path: String,
+        source: std::io::Error,
+    },
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Notification {
+    pub title: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Notifier {
+    /// Runs the command with the notification title and message as last arguments, such as
+    /// `notify-send`.
+    Command(String),
+    /// Posts the notification as JSON to the URL.
+    Webhook(String),
+    /// Appends the notification as a line of the file.
+    LogFile(PathBuf),
+}
+
+impl Notifier {
+    /// Notifiers set in the configuration, stdout excluded.
+    pub fn from_configuration(
+        cfg: &impl Configuration,
+    ) -> Result<Vec<Notifier>, ConfigurationError> {
+        let mut notifiers = vec![];
+        if let Some(command) = cfg.notification_command()? {
+            notifiers.push(Notifier::Command(command));
+        }
+        if let Some(url) = cfg.notification_webhook_url()? {
+            notifiers.push(Notifier::Webhook(url));
+        }
+        if let Some(path) = cfg.notification_log_file()? {
+            notifiers.push(Notifier::LogFile(path));
+        }
+        Ok(notifiers)
+    }
+
+    pub async fn notify(&self, notification: &Notification) -> Result<(), NotificationError> {
+        match self {
+            Notifier::Command(command) => {
+                let mut args = command.split_whitespace();
+                let program = args.next().ok_or(NotificationError::EmptyCommand)?;
+                let status = tokio::process::Command::new(program)
+                    .args(args)
+                    .arg(&notification.title)
+                    .arg(&notification.message)
+                    .status()
+                    .await
+                    .map_err(|source| NotificationError::CommandFailed {
+                        command: command.clone(),
+                        source,
+                    })?;
+                if !status.success() {
+                    return Err(NotificationError::CommandUnsuccessful {
+                        command: command.clone(),
+                        status,
+                    });
+                }
+            }
+            Notifier::Webhook(url) => {
+                let body = serde_json::to_string(notification)
+                    .map_err(|source| NotificationError::Serialization { source })?;
+                reqwest::Client::new()
+                    .post(url)
+                    .header(reqwest::header::CONTENT_TYPE, "application/json")
+                    .body(body)
+                    .send()
+                    .await
+                    .and_then(|response| response.error_for_status())
+                    .map_err(|source| NotificationError::WebhookFailed {
+                        url: url.clone(),
+                        source,
+                    })?;
+            }
+            Notifier::LogFile(path) => {
+                let line = format!(
+                    "{} {}: {}\n",
+                    Local::now().format("%Y-%m-%d %H:%M:%S"),
+                    notification.title,
+                    notification.message
+                );
+                let to_error = |source| NotificationError::LogFileFailed {
+                    path: path.to_display(),
+                    source,
+                };
+                let mut file = tokio::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .await
+                    .map_err(to_error)?;
+                file.write_all(line.as_bytes()).await.map_err(to_error)?;
+                file.flush().await.map_err(to_error)?;
+            }
+        }
+        logging::debug!("Notification sent: {}", notification.title);
+        Ok(())
+    }
+}
+
+/// Sends the notification through every notifier. Failures are logged as warnings, so a single
+/// unreachable notifier doesn't stop the others.
+pub async fn notify_all(notifiers: &[Notifier], notification: &Notification) {
+    for notifier in notifiers {
+        if let Err(err) = notifier.notify(notification).await {
+            logging::warning!("{}", err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::matchers::{body_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+
+    fn notification() -> Notification {
+        Notification {
+            title: "Damage Control I".to_string(),
+            message: "Profits per hour above 1,000,000".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_webhook() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .and(body_json(serde_json::json!({
+                "title": "Damage Control I",
+                "message": "Profits per hour above 1,000,000",
+            })))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        Notifier::Webhook(format!("{}/hook", server.uri()))
+            .notify(&notification())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_webhook_error_status() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        let result = Notifier::Webhook(server.uri())
+            .notify(&notification())
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(NotificationError::WebhookFailed { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_log_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notifications.log");
+        let notifier = Notifier::LogFile(path.clone());
+
+        notifier.notify(&notification()).await.unwrap();
+        notifier.notify(&notification()).await.unwrap();
+
+        let content = std::fs::read_to_string(path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with("Damage Control I: Profits per hour above 1,000,000"));
+    }
+}
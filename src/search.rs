@@ -0,0 +1,78 @@
+/// Ranks how well `name` matches `query`, ignoring case. Lower is better: exact names come first,
+/// then names starting with the query, names containing it, and finally names containing it with a
+/// few typos. Returns `None` when `name` doesn't match.
+pub fn rank(query: &str, name: &str) -> Option<usize> {
+    let query = query.trim().to_lowercase();
+    let name = name.to_lowercase();
+    if query.is_empty() {
+        return None;
+    }
+    if name == query {
+        return Some(0);
+    }
+    if name.starts_with(&query) {
+        return Some(1);
+    }
+    if name.contains(&query) {
+        return Some(2);
+    }
+
+    let query: Vec<char> = query.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let distance = substring_distance(&query, &name);
+    if distance <= allowed_typos(query.len()) {
+        return Some(2 + distance);
+    }
+    None
+}
+
+/// Short queries must be typed correctly, longer ones tolerate one typo every four characters.
+fn allowed_typos(query_length: usize) -> usize {
+    query_length / 4
+}
+
+/// Smallest edit distance between `query` and any substring of `text`.
+fn substring_distance(query: &[char], text: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=query.len()).collect();
+    let mut best = query.len();
+    for t in text {
+        let mut current = vec![0; query.len() + 1];
+        for (i, q) in query.iter().enumerate() {
+            let substitution = previous[i] + usize::from(q != t);
+            current[i + 1] = substitution.min(previous[i + 1] + 1).min(current[i] + 1);
+        }
+        best = best.min(current[query.len()]);
+        previous = current;
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rank_orders_exact_prefix_and_substring_matches() {
+        assert_eq!(rank("hammerhead i", "Hammerhead I"), Some(0));
+        assert_eq!(rank("Hammerhead", "Hammerhead II"), Some(1));
+        assert_eq!(rank("head", "Hammerhead II"), Some(2));
+        assert_eq!(rank("Rifter", "Hammerhead II"), None);
+        assert_eq!(rank("  ", "Hammerhead II"), None);
+    }
+
+    #[test]
+    fn rank_tolerates_typos() {
+        assert_eq!(rank("hamerhead", "Hammerhead II"), Some(3));
+        assert_eq!(rank("hammerhaed", "Hammerhead II"), Some(4));
+        assert_eq!(rank("hmrhd", "Hammerhead II"), None);
+        assert_eq!(rank("rifer", "Rifter"), Some(3));
+        assert_eq!(rank("rfr", "Rifter"), None);
+    }
+
+    #[test]
+    fn substring_distance_ignores_surrounding_characters() {
+        let query: Vec<char> = "merhed".chars().collect();
+        let text: Vec<char> = "hammerhead".chars().collect();
+        assert_eq!(substring_distance(&query, &text), 1);
+    }
+}
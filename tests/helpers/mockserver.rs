@@ -41,6 +41,15 @@ pub async fn register_api_character_handlers(server: &MockServer, responses_fold
         .respond_with(ResponseTemplate::new(200).set_body_string(character_public_info_response))
         .mount(&server)
         .await;
+
+    let character_implants_response =
+        fs::read_to_string(character_api_responses.join("implants.json")).unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/api/v1/characters/123456789/implants/"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(character_implants_response))
+        .mount(&server)
+        .await;
 }
 
 pub async fn register_api_corporation_handlers(server: &MockServer, responses_folder: &Path) {
@@ -168,6 +177,15 @@ pub async fn register_api_skills_handlers(server: &MockServer, responses_folder:
         .respond_with(ResponseTemplate::new(200).set_body_string(character_skills_response))
         .mount(&server)
         .await;
+
+    let character_attributes_response =
+        fs::read_to_string(api_responses.join("character_attributes.json")).unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/api/v1/characters/123456789/attributes/"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(character_attributes_response))
+        .mount(&server)
+        .await;
 }
 
 pub async fn register_api_universe_handlers(server: &MockServer, responses_folder: &Path) {